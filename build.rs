@@ -37,11 +37,7 @@ fn gen_presets_hook(mut file: &File) -> SdResult<()> {
         match_arms.push_str(
             format!(
                 r#"
-"{name}" => {{
-        let stdout = io::stdout();
-        let mut stdout = stdout.lock();
-        let _ = stdout.write_all(include_bytes!(r"{full_path}"));
-}}
+"{name}" => Some(include_bytes!(r"{full_path}")),
 "#
             )
             .as_str(),
@@ -60,10 +56,18 @@ pub fn get_preset_list<'a>() -> &'a [print::Preset] {{
     ]
 }}
 
-pub fn print_preset_content(name: &str) {{
+pub fn get_preset_content(name: &str) -> Option<&'static [u8]> {{
     match name {{
     {match_arms}
-    _ => {{}}
+    _ => None,
+    }}
+}}
+
+pub fn print_preset_content(name: &str) {{
+    if let Some(content) = get_preset_content(name) {{
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        let _ = stdout.write_all(content);
     }}
 }}
 "#
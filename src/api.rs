@@ -0,0 +1,195 @@
+//! Rendering the prompt from other programs, like terminal emulators and editors, without
+//! running the `starship` binary
+//!
+//! This is the part of the library that is kept stable: everything else is what the binary
+//! is built from and may change in any release.
+//!
+//! ```no_run
+//! use starship::api::{render_prompt, user_config, RenderContext};
+//!
+//! let context = RenderContext {
+//!     path: "/home/user/project".into(),
+//!     status: Some(1),
+//!     ..Default::default()
+//! };
+//! for segment in render_prompt(user_config(), context) {
+//!     print!("{}", segment.text);
+//! }
+//! ```
+
+use crate::config::{color_to_string, ModuleConfig, StarshipConfig};
+use crate::configs::StarshipRootConfig;
+use crate::context::{Context, Properties, Shell};
+use crate::print::prompt_segments;
+use crate::segment::Segment as PromptSegment;
+use serde::Serialize;
+
+use std::path::PathBuf;
+
+pub use crate::context::Target;
+
+/// A piece of the prompt with a single style
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Segment {
+    /// The module the segment belongs to, if any
+    pub module: Option<String>,
+    pub text: String,
+    /// Whether the text repeats to fill the rest of the line
+    pub fill: bool,
+    /// The foreground color, as written in style strings, e.g. `red` or `#ff0000`
+    pub fg: Option<String>,
+    /// The background color, as written in style strings
+    pub bg: Option<String>,
+    pub bold: bool,
+    pub dimmed: bool,
+    pub italic: bool,
+    pub underline: bool,
+    /// The URL the text links to
+    pub link: Option<String>,
+}
+
+/// What the prompt is rendered for
+#[derive(Clone, Debug)]
+pub struct RenderContext {
+    /// The directory to render the prompt for
+    pub path: PathBuf,
+    /// Which prompt to render
+    pub target: Target,
+    /// The width of the terminal, in columns
+    pub width: usize,
+    /// The exit status of the last command
+    pub status: Option<i32>,
+    /// How long the last command ran for, in milliseconds
+    pub cmd_duration: Option<u128>,
+    /// The number of background jobs
+    pub jobs: i64,
+}
+
+impl Default for RenderContext {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::new(),
+            target: Target::Main,
+            width: 80,
+            status: None,
+            cmd_duration: None,
+            jobs: 0,
+        }
+    }
+}
+
+/// The configuration in the user's `starship.toml`, if there is one
+pub fn user_config() -> Option<toml::Value> {
+    StarshipConfig::initialize().config
+}
+
+/// Renders the prompt with a configuration, or the default one without it
+pub fn render_prompt(config: Option<toml::Value>, context: RenderContext) -> Vec<Segment> {
+    let mut properties = Properties::default();
+    properties.status_code = context.status.map(|status| status.to_string());
+    properties.cmd_duration = context.cmd_duration.map(|duration| duration.to_string());
+    properties.jobs = context.jobs;
+    let mut prompt_context = Context::new_with_shell_and_path(
+        properties,
+        Shell::Unknown,
+        context.target,
+        context.path.clone(),
+        context.path,
+    );
+    prompt_context.width = context.width;
+    prompt_context.root_config = config
+        .as_ref()
+        .map_or_else(StarshipRootConfig::default, StarshipRootConfig::load);
+    prompt_context.config = StarshipConfig { config };
+
+    segments(&prompt_context)
+}
+
+/// The segments of the prompt for a context, also used for `starship prompt --format=json`
+pub(crate) fn segments(context: &Context) -> Vec<Segment> {
+    let mut module = None;
+    prompt_segments(context, true)
+        .iter()
+        .filter_map(|segment| match segment {
+            PromptSegment::ModuleStart(name) => {
+                module = Some(name.clone());
+                None
+            }
+            PromptSegment::ModuleEnd => {
+                module = None;
+                None
+            }
+            PromptSegment::Separator => None,
+            PromptSegment::Text(_) | PromptSegment::Fill(_) | PromptSegment::LineTerm => {
+                let style = segment.style().unwrap_or_default();
+                Some(Segment {
+                    module: module.clone(),
+                    text: segment.value().to_string(),
+                    fill: matches!(segment, PromptSegment::Fill(_)),
+                    fg: style.foreground.and_then(color_to_string),
+                    bg: style.background.and_then(color_to_string),
+                    bold: style.is_bold,
+                    dimmed: style.is_dimmed,
+                    italic: style.is_italic,
+                    underline: style.is_underline,
+                    link: segment.link().map(str::to_string),
+                })
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_prompt_with_config() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let config = toml::toml! {
+            format = "$status$character"
+            [status]
+            disabled = false
+            format = "[$status]($style) "
+        };
+        let segments = render_prompt(
+            Some(config),
+            RenderContext {
+                path: dir.path().to_path_buf(),
+                status: Some(1),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            segments,
+            vec![
+                Segment {
+                    module: Some("status".to_string()),
+                    text: "1".to_string(),
+                    fg: Some("red".to_string()),
+                    bold: true,
+                    ..Default::default()
+                },
+                Segment {
+                    module: Some("status".to_string()),
+                    text: " ".to_string(),
+                    ..Default::default()
+                },
+                Segment {
+                    module: Some("character".to_string()),
+                    text: "❯".to_string(),
+                    fg: Some("red".to_string()),
+                    bold: true,
+                    ..Default::default()
+                },
+                Segment {
+                    module: Some("character".to_string()),
+                    text: " ".to_string(),
+                    ..Default::default()
+                },
+            ]
+        );
+        dir.close()
+    }
+}
@@ -0,0 +1,190 @@
+use crate::context::{Context, Properties, Shell, Target};
+use crate::module::ALL_MODULES;
+use crate::modules;
+use crate::print::{compute_modules, format_duration};
+use clap::Args;
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How to benchmark a module, or the whole prompt
+#[derive(Args, Debug)]
+pub struct BenchOptions {
+    /// The module to render, the whole prompt is rendered without it
+    module: Option<String>,
+    /// How many times to render
+    #[clap(short = 'n', long, default_value_t = 100)]
+    iterations: usize,
+    /// Files to create in the temporary directory the module is rendered in, e.g. `Cargo.toml`
+    #[clap(short, long)]
+    file: Vec<String>,
+    /// Render in this directory instead of a temporary one
+    #[clap(short, long, conflicts_with = "file")]
+    path: Option<PathBuf>,
+    /// Print the results as JSON, e.g. to compare them in CI
+    #[clap(long)]
+    json: bool,
+}
+
+/// Latency statistics of the rendered iterations
+#[derive(Debug, PartialEq, Eq)]
+struct Summary {
+    mean: Duration,
+    min: Duration,
+    p50: Duration,
+    p90: Duration,
+    p99: Duration,
+    max: Duration,
+}
+
+impl Summary {
+    fn new(mut durations: Vec<Duration>) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+        durations.sort_unstable();
+        let total: Duration = durations.iter().sum();
+        Some(Self {
+            mean: total / u32::try_from(durations.len()).ok()?,
+            min: durations[0],
+            p50: percentile(&durations, 50),
+            p90: percentile(&durations, 90),
+            p99: percentile(&durations, 99),
+            max: durations[durations.len() - 1],
+        })
+    }
+}
+
+/// The nearest-rank percentile of sorted durations
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (sorted.len() * percent + 99) / 100;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+pub fn bench(options: BenchOptions) {
+    if let Some(module) = &options.module {
+        if !ALL_MODULES.contains(&module.as_str()) {
+            eprintln!("Error: Unknown module {module}. Use starship module --list to list out all supported modules.");
+            std::process::exit(1);
+        }
+    }
+
+    // Keep the temporary directory around until all iterations are done
+    let fixture = match &options.path {
+        Some(_) => None,
+        None => Some(tempfile::tempdir().expect("Unable to create a temporary directory")),
+    };
+    let path = match (&options.path, &fixture) {
+        (Some(path), _) => path.clone(),
+        (None, Some(fixture)) => {
+            for file in &options.file {
+                let file = fixture.path().join(file);
+                if let Some(parent) = file.parent() {
+                    let _ = fs::create_dir_all(parent);
+                }
+                fs::File::create(&file)
+                    .unwrap_or_else(|err| panic!("Unable to create {file:?}: {err:?}"));
+            }
+            fixture.path().to_path_buf()
+        }
+        (None, None) => unreachable!(),
+    };
+
+    let durations = (0..options.iterations)
+        .map(|_| {
+            let context = Context::new_with_shell_and_path(
+                Properties::default(),
+                Shell::Unknown,
+                Target::Main,
+                path.clone(),
+                path.clone(),
+            );
+            let start = Instant::now();
+            match &options.module {
+                Some(module) => drop(modules::handle(module, &context)),
+                None => drop(compute_modules(&context)),
+            }
+            start.elapsed()
+        })
+        .collect();
+
+    let Some(summary) = Summary::new(durations) else {
+        eprintln!("Error: At least one iteration is needed");
+        std::process::exit(1);
+    };
+    let name = options.module.as_deref().unwrap_or("prompt");
+
+    if options.json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "module": name,
+                "iterations": options.iterations,
+                "mean_us": summary.mean.as_micros() as u64,
+                "min_us": summary.min.as_micros() as u64,
+                "p50_us": summary.p50.as_micros() as u64,
+                "p90_us": summary.p90.as_micros() as u64,
+                "p99_us": summary.p99.as_micros() as u64,
+                "max_us": summary.max.as_micros() as u64,
+            })
+        );
+        return;
+    }
+
+    println!(
+        "\n Rendered {name} {} times in {}:",
+        options.iterations,
+        path.display()
+    );
+    for (label, duration) in [
+        ("mean", summary.mean),
+        ("min", summary.min),
+        ("p50", summary.p50),
+        ("p90", summary.p90),
+        ("p99", summary.p99),
+        ("max", summary.max),
+    ] {
+        println!(" {label:<4}  -  {}", format_bench_duration(&duration));
+    }
+}
+
+/// Module renders are often faster than a millisecond, so show microseconds below that
+fn format_bench_duration(duration: &Duration) -> String {
+    if duration.as_millis() > 0 {
+        format_duration(duration)
+    } else {
+        format!("{}µs", duration.as_micros())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_of_durations() {
+        let durations = (1..=100).rev().map(Duration::from_millis).collect();
+
+        assert_eq!(
+            Summary::new(durations),
+            Some(Summary {
+                mean: Duration::from_micros(50500),
+                min: Duration::from_millis(1),
+                p50: Duration::from_millis(50),
+                p90: Duration::from_millis(90),
+                p99: Duration::from_millis(99),
+                max: Duration::from_millis(100),
+            })
+        );
+        assert_eq!(Summary::new(vec![]), None);
+    }
+
+    #[test]
+    fn percentile_of_few_durations() {
+        let durations = [Duration::from_millis(1), Duration::from_millis(2)];
+
+        assert_eq!(percentile(&durations, 50), Duration::from_millis(1));
+        assert_eq!(percentile(&durations, 99), Duration::from_millis(2));
+    }
+}
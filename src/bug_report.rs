@@ -1,25 +1,73 @@
+use crate::context::{Context, Properties, Target};
+use crate::print::{compute_modules, format_duration};
 use crate::shadow;
 use crate::utils::{self, exec_cmd};
+use clap::Args;
 use nu_ansi_term::Style;
+use regex::Regex;
 
 use std::fs;
 use std::path::PathBuf;
 use std::time::Duration;
 
-pub fn create() {
-    println!("{}\n", shadow::VERSION.trim());
+/// What to add to the generated bug report
+#[derive(Args, Debug, Default)]
+pub struct ReportOptions {
+    /// Replace secrets in the configuration, the home directory and the user name
+    #[clap(long)]
+    redact: bool,
+    /// Add how long each module of the prompt takes to render
+    #[clap(long)]
+    timings: bool,
+    /// Add what each module of the prompt shows, like `starship explain`
+    #[clap(long)]
+    explain: bool,
+    /// Only print the report, to paste it into an issue, instead of offering to open GitHub
+    #[clap(long)]
+    print: bool,
+}
+
+pub fn create(options: ReportOptions) {
+    if !options.print {
+        println!("{}\n", shadow::VERSION.trim());
+    }
     let os_info = os_info::get();
 
-    let environment = Environment {
+    let (timings, explain) = if options.timings || options.explain {
+        let (timings, explain) = get_module_report();
+        (
+            Some(timings).filter(|_| options.timings),
+            Some(explain).filter(|_| options.explain),
+        )
+    } else {
+        (None, None)
+    };
+
+    let mut environment = Environment {
         os_type: os_info.os_type(),
         os_version: os_info.version().clone(),
         shell_info: get_shell_info(),
         terminal_info: get_terminal_info(),
         starship_config: get_starship_config(),
+        timings,
+        explain,
     };
 
+    if options.redact {
+        let redactor = Redactor::new(
+            utils::home_dir().map(|home| home.to_string_lossy().to_string()),
+            std::env::var(if cfg!(windows) { "USERNAME" } else { "USER" }).ok(),
+        );
+        environment.redact(&redactor);
+    }
+
     let issue_body = get_github_issue_body(&environment);
 
+    if options.print {
+        println!("{issue_body}");
+        return;
+    }
+
     println!(
         "{}\n{issue_body}\n\n",
         Style::new().bold().paint("Generated bug report:")
@@ -59,6 +107,94 @@ struct Environment {
     shell_info: ShellInfo,
     terminal_info: TerminalInfo,
     starship_config: String,
+    timings: Option<String>,
+    explain: Option<String>,
+}
+
+impl Environment {
+    fn redact(&mut self, redactor: &Redactor) {
+        self.shell_info.config = redactor.redact(&self.shell_info.config);
+        self.starship_config = redactor.redact(&self.starship_config);
+        for section in [&mut self.timings, &mut self.explain].into_iter().flatten() {
+            *section = redactor.redact(section);
+        }
+    }
+}
+
+/// Hides what should not end up in a public issue: the values of keys that look like
+/// secrets, the home directory and the name of the user
+struct Redactor {
+    secrets: Regex,
+    home: Option<String>,
+    user: Option<String>,
+}
+
+impl Redactor {
+    fn new(home: Option<String>, user: Option<String>) -> Self {
+        Self {
+            secrets: Regex::new(
+                r#"(?im)^(\s*(?:export\s+|set\s+-gx\s+|\$env:)?[\w.-]*(?:token|secret|password|passwd|api_?key|auth)[\w.-]*\s*[=\s]\s*).+$"#,
+            )
+            .unwrap(),
+            home: home.filter(|home| home.len() > 1),
+            user: user.filter(|user| !user.is_empty()),
+        }
+    }
+
+    fn redact(&self, text: &str) -> String {
+        let mut text = self
+            .secrets
+            .replace_all(text, "${1}\"<redacted>\"")
+            .to_string();
+        if let Some(home) = &self.home {
+            text = text.replace(home.as_str(), "~");
+        }
+        if let Some(user) = &self.user {
+            let user = Regex::new(&format!(r"\b{}\b", regex::escape(user))).unwrap();
+            text = user.replace_all(&text, "<user>").to_string();
+        }
+        text
+    }
+}
+
+/// The timings and the output of the modules in the prompt, without colors
+fn get_module_report() -> (String, String) {
+    let context = Context::new(Properties::default(), Target::Main);
+    let mut modules: Vec<_> = compute_modules(&context)
+        .into_iter()
+        .filter(|module| module.get_name() != "line_break")
+        .collect();
+
+    let explain = modules
+        .iter()
+        .filter(|module| !module.is_empty())
+        .map(|module| {
+            format!(
+                "{} \"{}\" ({})  -  {}",
+                module.get_name(),
+                module.get_segments().join(""),
+                format_duration(&module.duration),
+                module.get_description()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    modules.sort_by_key(|module| std::cmp::Reverse(module.duration));
+    let timings = modules
+        .iter()
+        .filter(|module| !module.is_empty() || module.duration.as_millis() > 0)
+        .map(|module| {
+            format!(
+                "{}  -  {}",
+                module.get_name(),
+                format_duration(&module.duration)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (timings, explain)
 }
 
 fn get_pkg_branch_tag() -> &'static str {
@@ -78,6 +214,16 @@ fn get_github_issue_body(environment: &Environment) -> String {
         _ => "bash",
     };
 
+    let mut modules = String::new();
+    if let Some(timings) = &environment.timings {
+        modules.push_str(&format!("\n\n#### Module Timings\n\n```\n{timings}\n```"));
+    }
+    if let Some(explain) = &environment.explain {
+        modules.push_str(&format!(
+            "\n\n#### Prompt Explanation\n\n```\n{explain}\n```"
+        ));
+    }
+
     format!("#### Current Behavior
 <!-- A clear and concise description of the behavior. -->
 
@@ -110,7 +256,7 @@ fn get_github_issue_body(environment: &Environment) -> String {
 
 ```toml
 {starship_config}
-```",
+```{modules}",
         starship_version = shadow::PKG_VERSION,
         shell_name = environment.shell_info.name,
         shell_version = environment.shell_info.version,
@@ -199,7 +345,7 @@ fn get_terminal_info() -> TerminalInfo {
     }
 }
 
-fn get_config_path(shell: &str) -> Option<PathBuf> {
+pub(crate) fn get_config_path(shell: &str) -> Option<PathBuf> {
     if shell == "nu" {
         return dirs_next::config_dir()
             .map(|config_dir| config_dir.join("nushell").join("config.nu"));
@@ -221,6 +367,10 @@ fn get_config_path(shell: &str) -> Option<PathBuf> {
             "elvish" => Some(".elvish/rc.elv"),
             "tcsh" => Some(".tcshrc"),
             "xonsh" => Some(".xonshrc"),
+            "murex" => Some(".murex_profile"),
+            "osh" => Some(".config/oils/oshrc"),
+            "ysh" => Some(".config/oils/yshrc"),
+            "rc" => Some(".rcrc"),
             "cmd" => Some("AppData/Local/clink/starship.lua"),
             _ => None,
         }
@@ -278,6 +428,8 @@ mod tests {
                 version: "5.6.7".to_string(),
             },
             starship_config: "No Starship config".to_string(),
+            timings: None,
+            explain: None,
         };
 
         let body = get_github_issue_body(&environment);
@@ -292,6 +444,53 @@ mod tests {
         assert!(link.contains("No+Starship+config"));
     }
 
+    #[test]
+    fn test_report_with_modules() {
+        let environment = Environment {
+            os_type: os_info::Type::Linux,
+            os_version: os_info::Version::Semantic(1, 2, 3),
+            shell_info: ShellInfo {
+                name: "test_shell".to_string(),
+                version: "2.3.4".to_string(),
+                config: "No config".to_string(),
+            },
+            terminal_info: TerminalInfo {
+                name: "test_terminal".to_string(),
+                version: "5.6.7".to_string(),
+            },
+            starship_config: "No Starship config".to_string(),
+            timings: Some("git_status  -  12ms".to_string()),
+            explain: None,
+        };
+
+        let body = get_github_issue_body(&environment);
+
+        assert!(body.ends_with("#### Module Timings\n\n```\ngit_status  -  12ms\n```"));
+        assert!(!body.contains("#### Prompt Explanation"));
+    }
+
+    #[test]
+    fn test_redact() {
+        let redactor = Redactor::new(
+            Some("/home/astronaut".to_string()),
+            Some("astronaut".to_string()),
+        );
+        let config = "[custom.weather]\n\
+                      api_key = \"abc123\"\n\
+                      command = \"/home/astronaut/bin/weather\"\n\
+                      export GITHUB_TOKEN=ghp_secret\n\
+                      format = \"astronaut@$hostname \"";
+
+        assert_eq!(
+            redactor.redact(config),
+            "[custom.weather]\n\
+             api_key = \"<redacted>\"\n\
+             command = \"~/bin/weather\"\n\
+             export GITHUB_TOKEN=\"<redacted>\"\n\
+             format = \"<user>@$hostname \""
+        );
+    }
+
     #[test]
     #[cfg(not(windows))]
     fn test_get_config_path() {
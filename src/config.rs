@@ -113,6 +113,18 @@ where
     }
 }
 
+/// A file whose contents match `regex`, which shows a module with `detect_content`
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+pub struct DetectContent<'a> {
+    pub file: &'a str,
+    pub regex: &'a str,
+}
+
 /// Root config of starship.
 pub struct StarshipConfig {
     pub config: Option<Value>,
@@ -133,17 +145,186 @@ pub fn get_config_path() -> Option<String> {
     }
 }
 
+/// Merges the tables of `os_overrides` and `host_overrides` that match the current OS and
+/// hostname into the config, so that one config file can be shared between machines
+///
+/// `os_overrides` are keyed by the OS as Rust names it, e.g. `linux`, `macos` or `windows`,
+/// and `host_overrides` by hostname patterns, where `*` matches any characters. The host
+/// overrides are merged last, in the order of the config file.
+fn with_overrides(mut config: Value, os: &str, hostname: Option<&str>) -> Value {
+    let Some(table) = config.as_table_mut() else {
+        return config;
+    };
+    let os_overrides = table.remove("os_overrides");
+    let host_overrides = table.remove("host_overrides");
+
+    let mut overrides = Vec::new();
+    if let Some(Value::Table(mut os_overrides)) = os_overrides {
+        overrides.extend(os_overrides.remove(os));
+    }
+    if let (Some(Value::Table(host_overrides)), Some(hostname)) = (host_overrides, hostname) {
+        overrides.extend(
+            host_overrides
+                .into_iter()
+                .filter(|(pattern, _)| matches_hostname(pattern, hostname))
+                .map(|(_, config)| config),
+        );
+    }
+
+    for config in overrides {
+        match config {
+            Value::Table(config) => merge_overrides(table, config),
+            config => log::warn!("Config overrides must be tables, got {}", config),
+        }
+    }
+    config
+}
+
+/// Sets the values of `overrides` in `config`, recursing into tables
+fn merge_overrides(config: &mut toml::value::Table, overrides: toml::value::Table) {
+    for (key, value) in overrides {
+        match (config.get_mut(&key), value) {
+            (Some(Value::Table(table)), Value::Table(overrides)) => {
+                merge_overrides(table, overrides)
+            }
+            (_, value) => {
+                config.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Whether `hostname` matches `pattern`, ignoring case, where `*` matches any characters
+fn matches_hostname(pattern: &str, hostname: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let hostname = hostname.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = hostname.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<_> = parts.collect();
+    // Without a `*`, the whole hostname has to match
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Fills in the symbols of the configured `symbol_set` for the options that aren't set
+///
+/// The symbols come from the presets of the same name, so that `nerd-font` and `ascii`
+/// don't need to be kept in sync with every module separately. `unicode` is what the
+/// modules default to, and the opt-in `auto` is resolved with `detect`.
+fn with_symbol_set(mut config: Value, detect: fn() -> &'static str) -> Value {
+    let symbol_set = config
+        .get("symbol_set")
+        .and_then(Value::as_str)
+        .unwrap_or("unicode");
+    let symbol_set = match symbol_set {
+        "auto" => detect(),
+        symbol_set => symbol_set,
+    };
+
+    let preset = match symbol_set {
+        "nerd-font" => "nerd-font-symbols",
+        "ascii" => "plain-text-symbols",
+        "unicode" => return config,
+        _ => {
+            log::warn!(
+                "Unknown symbol_set {:?}, expected one of \"auto\", \"nerd-font\", \"unicode\" or \"ascii\"",
+                symbol_set
+            );
+            return config;
+        }
+    };
+
+    let symbols = crate::shadow::get_preset_content(preset)
+        .and_then(|content| std::str::from_utf8(content).ok())
+        .and_then(|content| toml::from_str::<toml::value::Table>(content).ok());
+    if let (Some(config), Some(symbols)) = (config.as_table_mut(), symbols) {
+        merge_defaults(config, symbols);
+    }
+    config
+}
+
+/// Inserts the values of `defaults` that aren't set in `config`, recursing into tables
+fn merge_defaults(config: &mut toml::value::Table, defaults: toml::value::Table) {
+    for (key, default) in defaults {
+        match (config.get_mut(&key), default) {
+            (None, default) => {
+                config.insert(key, default);
+            }
+            (Some(Value::Table(table)), Value::Table(defaults)) => merge_defaults(table, defaults),
+            _ => (),
+        }
+    }
+}
+
+/// Guesses which symbols the terminal can show, for `symbol_set = "auto"`
+///
+/// `STARSHIP_SYMBOL_SET` takes precedence, as whether a Nerd Font is installed can't be
+/// detected. Otherwise, the Linux console and non UTF-8 locales fall back to `ascii`.
+fn detect_symbol_set() -> &'static str {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty());
+
+    resolve_symbol_set(
+        env::var("STARSHIP_SYMBOL_SET").ok().as_deref(),
+        env::var("TERM").ok().as_deref(),
+        locale.as_deref(),
+    )
+}
+
+fn resolve_symbol_set(
+    symbol_set: Option<&str>,
+    term: Option<&str>,
+    locale: Option<&str>,
+) -> &'static str {
+    match symbol_set {
+        Some("nerd-font") => return "nerd-font",
+        Some("unicode") => return "unicode",
+        Some("ascii") => return "ascii",
+        _ => (),
+    }
+
+    if matches!(term, Some("linux" | "vt100" | "vt220")) {
+        return "ascii";
+    }
+
+    match locale {
+        // The C locale is often set without meaning that the terminal isn't UTF-8
+        Some(locale) if locale != "C" && locale != "POSIX" => {
+            let locale = locale.to_ascii_lowercase();
+            if locale.contains("utf-8") || locale.contains("utf8") {
+                "unicode"
+            } else {
+                "ascii"
+            }
+        }
+        _ => "unicode",
+    }
+}
+
 impl StarshipConfig {
     /// Initialize the Config struct
     pub fn initialize() -> Self {
-        if let Some(file_data) = Self::config_from_file() {
-            Self {
-                config: Some(file_data),
-            }
-        } else {
-            Self {
-                config: Some(Value::Table(toml::value::Table::new())),
-            }
+        let config =
+            Self::config_from_file().unwrap_or_else(|| Value::Table(toml::value::Table::new()));
+
+        let hostname = gethostname::gethostname().into_string().ok();
+        let config = with_overrides(config, env::consts::OS, hostname.as_deref());
+
+        Self {
+            config: Some(with_symbol_set(config, detect_symbol_set)),
         }
     }
 
@@ -261,6 +442,17 @@ impl StarshipConfig {
     pub fn get_custom_modules(&self) -> Option<&toml::value::Table> {
         self.get_config(&["custom"])?.as_table()
     }
+
+    /// Get the subset of the table for a plugin module by its name
+    pub fn get_plugin_module_config(&self, module_name: &str) -> Option<&Value> {
+        self.get_config(&["plugin", module_name])
+    }
+
+    /// Get the table of all the configured plugin modules, if any
+    pub fn get_plugin_modules(&self) -> Option<&toml::value::Table> {
+        self.get_config(&["plugin"])?.as_table()
+    }
+
     /// Get the table of all the registered `env_var` modules, if any
     pub fn get_env_var_modules(&self) -> Option<&toml::value::Table> {
         self.get_config(&["env_var"])?.as_table()
@@ -353,6 +545,197 @@ pub fn parse_style_string(
                 }
             })
         })
+        .map(|style| match context {
+            Some(context) => downgrade_style(style, context.color_depth()),
+            None => style,
+        })
+}
+
+/// How many colors the terminal can show
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// Resolves the `color_depth` option, guessing from `COLORTERM` and `TERM` for `auto`
+    pub fn resolve(color_depth: &str, colorterm: Option<&str>, term: Option<&str>) -> Self {
+        match color_depth {
+            "truecolor" | "24bit" => return Self::TrueColor,
+            "256" => return Self::Ansi256,
+            "16" => return Self::Ansi16,
+            "auto" => (),
+            color_depth => log::warn!(
+                "Unknown color_depth {:?}, expected one of \"auto\", \"truecolor\", \"256\" or \"16\"",
+                color_depth
+            ),
+        }
+
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return Self::TrueColor;
+        }
+        match term {
+            Some(term) if term.contains("256color") => Self::Ansi256,
+            Some(term) if term == "linux" || term.ends_with("16color") => Self::Ansi16,
+            // Without a hint, keep the colors as they are
+            _ => Self::TrueColor,
+        }
+    }
+}
+
+/// Converts the colors of a style to the nearest ones the terminal can show
+fn downgrade_style(mut style: nu_ansi_term::Style, depth: ColorDepth) -> nu_ansi_term::Style {
+    style.foreground = style.foreground.map(|color| downgrade_color(color, depth));
+    style.background = style.background.map(|color| downgrade_color(color, depth));
+    style
+}
+
+/// The levels of the 6x6x6 color cube of the 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The RGB values of the 16 standard colors, as used by xterm
+const ANSI_16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+fn downgrade_color(color: Color, depth: ColorDepth) -> Color {
+    match (depth, color) {
+        (ColorDepth::TrueColor, color) => color,
+        (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Fixed(nearest_256(r, g, b)),
+        (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+        (ColorDepth::Ansi16, Color::Fixed(index)) => {
+            let (r, g, b) = fixed_to_rgb(index);
+            nearest_16(r, g, b)
+        }
+        (_, color) => color,
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let diff = |a: u8, b: u8| u32::from(a.abs_diff(b)).pow(2);
+    diff(r1, r2) + diff(g1, g2) + diff(b1, b2)
+}
+
+fn fixed_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_16[index as usize],
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[(index / 6 % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// Finds the closest color of the 256 color palette, skipping the 16 standard colors,
+/// which terminal themes usually change
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    (16..=255)
+        .min_by_key(|&index| distance(fixed_to_rgb(index), (r, g, b)))
+        .unwrap_or(16)
+}
+
+/// The standard colors, in the order of `ANSI_16`
+const COLORS_16: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Purple,
+    Color::Cyan,
+    Color::White,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightPurple,
+    Color::LightCyan,
+    Color::LightGray,
+];
+
+/// The names of `COLORS_16` in color strings
+const COLOR_NAMES_16: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "purple",
+    "cyan",
+    "white",
+    "bright-black",
+    "bright-red",
+    "bright-green",
+    "bright-yellow",
+    "bright-blue",
+    "bright-purple",
+    "bright-cyan",
+    "bright-white",
+];
+
+/// Formats a color the way it would be written in a style string, or `None` for
+/// the default color of the terminal
+pub fn color_to_string(color: Color) -> Option<String> {
+    match color {
+        Color::Fixed(num) => Some(num.to_string()),
+        Color::Rgb(r, g, b) => Some(format!("#{r:02x}{g:02x}{b:02x}")),
+        Color::Magenta => Some("purple".to_string()),
+        Color::LightMagenta => Some("bright-purple".to_string()),
+        Color::Default => None,
+        _ => COLORS_16
+            .iter()
+            .position(|&known| known == color)
+            .map(|index| COLOR_NAMES_16[index].to_string()),
+    }
+}
+
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI_16
+        .iter()
+        .zip(COLORS_16)
+        .min_by_key(|(rgb, _)| distance(**rgb, (r, g, b)))
+        .map_or(Color::White, |(_, color)| color)
+}
+
+/// Parses a color string into the RGB value of the color, for escape sequences that only
+/// take RGB colors
+pub fn parse_rgb_color(color_string: &str, context: Option<&Context>) -> Option<(u8, u8, u8)> {
+    let palette = context
+        .and_then(|x| get_palette(&x.root_config.palettes, x.root_config.palette.as_deref()));
+    match parse_color_string(color_string, palette)? {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Fixed(index) => Some(fixed_to_rgb(index)),
+        color => COLORS_16
+            .iter()
+            .position(|standard| *standard == color)
+            .map(|index| ANSI_16[index]),
+    }
 }
 
 /** Parse a string that represents a color setting, returning None if this fails
@@ -923,4 +1306,202 @@ mod tests {
         // Test default behavior
         assert!(get_palette(&palettes, None).is_none());
     }
+
+    #[test]
+    fn symbol_set_fills_in_unset_symbols() {
+        let config = toml::toml! {
+            symbol_set = "ascii"
+            [aws]
+            symbol = "my-aws "
+        };
+
+        let config = with_symbol_set(config, || "unicode");
+        assert_eq!(config["aws"]["symbol"].as_str(), Some("my-aws "));
+        assert_eq!(config["bun"]["symbol"].as_str(), Some("bun "));
+
+        let config = with_symbol_set(toml::toml! { symbol_set = "unicode" }, || "ascii");
+        assert!(config.get("bun").is_none());
+    }
+
+    #[test]
+    fn symbol_set_auto_uses_detection() {
+        let config = with_symbol_set(toml::toml! { symbol_set = "auto" }, || "nerd-font");
+        assert!(config.get("aws").is_some());
+
+        let config = with_symbol_set(toml::toml! { symbol_set = "auto" }, || "unicode");
+        assert!(config.get("aws").is_none());
+    }
+
+    #[test]
+    fn symbol_set_defaults_to_unicode() {
+        let config = with_symbol_set(Value::Table(toml::value::Table::new()), || "ascii");
+        assert!(config.get("aws").is_none());
+    }
+
+    #[test]
+    fn overrides_for_os_and_host() {
+        let config = toml::toml! {
+            format = "$all"
+            add_newline = true
+            [aws]
+            symbol = "aws "
+            style = "yellow"
+            [os_overrides.windows]
+            add_newline = false
+            [os_overrides.linux.aws]
+            symbol = "linux "
+            [host_overrides."work-*"]
+            format = "$directory$character"
+            [host_overrides."work-*".aws]
+            style = "red"
+        };
+
+        let overridden = with_overrides(config.clone(), "linux", Some("WORK-laptop"));
+        assert_eq!(overridden["format"].as_str(), Some("$directory$character"));
+        assert_eq!(overridden["add_newline"].as_bool(), Some(true));
+        assert_eq!(overridden["aws"]["symbol"].as_str(), Some("linux "));
+        assert_eq!(overridden["aws"]["style"].as_str(), Some("red"));
+        assert!(overridden.get("os_overrides").is_none());
+        assert!(overridden.get("host_overrides").is_none());
+
+        let overridden = with_overrides(config, "windows", Some("home"));
+        assert_eq!(overridden["format"].as_str(), Some("$all"));
+        assert_eq!(overridden["add_newline"].as_bool(), Some(false));
+        assert_eq!(overridden["aws"]["symbol"].as_str(), Some("aws "));
+    }
+
+    #[test]
+    fn hostname_patterns() {
+        assert!(matches_hostname("work-laptop", "work-laptop"));
+        assert!(!matches_hostname("work", "work-laptop"));
+        assert!(matches_hostname("work-*", "work-laptop"));
+        assert!(matches_hostname(
+            "*.corp.example.com",
+            "ci.corp.example.com"
+        ));
+        assert!(matches_hostname("build-*-*", "build-eu-42"));
+        assert!(!matches_hostname("build-*-*", "build-42"));
+        assert!(matches_hostname("*", "anything"));
+    }
+
+    #[test]
+    fn symbol_set_detection() {
+        assert_eq!(resolve_symbol_set(None, None, None), "unicode");
+        assert_eq!(
+            resolve_symbol_set(Some("nerd-font"), Some("linux"), None),
+            "nerd-font"
+        );
+        assert_eq!(resolve_symbol_set(None, Some("linux"), None), "ascii");
+        assert_eq!(
+            resolve_symbol_set(None, Some("xterm-256color"), Some("en_US.UTF-8")),
+            "unicode"
+        );
+        assert_eq!(
+            resolve_symbol_set(None, None, Some("en_US.utf8")),
+            "unicode"
+        );
+        assert_eq!(resolve_symbol_set(None, None, Some("C")), "unicode");
+        assert_eq!(
+            resolve_symbol_set(None, None, Some("en_US.ISO-8859-1")),
+            "ascii"
+        );
+    }
+
+    #[test]
+    fn color_depth_detection() {
+        assert_eq!(
+            ColorDepth::resolve("auto", Some("truecolor"), Some("xterm-256color")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::resolve("auto", None, Some("xterm-256color")),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(
+            ColorDepth::resolve("auto", None, Some("linux")),
+            ColorDepth::Ansi16
+        );
+        assert_eq!(
+            ColorDepth::resolve("auto", None, None),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::resolve("16", Some("truecolor"), None),
+            ColorDepth::Ansi16
+        );
+    }
+
+    #[test]
+    fn rgb_colors() {
+        assert_eq!(parse_rgb_color("#102030", None), Some((0x10, 0x20, 0x30)));
+        assert_eq!(parse_rgb_color("red", None), Some((205, 0, 0)));
+        assert_eq!(parse_rgb_color("bright-white", None), Some((255, 255, 255)));
+        assert_eq!(parse_rgb_color("196", None), Some((255, 0, 0)));
+        assert_eq!(parse_rgb_color("not a color", None), None);
+    }
+
+    #[test]
+    fn color_strings() {
+        assert_eq!(color_to_string(Color::Red), Some("red".to_string()));
+        assert_eq!(color_to_string(Color::Magenta), Some("purple".to_string()));
+        assert_eq!(
+            color_to_string(Color::DarkGray),
+            Some("bright-black".to_string())
+        );
+        assert_eq!(color_to_string(Color::Fixed(208)), Some("208".to_string()));
+        assert_eq!(
+            color_to_string(Color::Rgb(0x10, 0xab, 0xff)),
+            Some("#10abff".to_string())
+        );
+        assert_eq!(color_to_string(Color::Default), None);
+    }
+
+    #[test]
+    fn downgrade_colors() {
+        assert_eq!(
+            downgrade_color(Color::Rgb(255, 0, 0), ColorDepth::Ansi256),
+            Color::Fixed(196)
+        );
+        assert_eq!(
+            downgrade_color(Color::Rgb(0x80, 0x80, 0x80), ColorDepth::Ansi256),
+            Color::Fixed(244)
+        );
+        assert_eq!(
+            downgrade_color(Color::Rgb(250, 10, 10), ColorDepth::Ansi16),
+            Color::LightRed
+        );
+        assert_eq!(
+            downgrade_color(Color::Fixed(22), ColorDepth::Ansi16),
+            Color::Black
+        );
+        assert_eq!(
+            downgrade_color(Color::Fixed(22), ColorDepth::Ansi256),
+            Color::Fixed(22)
+        );
+        assert_eq!(
+            downgrade_color(Color::Blue, ColorDepth::Ansi16),
+            Color::Blue
+        );
+    }
+
+    #[test]
+    fn style_string_downgraded_with_context() {
+        let mut context = crate::test::default_context();
+        context.root_config.color_depth = "256".to_string();
+
+        let style = parse_style_string("bold fg:#ff0000 bg:#000000", Some(&context)).unwrap();
+        assert_eq!(style, Color::Fixed(196).bold().on(Color::Fixed(16)));
+
+        let style = parse_style_string("fg:#ff0000", None).unwrap();
+        assert_eq!(style, Color::Rgb(255, 0, 0).normal());
+    }
+
+    #[test]
+    fn style_string_kept_by_default() {
+        let mut context = crate::test::default_context();
+        context.env.insert("TERM", "xterm-256color".to_string());
+
+        let style = parse_style_string("fg:#ff0000", Some(&context)).unwrap();
+        assert_eq!(style, Color::Rgb(255, 0, 0).normal());
+    }
 }
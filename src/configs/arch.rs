@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct ArchConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub emulated_style: &'a str,
+    pub only_emulated: bool,
+    pub disabled: bool,
+}
+
+impl<'a> Default for ArchConfig<'a> {
+    fn default() -> Self {
+        ArchConfig {
+            format: "[$symbol$arch( \\(via $emulator\\))]($style) ",
+            symbol: "",
+            style: "bold dimmed white",
+            emulated_style: "bold red",
+            only_emulated: false,
+            disabled: true,
+        }
+    }
+}
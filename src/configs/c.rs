@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -15,7 +17,10 @@ pub struct CConfig<'a> {
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
     pub commands: Vec<Vec<&'a str>>,
 }
 
@@ -29,7 +34,10 @@ impl<'a> Default for CConfig<'a> {
             disabled: false,
             detect_extensions: vec!["c", "h"],
             detect_files: vec![],
+            detect_files_not: vec![],
             detect_folders: vec![],
+            detect_content: vec![],
+            scan_ancestors: false,
             commands: vec![
                 // the compiler is usually cc, and --version works on gcc and clang
                 vec!["cc", "--version"],
@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -9,6 +10,7 @@ use serde::{Deserialize, Serialize};
 #[serde(default)]
 pub struct CmdDurationConfig<'a> {
     pub min_time: i64,
+    pub min_time_by_command: IndexMap<String, i64>,
     pub format: &'a str,
     pub style: &'a str,
     pub show_milliseconds: bool,
@@ -18,12 +20,20 @@ pub struct CmdDurationConfig<'a> {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notification_timeout: Option<u32>,
+    pub notification_backend: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notification_webhook_url: Option<&'a str>,
+    pub notification_commands: Vec<&'a str>,
+    pub notification_ignored_commands: Vec<&'a str>,
+    pub notification_only_when_unfocused: bool,
+    pub average_window: usize,
 }
 
 impl<'a> Default for CmdDurationConfig<'a> {
     fn default() -> Self {
         CmdDurationConfig {
             min_time: 2_000,
+            min_time_by_command: IndexMap::new(),
             format: "took [$duration]($style) ",
             show_milliseconds: false,
             style: "yellow bold",
@@ -31,6 +41,12 @@ impl<'a> Default for CmdDurationConfig<'a> {
             show_notifications: false,
             min_time_to_notify: 45_000,
             notification_timeout: None,
+            notification_backend: "auto",
+            notification_webhook_url: None,
+            notification_commands: vec![],
+            notification_ignored_commands: vec![],
+            notification_only_when_unfocused: false,
+            average_window: 10,
         }
     }
 }
@@ -13,6 +13,8 @@ pub struct CondaConfig<'a> {
     pub symbol: &'a str,
     pub style: &'a str,
     pub ignore_base: bool,
+    pub show_full_stack: bool,
+    pub stack_separator: &'a str,
     pub disabled: bool,
 }
 
@@ -24,6 +26,8 @@ impl<'a> Default for CondaConfig<'a> {
             symbol: "🅒 ",
             style: "green bold",
             ignore_base: true,
+            show_full_stack: false,
+            stack_separator: " > ",
             disabled: false,
         }
     }
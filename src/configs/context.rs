@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct ContextConfig<'a> {
+    pub format: &'a str,
+    pub ssh_symbol: &'a str,
+    pub container_symbol: &'a str,
+    pub root_symbol: &'a str,
+    pub show_ssh: bool,
+    pub show_container: bool,
+    pub show_root: bool,
+    pub show_hostname: bool,
+    pub trim_at: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for ContextConfig<'a> {
+    fn default() -> Self {
+        ContextConfig {
+            format: "[$ssh$container$root( $hostname)]($style) ",
+            ssh_symbol: "🌐",
+            container_symbol: "⬢",
+            root_symbol: "⚡",
+            show_ssh: true,
+            show_container: true,
+            show_root: true,
+            show_hostname: true,
+            trim_at: ".",
+            style: "bold yellow",
+            disabled: true,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct CppPackageManagerConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+    pub conan_files: Vec<&'a str>,
+    pub vcpkg_files: Vec<&'a str>,
+}
+
+impl<'a> Default for CppPackageManagerConfig<'a> {
+    fn default() -> Self {
+        CppPackageManagerConfig {
+            format: "with [$symbol$manager( \\($profile\\))]($style) ",
+            symbol: "📦 ",
+            style: "bold blue",
+            disabled: false,
+            conan_files: vec!["conanfile.txt", "conanfile.py"],
+            vcpkg_files: vec!["vcpkg.json"],
+        }
+    }
+}
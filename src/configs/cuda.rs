@@ -0,0 +1,45 @@
+use crate::config::DetectContent;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct CudaConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
+}
+
+impl<'a> Default for CudaConfig<'a> {
+    fn default() -> Self {
+        CudaConfig {
+            format: "via [$symbol$toolkit( $version) ]($style)",
+            version_format: "v${raw}",
+            symbol: "🟩 ",
+            style: "bold green",
+            disabled: false,
+            detect_extensions: vec!["cu", "cuh"],
+            detect_files: vec![],
+            detect_files_not: vec![],
+            detect_folders: vec![],
+            detect_content: vec![DetectContent {
+                file: "environment.yml",
+                regex: "(?i)cuda|rocm",
+            }],
+            scan_ancestors: false,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::config::{Either, VecOr};
+use crate::config::{DetectContent, Either, VecOr};
 
 use serde::{self, Deserialize, Serialize};
 
@@ -20,15 +20,26 @@ pub struct CustomConfig<'a> {
     pub disabled: bool,
     #[serde(alias = "files")]
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     #[serde(alias = "extensions")]
     pub detect_extensions: Vec<&'a str>,
     #[serde(alias = "directories")]
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub os: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub use_stdin: Option<bool>,
     pub ignore_timeout: bool,
+    pub variables: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_interval: Option<&'a str>,
+    pub shared_cache: bool,
+    pub require_repo: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when_module: Option<&'a str>,
+    pub exec: Vec<&'a str>,
 }
 
 impl<'a> Default for CustomConfig<'a> {
@@ -43,11 +54,20 @@ impl<'a> Default for CustomConfig<'a> {
             style: "green bold",
             disabled: false,
             detect_files: Vec::default(),
+            detect_files_not: Vec::default(),
             detect_extensions: Vec::default(),
             detect_folders: Vec::default(),
+            detect_content: Vec::default(),
+            scan_ancestors: false,
             os: None,
             use_stdin: None,
             ignore_timeout: false,
+            variables: Vec::default(),
+            refresh_interval: None,
+            shared_cache: false,
+            require_repo: false,
+            when_module: None,
+            exec: Vec::default(),
         }
     }
 }
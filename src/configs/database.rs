@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct DatabaseConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for DatabaseConfig<'a> {
+    fn default() -> Self {
+        DatabaseConfig {
+            format: "on [$symbol$engine://($user@)$host(/$database)]($style) ",
+            symbol: "🛢 ",
+            style: "bold blue",
+            disabled: false,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -15,7 +17,10 @@ pub struct DockerContextConfig<'a> {
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for DockerContextConfig<'a> {
@@ -28,7 +33,10 @@ impl<'a> Default for DockerContextConfig<'a> {
             disabled: false,
             detect_extensions: vec![],
             detect_files: vec!["docker-compose.yml", "docker-compose.yaml", "Dockerfile"],
+            detect_files_not: vec![],
             detect_folders: vec![],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct DotenvConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub files: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> Default for DotenvConfig<'a> {
+    fn default() -> Self {
+        DotenvConfig {
+            format: "[$symbol$count( \\($exported exported\\))]($style) ",
+            symbol: "🔑 ",
+            style: "bold yellow",
+            files: vec![".env", ".env.local"],
+            disabled: true,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -16,7 +18,10 @@ pub struct DotnetConfig<'a> {
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for DotnetConfig<'a> {
@@ -36,7 +41,10 @@ impl<'a> Default for DotnetConfig<'a> {
                 "Directory.Build.targets",
                 "Packages.props",
             ],
+            detect_files_not: vec![],
             detect_folders: vec![],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -14,6 +15,11 @@ pub struct EnvVarConfig<'a> {
     pub variable: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<&'a str>,
+    #[serde(borrow)]
+    pub mapping: IndexMap<String, &'a str>,
+    #[serde(borrow)]
+    pub variables: Vec<EnvVarEntryConfig<'a>>,
+    pub separator: &'a str,
     pub format: &'a str,
     pub disabled: bool,
 }
@@ -25,8 +31,44 @@ impl<'a> Default for EnvVarConfig<'a> {
             style: "black bold dimmed",
             variable: None,
             default: None,
+            mapping: IndexMap::new(),
+            variables: Vec::new(),
+            separator: " ",
             format: "with [$env_value]($style) ",
             disabled: false,
         }
     }
 }
+
+/// One of the `variables` of an `env_var` module, which are shown together in its `format`
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct EnvVarEntryConfig<'a> {
+    pub variable: &'a str,
+    pub symbol: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<&'a str>,
+    #[serde(borrow)]
+    pub mapping: IndexMap<String, &'a str>,
+    pub format: &'a str,
+}
+
+impl<'a> Default for EnvVarEntryConfig<'a> {
+    fn default() -> Self {
+        EnvVarEntryConfig {
+            variable: "",
+            symbol: "",
+            style: None,
+            default: None,
+            mapping: IndexMap::new(),
+            format: "[$symbol$env_value]($style)",
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -15,7 +17,10 @@ pub struct ErlangConfig<'a> {
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for ErlangConfig<'a> {
@@ -28,7 +33,10 @@ impl<'a> Default for ErlangConfig<'a> {
             disabled: false,
             detect_extensions: vec![],
             detect_files: vec!["rebar.config", "erlang.mk"],
+            detect_files_not: vec![],
             detect_folders: vec![],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 pub struct FillConfig<'a> {
     pub style: &'a str,
     pub symbol: &'a str,
+    pub weights: Vec<usize>,
     pub disabled: bool,
 }
 
@@ -18,6 +19,7 @@ impl<'a> Default for FillConfig<'a> {
         FillConfig {
             style: "bold black",
             symbol: ".",
+            weights: vec![],
             disabled: false,
         }
     }
@@ -11,6 +11,8 @@ pub struct GitMetricsConfig<'a> {
     pub added_style: &'a str,
     pub deleted_style: &'a str,
     pub only_nonzero_diffs: bool,
+    pub max_index_entries: i64,
+    pub skipped: &'a str,
     pub format: &'a str,
     pub disabled: bool,
 }
@@ -21,7 +23,9 @@ impl<'a> Default for GitMetricsConfig<'a> {
             added_style: "bold green",
             deleted_style: "bold red",
             only_nonzero_diffs: true,
-            format: "([+$added]($added_style) )([-$deleted]($deleted_style) )",
+            max_index_entries: 0,
+            skipped: "…",
+            format: "([+$added]($added_style) )([-$deleted]($deleted_style) )($skipped )",
             disabled: true,
         }
     }
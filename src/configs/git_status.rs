@@ -22,6 +22,9 @@ pub struct GitStatusConfig<'a> {
     pub staged: &'a str,
     pub untracked: &'a str,
     pub ignore_submodules: bool,
+    pub fsmonitor: &'a str,
+    pub max_index_entries: i64,
+    pub skipped: &'a str,
     pub disabled: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub windows_starship: Option<&'a str>,
@@ -44,6 +47,9 @@ impl<'a> Default for GitStatusConfig<'a> {
             staged: "+",
             untracked: "?",
             ignore_submodules: false,
+            fsmonitor: "",
+            max_index_entries: 0,
+            skipped: "…",
             disabled: false,
             windows_starship: None,
         }
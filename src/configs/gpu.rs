@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct GpuConfig<'a> {
+    pub threshold: i64,
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub refresh_interval: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for GpuConfig<'a> {
+    fn default() -> Self {
+        GpuConfig {
+            threshold: 0,
+            format: "on [$symbol$utilization% $memory_used/$memory_total]($style) ",
+            symbol: "🎛 ",
+            style: "bold green",
+            refresh_interval: "10s",
+            disabled: true,
+        }
+    }
+}
@@ -17,7 +17,7 @@ pub struct GuixShellConfig<'a> {
 impl<'a> Default for GuixShellConfig<'a> {
     fn default() -> Self {
         GuixShellConfig {
-            format: "via [$symbol]($style) ",
+            format: "via [$symbol(\\($name\\))]($style) ",
             symbol: "🐃 ",
             style: "yellow bold",
             disabled: false,
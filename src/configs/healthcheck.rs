@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct HealthcheckConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub up_symbol: &'a str,
+    pub down_symbol: &'a str,
+    pub style: &'a str,
+    pub down_style: &'a str,
+    pub urls: Vec<&'a str>,
+    pub timeout: u64,
+    pub refresh_interval: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for HealthcheckConfig<'a> {
+    fn default() -> Self {
+        HealthcheckConfig {
+            format: "[$symbol$status]($style) ",
+            symbol: "♥ ",
+            up_symbol: "●",
+            down_symbol: "○",
+            style: "bold green",
+            down_style: "bold red",
+            urls: vec![],
+            timeout: 1000,
+            refresh_interval: "5m",
+            disabled: true,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct HistoryConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for HistoryConfig<'a> {
+    fn default() -> Self {
+        HistoryConfig {
+            format: "[$symbol$index]($style) ",
+            symbol: "!",
+            style: "bold dimmed white",
+            disabled: true,
+        }
+    }
+}
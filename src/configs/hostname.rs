@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 pub struct HostnameConfig<'a> {
     pub ssh_only: bool,
     pub ssh_symbol: &'a str,
+    pub ssh_loopback_symbol: &'a str,
     pub trim_at: &'a str,
     pub format: &'a str,
     pub style: &'a str,
@@ -21,6 +22,7 @@ impl<'a> Default for HostnameConfig<'a> {
         HostnameConfig {
             ssh_only: true,
             ssh_symbol: "🌐 ",
+            ssh_loopback_symbol: "⇢ ",
             trim_at: ".",
             format: "[$ssh_symbol$hostname]($style) in ",
             style: "green dimmed bold",
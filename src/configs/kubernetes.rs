@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -17,7 +19,10 @@ pub struct KubernetesConfig<'a> {
     pub user_aliases: HashMap<String, &'a str>,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for KubernetesConfig<'a> {
@@ -31,7 +36,10 @@ impl<'a> Default for KubernetesConfig<'a> {
             user_aliases: HashMap::new(),
             detect_extensions: vec![],
             detect_files: vec![],
+            detect_files_not: vec![],
             detect_folders: vec![],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct LicenseConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub warning_style: &'a str,
+    pub missing: &'a str,
+    pub denied: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> Default for LicenseConfig<'a> {
+    fn default() -> Self {
+        LicenseConfig {
+            format: "[$symbol$license]($style) ",
+            symbol: "⚖️ ",
+            style: "bold blue",
+            warning_style: "bold red",
+            missing: "",
+            denied: vec![],
+            disabled: true,
+        }
+    }
+}
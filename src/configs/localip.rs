@@ -11,6 +11,10 @@ pub struct LocalipConfig<'a> {
     pub ssh_only: bool,
     pub format: &'a str,
     pub style: &'a str,
+    pub interface: &'a str,
+    pub prefer_ipv6: bool,
+    pub max_addresses: usize,
+    pub separator: &'a str,
     pub disabled: bool,
 }
 
@@ -18,8 +22,12 @@ impl<'a> Default for LocalipConfig<'a> {
     fn default() -> Self {
         LocalipConfig {
             ssh_only: true,
-            format: "[$localipv4]($style) ",
+            format: "[$localip]($style) ",
             style: "yellow bold",
+            interface: "",
+            prefer_ipv6: false,
+            max_addresses: 1,
+            separator: " ",
             disabled: true,
         }
     }
@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -16,7 +18,10 @@ pub struct LuaConfig<'a> {
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for LuaConfig<'a> {
@@ -30,7 +35,10 @@ impl<'a> Default for LuaConfig<'a> {
             disabled: false,
             detect_extensions: vec!["lua"],
             detect_files: vec![".lua-version"],
+            detect_files_not: vec![],
             detect_folders: vec!["lua"],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
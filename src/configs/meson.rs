@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -11,9 +13,17 @@ pub struct MesonConfig<'a> {
     pub truncation_length: u32,
     pub truncation_symbol: &'a str,
     pub format: &'a str,
+    pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
     pub disabled: bool,
+    pub build_dirs: Vec<&'a str>,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for MesonConfig<'a> {
@@ -21,10 +31,18 @@ impl<'a> Default for MesonConfig<'a> {
         MesonConfig {
             truncation_length: std::u32::MAX,
             truncation_symbol: "…",
-            format: "via [$symbol$project]($style) ",
+            format: "via [$symbol$project( $version)( \\($buildtype\\))]($style) ",
+            version_format: "v${raw}",
             symbol: "⬢ ",
             style: "blue bold",
             disabled: false,
+            build_dirs: vec!["build", "builddir", "_build"],
+            detect_extensions: vec![],
+            detect_files: vec!["meson.build"],
+            detect_files_not: vec![],
+            detect_folders: vec![],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
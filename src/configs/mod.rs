@@ -1,9 +1,11 @@
 use indexmap::IndexMap;
 use serde::{self, Deserialize, Serialize};
 
+pub mod arch;
 pub mod aws;
 pub mod azure;
 pub mod battery;
+pub mod blocks;
 pub mod buf;
 pub mod bun;
 pub mod c;
@@ -13,13 +15,18 @@ pub mod cmd_duration;
 pub mod cobol;
 pub mod conda;
 pub mod container;
+pub mod context;
+pub mod cpp_package_manager;
 pub mod crystal;
 pub mod custom;
+pub mod cuda;
 pub mod daml;
 pub mod dart;
+pub mod database;
 pub mod deno;
 pub mod directory;
 pub mod docker_context;
+pub mod dotenv;
 pub mod dotnet;
 pub mod elixir;
 pub mod elm;
@@ -33,23 +40,29 @@ pub mod git_metrics;
 pub mod git_state;
 pub mod git_status;
 pub mod go;
+pub mod gpu;
 pub mod guix_shell;
 pub mod haskell;
 pub mod haxe;
+pub mod healthcheck;
 pub mod helm;
 pub mod hg_branch;
+pub mod history;
 pub mod hostname;
 pub mod java;
 pub mod jobs;
 pub mod julia;
+pub mod jupyter;
 pub mod kotlin;
 pub mod kubernetes;
+pub mod license;
 pub mod line_break;
 pub mod localip;
 pub mod lua;
 pub mod memory_usage;
 pub mod meson;
 pub mod nim;
+pub mod nix_generation;
 pub mod nix_shell;
 pub mod nodejs;
 pub mod ocaml;
@@ -59,15 +72,19 @@ pub mod os;
 pub mod package;
 pub mod perl;
 pub mod php;
+pub mod plugin;
 pub mod pulumi;
 pub mod purescript;
 pub mod python;
 pub mod raku;
+pub mod reboot_required;
 pub mod red;
+pub mod redact;
 pub mod rlang;
 pub mod ruby;
 pub mod rust;
 pub mod scala;
+pub mod separators;
 pub mod shell;
 pub mod shlvl;
 pub mod singularity;
@@ -76,12 +93,16 @@ mod starship_root;
 pub mod status;
 pub mod sudo;
 pub mod swift;
+pub mod systemd;
 pub mod terraform;
 pub mod time;
+pub mod timer;
+pub mod updates;
 pub mod username;
 pub mod v;
 pub mod vagrant;
 pub mod vcsh;
+pub mod window_title;
 pub mod zig;
 
 pub use starship_root::*;
@@ -102,6 +123,8 @@ pub struct FullConfig<'a> {
     root: StarshipRootConfig,
     // modules
     #[serde(borrow)]
+    arch: arch::ArchConfig<'a>,
+    #[serde(borrow)]
     aws: aws::AwsConfig<'a>,
     #[serde(borrow)]
     azure: azure::AzureConfig<'a>,
@@ -126,18 +149,28 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     container: container::ContainerConfig<'a>,
     #[serde(borrow)]
+    context: context::ContextConfig<'a>,
+    #[serde(borrow)]
+    cpp_package_manager: cpp_package_manager::CppPackageManagerConfig<'a>,
+    #[serde(borrow)]
     crystal: crystal::CrystalConfig<'a>,
     #[serde(borrow)]
+    cuda: cuda::CudaConfig<'a>,
+    #[serde(borrow)]
     daml: daml::DamlConfig<'a>,
     #[serde(borrow)]
     dart: dart::DartConfig<'a>,
     #[serde(borrow)]
+    database: database::DatabaseConfig<'a>,
+    #[serde(borrow)]
     deno: deno::DenoConfig<'a>,
     #[serde(borrow)]
     directory: directory::DirectoryConfig<'a>,
     #[serde(borrow)]
     docker_context: docker_context::DockerContextConfig<'a>,
     #[serde(borrow)]
+    dotenv: dotenv::DotenvConfig<'a>,
+    #[serde(borrow)]
     dotnet: dotnet::DotnetConfig<'a>,
     #[serde(borrow)]
     elixir: elixir::ElixirConfig<'a>,
@@ -164,16 +197,22 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     golang: go::GoConfig<'a>,
     #[serde(borrow)]
+    gpu: gpu::GpuConfig<'a>,
+    #[serde(borrow)]
     guix_shell: guix_shell::GuixShellConfig<'a>,
     #[serde(borrow)]
     haskell: haskell::HaskellConfig<'a>,
     #[serde(borrow)]
     haxe: haxe::HaxeConfig<'a>,
     #[serde(borrow)]
+    healthcheck: healthcheck::HealthcheckConfig<'a>,
+    #[serde(borrow)]
     helm: helm::HelmConfig<'a>,
     #[serde(borrow)]
     hg_branch: hg_branch::HgBranchConfig<'a>,
     #[serde(borrow)]
+    history: history::HistoryConfig<'a>,
+    #[serde(borrow)]
     hostname: hostname::HostnameConfig<'a>,
     #[serde(borrow)]
     java: java::JavaConfig<'a>,
@@ -182,9 +221,13 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     julia: julia::JuliaConfig<'a>,
     #[serde(borrow)]
+    jupyter: jupyter::JupyterConfig<'a>,
+    #[serde(borrow)]
     kotlin: kotlin::KotlinConfig<'a>,
     #[serde(borrow)]
     kubernetes: kubernetes::KubernetesConfig<'a>,
+    #[serde(borrow)]
+    license: license::LicenseConfig<'a>,
     line_break: line_break::LineBreakConfig,
     #[serde(borrow)]
     localip: localip::LocalipConfig<'a>,
@@ -197,6 +240,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     nim: nim::NimConfig<'a>,
     #[serde(borrow)]
+    nix_generation: nix_generation::NixGenerationConfig<'a>,
+    #[serde(borrow)]
     nix_shell: nix_shell::NixShellConfig<'a>,
     #[serde(borrow)]
     nodejs: nodejs::NodejsConfig<'a>,
@@ -223,6 +268,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     raku: raku::RakuConfig<'a>,
     #[serde(borrow)]
+    reboot_required: reboot_required::RebootRequiredConfig<'a>,
+    #[serde(borrow)]
     red: red::RedConfig<'a>,
     #[serde(borrow)]
     rlang: rlang::RLangConfig<'a>,
@@ -247,10 +294,16 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     swift: swift::SwiftConfig<'a>,
     #[serde(borrow)]
+    systemd: systemd::SystemdConfig<'a>,
+    #[serde(borrow)]
     terraform: terraform::TerraformConfig<'a>,
     #[serde(borrow)]
     time: time::TimeConfig<'a>,
     #[serde(borrow)]
+    timer: timer::TimerConfig<'a>,
+    #[serde(borrow)]
+    updates: updates::UpdatesConfig<'a>,
+    #[serde(borrow)]
     username: username::UsernameConfig<'a>,
     #[serde(borrow)]
     vagrant: vagrant::VagrantConfig<'a>,
@@ -262,6 +315,8 @@ pub struct FullConfig<'a> {
     zig: zig::ZigConfig<'a>,
     #[serde(borrow)]
     custom: IndexMap<String, custom::CustomConfig<'a>>,
+    #[serde(borrow)]
+    plugin: IndexMap<String, plugin::PluginConfig<'a>>,
 }
 
 #[cfg(test)]
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct NixGenerationConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub dirty_symbol: &'a str,
+    pub profiles: Vec<&'a str>,
+    pub config_path: &'a str,
+    pub disabled: bool,
+}
+
+/* The trailing double spaces in `symbol` are needed to work around issues with
+multiwidth emoji support in some shells. Please do not file a PR to change this
+unless you can show that your changes do not affect this workaround.  */
+impl<'a> Default for NixGenerationConfig<'a> {
+    fn default() -> Self {
+        NixGenerationConfig {
+            format: "[$symbol$profile gen $generation( $dirty)]($style) ",
+            symbol: "❄️  ",
+            style: "bold blue",
+            dirty_symbol: "✎",
+            profiles: vec!["home-manager", "system"],
+            config_path: "",
+            disabled: true,
+        }
+    }
+}
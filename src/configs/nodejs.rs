@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -10,13 +12,18 @@ use serde::{Deserialize, Serialize};
 pub struct NodejsConfig<'a> {
     pub format: &'a str,
     pub version_format: &'a str,
+    pub use_pinned_version: bool,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub mismatch_style: &'a str,
     pub disabled: bool,
     pub not_capable_style: &'a str,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for NodejsConfig<'a> {
@@ -24,13 +31,18 @@ impl<'a> Default for NodejsConfig<'a> {
         NodejsConfig {
             format: "via [$symbol($version )]($style)",
             version_format: "v${raw}",
+            use_pinned_version: false,
             symbol: " ",
             style: "bold green",
+            mismatch_style: "",
             disabled: false,
             not_capable_style: "bold red",
             detect_extensions: vec!["js", "mjs", "cjs", "ts", "mts", "cts"],
             detect_files: vec!["package.json", ".node-version", ".nvmrc"],
+            detect_files_not: vec![],
             detect_folders: vec!["node_modules"],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
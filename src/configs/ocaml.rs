@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -17,7 +19,10 @@ pub struct OCamlConfig<'a> {
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for OCamlConfig<'a> {
@@ -32,7 +37,10 @@ impl<'a> Default for OCamlConfig<'a> {
             disabled: false,
             detect_extensions: vec!["opam", "ml", "mli", "re", "rei"],
             detect_files: vec!["dune", "dune-project", "jbuild", "jbuild-ignore", ".merlin"],
+            detect_files_not: vec![],
             detect_folders: vec!["_opam", "esy.lock"],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
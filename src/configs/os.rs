@@ -13,6 +13,7 @@ pub struct OSConfig<'a> {
     pub format: &'a str,
     pub style: &'a str,
     pub symbols: IndexMap<Type, &'a str>,
+    pub wsl_symbol: &'a str,
     pub disabled: bool,
 }
 
@@ -77,6 +78,7 @@ impl<'a> Default for OSConfig<'a> {
                 //void =>       " ",
                 //solaris =>    " ",
             },
+            wsl_symbol: "🪟 ",
             disabled: true,
         }
     }
@@ -14,6 +14,7 @@ pub struct PackageConfig<'a> {
     pub display_private: bool,
     pub disabled: bool,
     pub version_format: &'a str,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for PackageConfig<'a> {
@@ -25,6 +26,7 @@ impl<'a> Default for PackageConfig<'a> {
             display_private: false,
             disabled: false,
             version_format: "v${raw}",
+            scan_ancestors: false,
         }
     }
 }
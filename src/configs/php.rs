@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -12,10 +14,14 @@ pub struct PhpConfig<'a> {
     pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub mismatch_style: &'a str,
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for PhpConfig<'a> {
@@ -25,10 +31,14 @@ impl<'a> Default for PhpConfig<'a> {
             version_format: "v${raw}",
             symbol: "🐘 ",
             style: "147 bold",
+            mismatch_style: "",
             disabled: false,
             detect_extensions: vec!["php"],
             detect_files: vec!["composer.json", ".php-version"],
+            detect_files_not: vec![],
             detect_folders: vec![],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
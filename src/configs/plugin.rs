@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+// Unknown options are passed on to the plugin, so they aren't denied
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct PluginConfig<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    pub ignore_timeout: bool,
+    pub disabled: bool,
+}
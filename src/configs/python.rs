@@ -1,4 +1,4 @@
-use crate::config::VecOr;
+use crate::config::{DetectContent, VecOr};
 
 use serde::{Deserialize, Serialize};
 
@@ -13,14 +13,20 @@ pub struct PythonConfig<'a> {
     pub pyenv_version_name: bool,
     pub pyenv_prefix: &'a str,
     pub python_binary: VecOr<&'a str>,
+    pub managers: Vec<&'a str>,
     pub format: &'a str,
     pub version_format: &'a str,
+    pub use_pinned_version: bool,
     pub style: &'a str,
+    pub mismatch_style: &'a str,
     pub symbol: &'a str,
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for PythonConfig<'a> {
@@ -29,9 +35,12 @@ impl<'a> Default for PythonConfig<'a> {
             pyenv_version_name: false,
             pyenv_prefix: "pyenv ",
             python_binary: VecOr(vec!["python", "python3", "python2"]),
+            managers: vec!["poetry", "uv", "hatch", "pipenv"],
             format: "via [${symbol}${pyenv_prefix}(${version} )(\\($virtualenv\\) )]($style)",
             version_format: "v${raw}",
+            use_pinned_version: false,
             style: "yellow bold",
+            mismatch_style: "",
             symbol: "🐍 ",
             disabled: false,
             detect_extensions: vec!["py"],
@@ -44,7 +53,10 @@ impl<'a> Default for PythonConfig<'a> {
                 "setup.py",
                 "__init__.py",
             ],
+            detect_files_not: vec![],
             detect_folders: vec![],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct RebootRequiredConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub refresh_interval: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for RebootRequiredConfig<'a> {
+    fn default() -> Self {
+        RebootRequiredConfig {
+            format: "[$symbol]($style) ",
+            symbol: "↻ reboot",
+            style: "bold yellow",
+            refresh_interval: "1h",
+            disabled: true,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct RedactConfig {
+    pub patterns: Vec<String>,
+    pub replacement: String,
+    pub disabled: bool,
+}
+
+impl Default for RedactConfig {
+    fn default() -> Self {
+        RedactConfig {
+            patterns: [
+                // AWS access key IDs
+                r"\b(AKIA|ASIA)[0-9A-Z]{16}\b",
+                // GitHub tokens
+                r"\bgh[pousr]_[A-Za-z0-9]{36,}\b",
+                r"\bgithub_pat_[A-Za-z0-9_]{22,}\b",
+                // GitLab personal access tokens
+                r"\bglpat-[A-Za-z0-9_-]{20,}",
+                // Slack tokens
+                r"\bxox[abposr]-[A-Za-z0-9-]{10,}",
+                // JSON Web Tokens
+                r"\beyJ[A-Za-z0-9_-]+\.eyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+",
+            ]
+            .map(String::from)
+            .to_vec(),
+            replacement: "[redacted]".to_string(),
+            disabled: false,
+        }
+    }
+}
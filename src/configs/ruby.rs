@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -12,10 +14,15 @@ pub struct RubyConfig<'a> {
     pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub mismatch_style: &'a str,
+    pub bundler_symbol: &'a str,
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
     pub detect_variables: Vec<&'a str>,
 }
 
@@ -26,10 +33,15 @@ impl<'a> Default for RubyConfig<'a> {
             version_format: "v${raw}",
             symbol: "💎 ",
             style: "bold red",
+            mismatch_style: "",
+            bundler_symbol: "📦 ",
             disabled: false,
             detect_extensions: vec!["rb"],
             detect_files: vec!["Gemfile", ".ruby-version"],
+            detect_files_not: vec![],
             detect_folders: vec![],
+            detect_content: vec![],
+            scan_ancestors: false,
             detect_variables: vec!["RUBY_VERSION", "RBENV_VERSION"],
         }
     }
@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -10,12 +12,18 @@ use serde::{Deserialize, Serialize};
 pub struct RustConfig<'a> {
     pub format: &'a str,
     pub version_format: &'a str,
+    pub use_pinned_version: bool,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub mismatch_style: &'a str,
+    pub overrides_symbol: &'a str,
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for RustConfig<'a> {
@@ -23,12 +31,18 @@ impl<'a> Default for RustConfig<'a> {
         RustConfig {
             format: "via [$symbol($version )]($style)",
             version_format: "v${raw}",
+            use_pinned_version: false,
             symbol: "🦀 ",
             style: "bold red",
+            mismatch_style: "",
+            overrides_symbol: "⚑ ",
             disabled: false,
             detect_extensions: vec!["rs"],
             detect_files: vec!["Cargo.toml"],
+            detect_files_not: vec![],
             detect_folders: vec![],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
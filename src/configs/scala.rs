@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -15,7 +17,10 @@ pub struct ScalaConfig<'a> {
     pub symbol: &'a str,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for ScalaConfig<'a> {
@@ -28,7 +33,10 @@ impl<'a> Default for ScalaConfig<'a> {
             symbol: "🆂 ",
             detect_extensions: vec!["sbt", "scala"],
             detect_files: vec![".scalaenv", ".sbtenv", "build.sbt"],
+            detect_files_not: vec![],
             detect_folders: vec![".metals"],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct SeparatorsConfig {
+    pub left: String,
+    pub right: String,
+    pub left_thin: String,
+    pub right_thin: String,
+    pub disabled: bool,
+}
+
+impl Default for SeparatorsConfig {
+    fn default() -> Self {
+        SeparatorsConfig {
+            left: "\u{e0b0}".to_string(),
+            right: "\u{e0b2}".to_string(),
+            left_thin: "\u{e0b1}".to_string(),
+            right_thin: "\u{e0b3}".to_string(),
+            disabled: true,
+        }
+    }
+}
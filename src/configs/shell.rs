@@ -18,8 +18,15 @@ pub struct ShellConfig<'a> {
     pub tcsh_indicator: &'a str,
     pub nu_indicator: &'a str,
     pub xonsh_indicator: &'a str,
+    pub murex_indicator: &'a str,
+    pub osh_indicator: &'a str,
+    pub ysh_indicator: &'a str,
+    pub rc_indicator: &'a str,
     pub cmd_indicator: &'a str,
     pub unknown_indicator: &'a str,
+    pub login_symbol: &'a str,
+    pub restricted_symbol: &'a str,
+    pub version_format: &'a str,
     pub style: &'a str,
     pub disabled: bool,
 }
@@ -37,8 +44,15 @@ impl<'a> Default for ShellConfig<'a> {
             tcsh_indicator: "tsh",
             nu_indicator: "nu",
             xonsh_indicator: "xsh",
+            murex_indicator: "murex",
+            osh_indicator: "osh",
+            ysh_indicator: "ysh",
+            rc_indicator: "rc",
             cmd_indicator: "cmd",
             unknown_indicator: "",
+            login_symbol: "-",
+            restricted_symbol: "r",
+            version_format: "v${raw}",
             style: "white bold",
             disabled: true,
         }
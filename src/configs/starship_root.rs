@@ -1,3 +1,7 @@
+use super::blocks::BlocksConfig;
+use super::redact::RedactConfig;
+use super::separators::SeparatorsConfig;
+use super::window_title::WindowTitleConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -14,12 +18,26 @@ pub struct StarshipRootConfig {
     pub format: String,
     pub right_format: String,
     pub continuation_prompt: String,
+    pub select_prompt: String,
+    pub trace_prompt: String,
+    pub prompt_modes: HashMap<String, String>,
     pub scan_timeout: u64,
     pub command_timeout: u64,
     pub add_newline: bool,
+    pub shell_integration: bool,
+    pub symbol_set: String,
+    pub color_depth: String,
+    pub separators: SeparatorsConfig,
+    pub blocks: BlocksConfig,
+    pub banner: String,
+    pub window_title: WindowTitleConfig,
+    pub redact: RedactConfig,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub palette: Option<String>,
     pub palettes: HashMap<String, Palette>,
+    pub groups: HashMap<String, Vec<String>>,
+    pub fragments: HashMap<String, String>,
+    pub detect_priority: HashMap<String, i64>,
 }
 
 pub type Palette = HashMap<String, String>;
@@ -30,6 +48,7 @@ pub type Palette = HashMap<String, String>;
 pub const PROMPT_ORDER: &[&str] = &[
     "username",
     "hostname",
+    "context",
     "localip",
     "shlvl",
     "singularity",
@@ -44,6 +63,8 @@ pub const PROMPT_ORDER: &[&str] = &[
     "hg_branch",
     "docker_context",
     "package",
+    "license",
+    "cpp_package_manager",
     // ↓ Toolchain version modules ↓
     // (Let's keep these sorted alphabetically)
     "bun",
@@ -89,27 +110,40 @@ pub const PROMPT_ORDER: &[&str] = &[
     "buf",
     "guix_shell",
     "nix_shell",
+    "nix_generation",
     "conda",
+    "jupyter",
     "meson",
     "spack",
     "memory_usage",
+    "gpu",
+    "systemd",
+    "updates",
+    "reboot_required",
     "aws",
     "gcloud",
     "openstack",
     "azure",
+    "database",
+    "healthcheck",
+    "dotenv",
     "env_var",
     "crystal",
+    "cuda",
     "custom",
     "sudo",
     "cmd_duration",
+    "timer",
     "line_break",
     "jobs",
+    "history",
     #[cfg(feature = "battery")]
     "battery",
     "time",
     "status",
     "container",
     "os",
+    "arch",
     "shell",
     "character",
 ];
@@ -122,11 +156,25 @@ impl Default for StarshipRootConfig {
             format: "$all".to_string(),
             right_format: String::new(),
             continuation_prompt: "[∙](bright-black) ".to_string(),
+            select_prompt: String::new(),
+            trace_prompt: String::new(),
+            prompt_modes: HashMap::default(),
             scan_timeout: 30,
             command_timeout: 500,
             add_newline: true,
+            shell_integration: false,
+            symbol_set: "unicode".to_string(),
+            color_depth: "truecolor".to_string(),
+            separators: SeparatorsConfig::default(),
+            blocks: BlocksConfig::default(),
+            banner: String::new(),
+            window_title: WindowTitleConfig::default(),
+            redact: RedactConfig::default(),
             palette: None,
             palettes: HashMap::default(),
+            groups: HashMap::default(),
+            fragments: HashMap::default(),
+            detect_priority: HashMap::default(),
         }
     }
 }
@@ -16,6 +16,8 @@ pub struct StatusConfig<'a> {
     pub sigint_symbol: &'a str,
     pub signal_symbol: &'a str,
     pub style: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success_style: Option<&'a str>,
     pub map_symbol: bool,
     pub recognize_signal_code: bool,
     pub pipestatus: bool,
@@ -23,6 +25,8 @@ pub struct StatusConfig<'a> {
     pub pipestatus_format: &'a str,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pipestatus_segment_format: Option<&'a str>,
+    pub pipestatus_collapse: bool,
+    pub pipestatus_collapse_symbol: &'a str,
     pub disabled: bool,
 }
 
@@ -37,6 +41,7 @@ impl<'a> Default for StatusConfig<'a> {
             sigint_symbol: "🧱",
             signal_symbol: "⚡",
             style: "bold red",
+            success_style: None,
             map_symbol: false,
             recognize_signal_code: true,
             pipestatus: false,
@@ -44,6 +49,8 @@ impl<'a> Default for StatusConfig<'a> {
             pipestatus_format:
                 "\\[$pipestatus\\] => [$symbol$common_meaning$signal_name$maybe_int]($style)",
             pipestatus_segment_format: None,
+            pipestatus_collapse: false,
+            pipestatus_collapse_symbol: "…",
             disabled: true,
         }
     }
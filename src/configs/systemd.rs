@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct SystemdConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub user_units: bool,
+    pub refresh_interval: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for SystemdConfig<'a> {
+    fn default() -> Self {
+        SystemdConfig {
+            format: "[$symbol$failed failed]($style) ",
+            symbol: "⚠ ",
+            style: "bold red",
+            user_units: false,
+            refresh_interval: "1m",
+            disabled: true,
+        }
+    }
+}
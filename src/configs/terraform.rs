@@ -1,3 +1,5 @@
+use crate::config::DetectContent;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -10,12 +12,17 @@ use serde::{Deserialize, Serialize};
 pub struct TerraformConfig<'a> {
     pub format: &'a str,
     pub version_format: &'a str,
+    pub use_pinned_version: bool,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub mismatch_style: &'a str,
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
+    pub detect_files_not: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub detect_content: Vec<DetectContent<'a>>,
+    pub scan_ancestors: bool,
 }
 
 impl<'a> Default for TerraformConfig<'a> {
@@ -23,12 +30,17 @@ impl<'a> Default for TerraformConfig<'a> {
         TerraformConfig {
             format: "via [$symbol$workspace]($style) ",
             version_format: "v${raw}",
+            use_pinned_version: false,
             symbol: "💠 ",
             style: "bold 105",
+            mismatch_style: "",
             disabled: false,
             detect_extensions: vec!["tf", "tfplan", "tfstate"],
             detect_files: vec![],
+            detect_files_not: vec![],
             detect_folders: vec![".terraform"],
+            detect_content: vec![],
+            scan_ancestors: false,
         }
     }
 }
@@ -16,18 +16,50 @@ pub struct TimeConfig<'a> {
     pub disabled: bool,
     pub utc_time_offset: &'a str,
     pub time_range: &'a str,
+    #[serde(borrow)]
+    pub timezones: Vec<TimeZoneConfig<'a>>,
+    pub timezone_format: &'a str,
+    pub timezone_separator: &'a str,
 }
 
 impl<'a> Default for TimeConfig<'a> {
     fn default() -> Self {
         TimeConfig {
-            format: "at [$time]($style) ",
+            format: "at [$time]($style) ($timezones )",
             style: "bold yellow",
             use_12hr: false,
             time_format: None,
             disabled: true,
             utc_time_offset: "local",
             time_range: "-",
+            timezones: vec![],
+            timezone_format: "[$label $time]($style)",
+            timezone_separator: " ",
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct TimeZoneConfig<'a> {
+    pub label: &'a str,
+    pub utc_time_offset: &'a str,
+    pub time_format: Option<&'a str>,
+    pub style: Option<&'a str>,
+}
+
+impl<'a> Default for TimeZoneConfig<'a> {
+    fn default() -> Self {
+        TimeZoneConfig {
+            label: "",
+            utc_time_offset: "local",
+            time_format: None,
+            style: None,
         }
     }
 }
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct TimerConfig<'a> {
+    pub format: &'a str,
+    pub expired_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub expired_style: &'a str,
+    pub expired_display_time: i64,
+    pub disabled: bool,
+}
+
+impl<'a> Default for TimerConfig<'a> {
+    fn default() -> Self {
+        TimerConfig {
+            format: "[$symbol$remaining( $label)]($style) ",
+            expired_format: "[${symbol}done( $label)]($style) ",
+            symbol: "🍅 ",
+            style: "bold red",
+            expired_style: "bold green",
+            expired_display_time: 300_000,
+            disabled: true,
+        }
+    }
+}
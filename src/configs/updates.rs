@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct UpdatesConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub manager: &'a str,
+    pub file: &'a str,
+    pub refresh_interval: &'a str,
+    pub timeout: u64,
+    #[serde(borrow)]
+    pub display: Vec<UpdatesDisplayConfig<'a>>,
+    pub disabled: bool,
+}
+
+impl<'a> Default for UpdatesConfig<'a> {
+    fn default() -> Self {
+        UpdatesConfig {
+            format: "[$symbol$count]($style) ",
+            symbol: "⇡ ",
+            manager: "auto",
+            file: "",
+            refresh_interval: "6h",
+            timeout: 5000,
+            display: vec![
+                UpdatesDisplayConfig::default(),
+                UpdatesDisplayConfig {
+                    threshold: 50,
+                    style: "bold red",
+                },
+            ],
+            disabled: true,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct UpdatesDisplayConfig<'a> {
+    pub threshold: i64,
+    pub style: &'a str,
+}
+
+impl<'a> Default for UpdatesDisplayConfig<'a> {
+    fn default() -> Self {
+        UpdatesDisplayConfig {
+            threshold: 1,
+            style: "bold yellow",
+        }
+    }
+}
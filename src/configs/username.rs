@@ -12,6 +12,8 @@ pub struct UsernameConfig<'a> {
     pub style_root: &'a str,
     pub style_user: &'a str,
     pub show_always: bool,
+    pub show_sudo_user: bool,
+    pub sudo_user_separator: &'a str,
     pub disabled: bool,
 }
 
@@ -22,6 +24,8 @@ impl<'a> Default for UsernameConfig<'a> {
             style_root: "red bold",
             style_user: "yellow bold",
             show_always: false,
+            show_sudo_user: false,
+            sudo_user_separator: "→",
             disabled: false,
         }
     }
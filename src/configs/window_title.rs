@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct WindowTitleConfig {
+    pub format: String,
+    pub tab_color: String,
+    pub disabled: bool,
+}
+
+impl Default for WindowTitleConfig {
+    fn default() -> Self {
+        WindowTitleConfig {
+            format: "$directory".to_string(),
+            tab_color: String::new(),
+            disabled: true,
+        }
+    }
+}
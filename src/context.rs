@@ -1,32 +1,43 @@
-use crate::config::{ModuleConfig, StarshipConfig};
+use crate::config::{ColorDepth, DetectContent, ModuleConfig, StarshipConfig};
 use crate::configs::StarshipRootConfig;
+use crate::executor::CommandExecutor;
+use crate::host::{Host, LocalHost, SshExecutor, SshHost};
 use crate::module::Module;
-use crate::utils::{create_command, exec_timeout, read_file, CommandOutput};
+use crate::utils::CommandOutput;
 
 use crate::modules;
-use crate::utils::{self, home_dir};
+use crate::state::{self, SessionState};
+use crate::utils;
 use clap::Parser;
+#[cfg(feature = "git")]
 use git_repository::{
     self as git,
     sec::{self as git_sec, trust::DefaultForLevel},
     state as git_state, Repository, ThreadSafeRepository,
 };
 use once_cell::sync::OnceCell;
+use regex::Regex;
 #[cfg(test)]
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
 use std::ffi::{OsStr, OsString};
 use std::fmt::Debug;
-use std::fs;
 use std::marker::PhantomData;
 use std::num::ParseIntError;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::string::String;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use terminal_size::terminal_size;
 
+/// How much of a file is read to match it with `detect_content`
+const MAX_DETECT_CONTENT_SIZE: u64 = 1024 * 1024;
+
+/// How many directories above the current one are scanned with `scan_ancestors`
+const MAX_SCANNED_ANCESTORS: usize = 8;
+
 /// Context contains data or common methods that may be used by multiple modules.
 /// The data contained within Context will be relevant to this particular rendering
 /// of the prompt.
@@ -45,12 +56,21 @@ pub struct Context<'a> {
     /// A struct containing directory contents in a lookup-optimized format.
     dir_contents: OnceCell<DirContents>,
 
+    /// The contents of the directories above the current one, up to the repository root
+    ancestor_dir_contents: OnceCell<Vec<DirContents>>,
+
     /// Properties to provide to modules.
     pub properties: Properties,
 
     /// Private field to store Git information for modules who need it
     repo: OnceCell<Repo>,
 
+    /// The values set with `starship state set` for this shell session
+    session_state: OnceCell<SessionState>,
+
+    /// The patterns of `redact`, combined into one
+    redaction: OnceCell<Option<Regex>>,
+
     /// The shell the user is assumed to be running
     pub shell: Shell,
 
@@ -68,6 +88,12 @@ pub struct Context<'a> {
     #[cfg(test)]
     pub cmd: HashMap<&'a str, Option<CommandOutput>>,
 
+    /// Runs the commands of modules, see `exec_cmd`
+    pub executor: Arc<dyn CommandExecutor>,
+
+    /// Where the environment and the files of the current directory are read from
+    pub host: Arc<dyn Host>,
+
     /// a mock of the root directory
     #[cfg(test)]
     pub root_dir: tempfile::TempDir,
@@ -140,10 +166,26 @@ impl<'a> Context<'a> {
             properties.status_code = None;
         }
 
-        // Canonicalize the current path to resolve symlinks, etc.
-        // NOTE: On Windows this may convert the path to extended-path syntax.
-        let current_dir = Context::expand_tilde(path);
-        let current_dir = dunce::canonicalize(&current_dir).unwrap_or(current_dir);
+        #[cfg(not(test))]
+        let executor: Arc<dyn CommandExecutor> = Arc::new(crate::executor::SystemExecutor);
+        #[cfg(test)]
+        let executor: Arc<dyn CommandExecutor> = Arc::new(crate::executor::MockExecutor);
+        let (host, executor, current_dir): (Arc<dyn Host>, Arc<dyn CommandExecutor>, _) =
+            match &properties.remote {
+                // The path is on the remote machine, so it is left as it is
+                Some(destination) => (
+                    Arc::new(SshHost::new(destination.clone())),
+                    Arc::new(SshExecutor::new(destination.clone())),
+                    path,
+                ),
+                None => {
+                    // Canonicalize the current path to resolve symlinks, etc.
+                    // NOTE: On Windows this may convert the path to extended-path syntax.
+                    let current_dir = Context::expand_tilde(path);
+                    let current_dir = dunce::canonicalize(&current_dir).unwrap_or(current_dir);
+                    (Arc::new(LocalHost), executor, current_dir)
+                }
+            };
         let logical_dir = logical_path;
 
         let root_config = config
@@ -159,7 +201,10 @@ impl<'a> Context<'a> {
             current_dir,
             logical_dir,
             dir_contents: OnceCell::new(),
+            ancestor_dir_contents: OnceCell::new(),
             repo: OnceCell::new(),
+            session_state: OnceCell::new(),
+            redaction: OnceCell::new(),
             shell,
             target,
             width,
@@ -169,6 +214,8 @@ impl<'a> Context<'a> {
             env: HashMap::new(),
             #[cfg(test)]
             cmd: HashMap::new(),
+            executor,
+            host,
             #[cfg(feature = "battery")]
             battery_info_provider: &crate::modules::BatteryInfoProviderImpl,
             root_config,
@@ -179,10 +226,72 @@ impl<'a> Context<'a> {
     // Tries to retrieve home directory from a table in testing mode or else retrieves it from the os
     pub fn get_home(&self) -> Option<PathBuf> {
         if cfg!(test) {
-            return self.get_env("HOME").map(PathBuf::from).or_else(home_dir);
+            return self
+                .get_env("HOME")
+                .map(PathBuf::from)
+                .or_else(|| self.host.home_dir());
         }
 
-        home_dir()
+        self.host.home_dir()
+    }
+
+    /// Returns how many colors the terminal can show, see `color_depth`
+    pub fn color_depth(&self) -> ColorDepth {
+        ColorDepth::resolve(
+            &self.root_config.color_depth,
+            self.get_env("COLORTERM").as_deref(),
+            self.get_env("TERM").as_deref(),
+        )
+    }
+
+    /// Returns the values set with `starship state set` for the current shell session
+    pub fn get_session_state(&self) -> &SessionState {
+        self.session_state.get_or_init(|| state::read_state(self))
+    }
+
+    /// The pattern that matches the secrets to hide from the prompt, see `RedactConfig`
+    pub fn redaction(&self) -> Option<&Regex> {
+        self.redaction
+            .get_or_init(|| {
+                let config = &self.root_config.redact;
+                if config.disabled {
+                    return None;
+                }
+                // A broken pattern only leaves out its own secrets
+                let patterns: Vec<String> = config
+                    .patterns
+                    .iter()
+                    .filter(|pattern| match Regex::new(pattern) {
+                        Ok(_) => true,
+                        Err(error) => {
+                            log::warn!("Invalid pattern in `redact.patterns`:\n{error}");
+                            false
+                        }
+                    })
+                    .map(|pattern| format!("(?:{pattern})"))
+                    .collect();
+                if patterns.is_empty() {
+                    return None;
+                }
+                Regex::new(&patterns.join("|")).ok()
+            })
+            .as_ref()
+    }
+
+    /// The `format` of the prompt, or the one of the prompt mode chosen with `starship mode`
+    pub fn prompt_format(&self) -> &str {
+        self.get_session_state()
+            .get(state::PROMPT_MODE_KEY)
+            .and_then(|mode| self.root_config.prompt_modes.get(mode))
+            .unwrap_or(&self.root_config.format)
+    }
+
+    /// Returns the directory starship keeps its logs and state in
+    pub fn get_cache_dir(&self) -> Option<PathBuf> {
+        match self.get_env("STARSHIP_CACHE") {
+            Some(cache_dir) => Some(PathBuf::from(cache_dir)),
+            None => Some(self.get_home()?.join(".cache/starship")),
+        }
     }
 
     // Retrieves a environment variable from the os or from a table if in testing mode
@@ -196,7 +305,7 @@ impl<'a> Context<'a> {
     #[cfg(not(test))]
     #[inline]
     pub fn get_env<K: AsRef<str>>(&self, key: K) -> Option<String> {
-        env::var(key.as_ref()).ok()
+        self.host.get_env(key.as_ref())?.into_string().ok()
     }
 
     // Retrieves a environment variable from the os or from a table if in testing mode (os version)
@@ -208,7 +317,7 @@ impl<'a> Context<'a> {
     #[cfg(not(test))]
     #[inline]
     pub fn get_env_os<K: AsRef<str>>(&self, key: K) -> Option<OsString> {
-        env::var_os(key.as_ref())
+        self.host.get_env(key.as_ref())
     }
 
     /// Convert a `~` in a path to the home directory
@@ -228,14 +337,15 @@ impl<'a> Context<'a> {
         Module::new(name, desc, config)
     }
 
-    /// Check if `disabled` option of the module is true in configuration file.
+    /// Check if `disabled` option of the module is true in configuration file,
+    /// or if the module is hidden for the shell session with `starship toggle`.
     pub fn is_module_disabled_in_config(&self, name: &str) -> bool {
         let config = self.config.get_module_config(name);
 
         // If the segment has "disabled" set to "true", don't show it
         let disabled = config.and_then(|table| table.as_table()?.get("disabled")?.as_bool());
 
-        disabled == Some(true)
+        disabled == Some(true) || state::is_hidden(self.get_session_state(), name)
     }
 
     /// Return whether the specified custom module has a `disabled` option set to true.
@@ -244,7 +354,10 @@ impl<'a> Context<'a> {
         let config = self.config.get_custom_module_config(name)?;
         let disabled = Some(config).and_then(|table| table.as_table()?.get("disabled")?.as_bool());
 
-        Some(disabled == Some(true))
+        Some(
+            disabled == Some(true)
+                || state::is_hidden(self.get_session_state(), &format!("custom.{name}")),
+        )
     }
 
     // returns a new ScanDir struct with reference to current dir_files of context
@@ -253,76 +366,132 @@ impl<'a> Context<'a> {
         Some(ScanDir {
             dir_contents: self.dir_contents().ok()?,
             files: &[],
+            files_not: &[],
             folders: &[],
             extensions: &[],
+            content: &[],
+            context: Some(self),
+            scan_ancestors: false,
         })
     }
 
+    /// Whether the prompt is rendered for the machine starship runs on
+    ///
+    /// Modules that read files without going through `dir_contents` would see the files of
+    /// this machine for `--remote`, so they must not show when this is `false`.
+    pub fn is_local(&self) -> bool {
+        self.host.is_local()
+    }
+
     /// Will lazily get repo root and branch when a module requests it.
-    pub fn get_repo(&self) -> Result<&Repo, git::discover::Error> {
-        self.repo
-            .get_or_try_init(|| -> Result<Repo, git::discover::Error> {
-                // custom open options
-                let mut git_open_opts_map =
-                    git_sec::trust::Mapping::<git::open::Options>::default();
-
-                // don't use the global git configs
-                let config = git::permissions::Config {
-                    git_binary: false,
-                    system: false,
-                    git: false,
-                    user: false,
-                    env: true,
-                    includes: true,
-                };
-                // change options for config permissions without touching anything else
-                git_open_opts_map.reduced =
-                    git_open_opts_map.reduced.permissions(git::Permissions {
-                        config,
-                        ..git::Permissions::default_for_level(git_sec::Trust::Reduced)
-                    });
-                git_open_opts_map.full = git_open_opts_map.full.permissions(git::Permissions {
-                    config,
-                    ..git::Permissions::default_for_level(git_sec::Trust::Full)
-                });
-
-                let shared_repo =
-                    match ThreadSafeRepository::discover_with_environment_overrides_opts(
-                        &self.current_dir,
-                        Default::default(),
-                        git_open_opts_map,
-                    ) {
-                        Ok(repo) => repo,
-                        Err(e) => {
-                            log::debug!("Failed to find git repo: {e}");
-                            return Err(e);
-                        }
-                    };
-
-                let repository = shared_repo.to_thread_local();
-                log::trace!(
-                    "Found git repo: {repository:?}, (trust: {:?})",
-                    repository.git_dir_trust()
-                );
-
-                let branch = get_current_branch(&repository);
-                let remote = get_remote_repository_info(&repository, branch.as_deref());
-                let path = repository.path().to_path_buf();
-                Ok(Repo {
-                    repo: shared_repo,
-                    branch,
-                    workdir: repository.work_dir().map(PathBuf::from),
-                    path,
-                    state: repository.state(),
-                    remote,
-                })
+    #[cfg(feature = "git")]
+    pub fn get_repo(&self) -> Result<&Repo, RepoError> {
+        self.repo.get_or_try_init(|| -> Result<Repo, RepoError> {
+            // Repositories are opened from the local disk, which doesn't have the remote one
+            if !self.is_local() {
+                log::debug!("Not looking for a git repo on a remote host");
+                return Err(git::discover::upwards::Error::InaccessibleDirectory {
+                    path: self.current_dir.clone(),
+                }
+                .into());
+            }
+
+            // custom open options
+            let mut git_open_opts_map = git_sec::trust::Mapping::<git::open::Options>::default();
+
+            // don't use the global git configs
+            let config = git::permissions::Config {
+                git_binary: false,
+                system: false,
+                git: false,
+                user: false,
+                env: true,
+                includes: true,
+            };
+            // change options for config permissions without touching anything else
+            git_open_opts_map.reduced = git_open_opts_map.reduced.permissions(git::Permissions {
+                config,
+                ..git::Permissions::default_for_level(git_sec::Trust::Reduced)
+            });
+            git_open_opts_map.full = git_open_opts_map.full.permissions(git::Permissions {
+                config,
+                ..git::Permissions::default_for_level(git_sec::Trust::Full)
+            });
+
+            let shared_repo = match ThreadSafeRepository::discover_with_environment_overrides_opts(
+                &self.current_dir,
+                Default::default(),
+                git_open_opts_map,
+            ) {
+                Ok(repo) => repo,
+                Err(e) => {
+                    log::debug!("Failed to find git repo: {e}");
+                    return Err(e);
+                }
+            };
+
+            let repository = shared_repo.to_thread_local();
+            log::trace!(
+                "Found git repo: {repository:?}, (trust: {:?})",
+                repository.git_dir_trust()
+            );
+
+            let branch = get_current_branch(&repository);
+            let remote = get_remote_repository_info(&repository, branch.as_deref());
+            let path = repository.path().to_path_buf();
+            Ok(Repo {
+                repo: shared_repo,
+                branch,
+                workdir: repository.work_dir().map(PathBuf::from),
+                path,
+                state: repository.state(),
+                remote,
             })
+        })
+    }
+
+    /// Repositories are only discovered when starship is built with the `git` feature
+    #[cfg(not(feature = "git"))]
+    pub fn get_repo(&self) -> Result<&Repo, RepoError> {
+        self.repo.get_or_try_init(|| {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "starship was built without the `git` feature",
+            ))
+        })
     }
 
     pub fn dir_contents(&self) -> Result<&DirContents, std::io::Error> {
         self.dir_contents.get_or_try_init(|| {
             let timeout = self.root_config.scan_timeout;
-            DirContents::from_path_with_timeout(&self.current_dir, Duration::from_millis(timeout))
+            DirContents::from_host(
+                self.host.clone(),
+                &self.current_dir,
+                Duration::from_millis(timeout),
+            )
+        })
+    }
+
+    /// The contents of the directories above the current one, closest first, up to the root
+    /// of its repository, and at most `MAX_SCANNED_ANCESTORS` of them. Outside of a
+    /// repository, there are none.
+    pub fn ancestor_dir_contents(&self) -> &[DirContents] {
+        self.ancestor_dir_contents.get_or_init(|| {
+            let Some(root) = self
+                .get_repo()
+                .ok()
+                .and_then(|repo| repo.workdir.as_deref())
+            else {
+                return Vec::new();
+            };
+            let timeout = Duration::from_millis(self.root_config.scan_timeout);
+            self.current_dir
+                .ancestors()
+                .skip(1)
+                .take(MAX_SCANNED_ANCESTORS)
+                .take_while(|dir| dir.starts_with(root))
+                .filter_map(|dir| DirContents::from_host(self.host.clone(), dir, timeout).ok())
+                .collect()
         })
     }
 
@@ -338,6 +507,10 @@ impl<'a> Context<'a> {
             "tcsh" => Shell::Tcsh,
             "nu" => Shell::Nu,
             "xonsh" => Shell::Xonsh,
+            "murex" => Shell::Murex,
+            "osh" => Shell::Osh,
+            "ysh" => Shell::Ysh,
+            "rc" => Shell::Rc,
             "cmd" => Shell::Cmd,
             _ => Shell::Unknown,
         }
@@ -351,12 +524,33 @@ impl<'a> Context<'a> {
             .and_then(|cd| cd.parse::<u128>().ok())
     }
 
+    /// Returns the name of the last command, without any leading path
+    pub fn get_cmd_name(&self) -> Option<&str> {
+        let cmd_name = self.properties.cmd_name.as_deref()?.trim();
+        let cmd_name = cmd_name.rsplit(['/', '\\']).next().unwrap_or(cmd_name);
+        (!cmd_name.is_empty()).then_some(cmd_name)
+    }
+
     /// Execute a command and return the output on stdout and stderr if successful
     #[inline]
     pub fn exec_cmd<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
         &self,
         cmd: T,
         args: &[U],
+    ) -> Option<CommandOutput> {
+        self.exec_cmd_with_timeout(
+            cmd,
+            args,
+            Duration::from_millis(self.root_config.command_timeout),
+        )
+    }
+
+    /// Execute a command like `exec_cmd`, with its own time limit instead of `command_timeout`
+    pub fn exec_cmd_with_timeout<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
+        &self,
+        cmd: T,
+        args: &[U],
+        time_limit: Duration,
     ) -> Option<CommandOutput> {
         log::trace!(
             "Executing command {:?} with args {:?} from context",
@@ -366,21 +560,13 @@ impl<'a> Context<'a> {
         #[cfg(test)]
         {
             let command = crate::utils::display_command(&cmd, args);
-            if let Some(output) = self
-                .cmd
-                .get(command.as_str())
-                .cloned()
-                .or_else(|| crate::utils::mock_cmd(&cmd, args))
-            {
+            if let Some(output) = self.cmd.get(command.as_str()).cloned() {
                 return output;
             }
         }
-        let mut cmd = create_command(cmd).ok()?;
-        cmd.args(args).current_dir(&self.current_dir);
-        exec_timeout(
-            &mut cmd,
-            Duration::from_millis(self.root_config.command_timeout),
-        )
+        let args: Vec<&OsStr> = args.iter().map(AsRef::as_ref).collect();
+        self.executor
+            .exec(cmd.as_ref(), &args, &self.current_dir, time_limit)
     }
 
     /// Attempt to execute several commands with `exec_cmd`, return the results of the first that works
@@ -399,12 +585,20 @@ impl<'a> Context<'a> {
             return None;
         }
 
-        read_file(self.current_dir.join(file_name)).ok()
+        let contents = self
+            .host
+            .read_file(&self.current_dir.join(file_name), None)
+            .ok()?;
+        String::from_utf8(contents).ok()
     }
 }
 
 #[derive(Debug)]
 pub struct DirContents {
+    // The host the directory is on, to read the files in it from.
+    host: Arc<dyn Host>,
+    // The directory the contents are of.
+    path: PathBuf,
     // HashSet of all files, no folders, relative to the base directory given at construction.
     files: HashSet<PathBuf>,
     // HashSet of all file names, e.g. the last section without any folders, as strings.
@@ -418,10 +612,14 @@ pub struct DirContents {
 impl DirContents {
     #[cfg(test)]
     fn from_path(base: &Path) -> Result<Self, std::io::Error> {
-        Self::from_path_with_timeout(base, Duration::from_secs(30))
+        Self::from_host(Arc::new(LocalHost), base, Duration::from_secs(30))
     }
 
-    fn from_path_with_timeout(base: &Path, timeout: Duration) -> Result<Self, std::io::Error> {
+    fn from_host(
+        host: Arc<dyn Host>,
+        base: &Path,
+        timeout: Duration,
+    ) -> Result<Self, std::io::Error> {
         let start = Instant::now();
 
         let mut folders: HashSet<PathBuf> = HashSet::new();
@@ -429,46 +627,38 @@ impl DirContents {
         let mut file_names: HashSet<String> = HashSet::new();
         let mut extensions: HashSet<String> = HashSet::new();
 
-        fs::read_dir(base)?
-            .enumerate()
-            .take_while(|(n, _)| {
-                cfg!(test) // ignore timeout during tests
-                || n & 0xFF != 0 // only check timeout once every 2^8 entries
-                || start.elapsed() < timeout
-            })
-            .filter_map(|(_, entry)| entry.ok())
-            .for_each(|entry| {
-                let path = PathBuf::from(entry.path().strip_prefix(base).unwrap());
-                if entry.path().is_dir() {
-                    folders.insert(path);
-                } else {
-                    if !path.to_string_lossy().starts_with('.') {
-                        // Extract the file extensions (yes, that's plural) from a filename.
-                        // Why plural? Consider the case of foo.tar.gz. It's a compressed
-                        // tarball (tar.gz), and it's a gzipped file (gz). We should be able
-                        // to match both.
-
-                        // find the minimal extension on a file. ie, the gz in foo.tar.gz
-                        // NB the .to_string_lossy().to_string() here looks weird but is
-                        // required to convert it from a Cow.
-                        path.extension()
-                            .map(|ext| extensions.insert(ext.to_string_lossy().to_string()));
-
-                        // find the full extension on a file. ie, the tar.gz in foo.tar.gz
-                        path.file_name().map(|file_name| {
-                            file_name
-                                .to_string_lossy()
-                                .split_once('.')
-                                .map(|(_, after)| extensions.insert(after.to_string()))
-                        });
-                    }
-                    if let Some(file_name) = path.file_name() {
-                        // this .to_string_lossy().to_string() is also required
-                        file_names.insert(file_name.to_string_lossy().to_string());
-                    }
-                    files.insert(path);
+        host.read_dir(base, timeout)?.into_iter().for_each(|entry| {
+            let path = PathBuf::from(entry.name);
+            if entry.is_dir {
+                folders.insert(path);
+            } else {
+                if !path.to_string_lossy().starts_with('.') {
+                    // Extract the file extensions (yes, that's plural) from a filename.
+                    // Why plural? Consider the case of foo.tar.gz. It's a compressed
+                    // tarball (tar.gz), and it's a gzipped file (gz). We should be able
+                    // to match both.
+
+                    // find the minimal extension on a file. ie, the gz in foo.tar.gz
+                    // NB the .to_string_lossy().to_string() here looks weird but is
+                    // required to convert it from a Cow.
+                    path.extension()
+                        .map(|ext| extensions.insert(ext.to_string_lossy().to_string()));
+
+                    // find the full extension on a file. ie, the tar.gz in foo.tar.gz
+                    path.file_name().map(|file_name| {
+                        file_name
+                            .to_string_lossy()
+                            .split_once('.')
+                            .map(|(_, after)| extensions.insert(after.to_string()))
+                    });
                 }
-            });
+                if let Some(file_name) = path.file_name() {
+                    // this .to_string_lossy().to_string() is also required
+                    file_names.insert(file_name.to_string_lossy().to_string());
+                }
+                files.insert(path);
+            }
+        });
 
         log::trace!(
             "Building HashSets of directory files, folders and extensions took {:?}",
@@ -476,6 +666,8 @@ impl DirContents {
         );
 
         Ok(Self {
+            host,
+            path: base.to_path_buf(),
             files,
             file_names,
             folders,
@@ -487,6 +679,21 @@ impl DirContents {
         self.files.iter()
     }
 
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The string contents of a file in the directory, or in one of its folders. Files right in
+    /// it are only read when they were listed.
+    pub fn read_file(&self, path: &str) -> Option<String> {
+        let path = Path::new(path);
+        if path.parent() == Some(Path::new("")) && !self.files.contains(path) {
+            return None;
+        }
+        let contents = self.host.read_file(&self.path.join(path), None).ok()?;
+        String::from_utf8(contents).ok()
+    }
+
     pub fn has_file(&self, path: &str) -> bool {
         self.files.contains(Path::new(path))
     }
@@ -537,9 +744,38 @@ impl DirContents {
             .iter()
             .any(|ext| ext.starts_with('!') && self.has_extension(&ext[1..]))
     }
+
+    /// Whether the contents of a file in the directory match one of the rules. Only the
+    /// first `MAX_DETECT_CONTENT_SIZE` bytes of a file are read.
+    pub fn has_any_matching_content(&self, rules: &[DetectContent]) -> bool {
+        rules.iter().any(|rule| {
+            if !self.files.contains(Path::new(rule.file)) {
+                return false;
+            }
+            let regex = match Regex::new(rule.regex) {
+                Ok(regex) => regex,
+                Err(error) => {
+                    log::warn!("Invalid regex in `detect_content`: {}", error);
+                    return false;
+                }
+            };
+            self.host
+                .read_file(&self.path.join(rule.file), Some(MAX_DETECT_CONTENT_SIZE))
+                .map_or(false, |contents| {
+                    regex.is_match(&String::from_utf8_lossy(&contents))
+                })
+        })
+    }
 }
 
+/// The error of `Context::get_repo` when no repository was found
+#[cfg(feature = "git")]
+pub type RepoError = git::discover::Error;
+#[cfg(not(feature = "git"))]
+pub type RepoError = std::io::Error;
+
 pub struct Repo {
+    #[cfg(feature = "git")]
     pub repo: ThreadSafeRepository,
 
     /// If `current_dir` is a git repository or is contained within one,
@@ -554,17 +790,66 @@ pub struct Repo {
     pub path: PathBuf,
 
     /// State
+    #[cfg(feature = "git")]
     pub state: Option<git_state::InProgress>,
 
     /// Remote repository
     pub remote: Option<Remote>,
 }
 
+#[cfg(feature = "git")]
 impl Repo {
     /// Opens the associated git repository.
     pub fn open(&self) -> Repository {
         self.repo.to_thread_local()
     }
+
+    /// The number of entries in the index, read from its header
+    pub fn index_entries(&self) -> Option<u32> {
+        let mut header = [0; 12];
+        std::fs::File::open(self.path.join("index"))
+            .and_then(|mut index| std::io::Read::read_exact(&mut index, &mut header))
+            .ok()?;
+        (&header[..4] == b"DIRC")
+            .then(|| u32::from_be_bytes([header[8], header[9], header[10], header[11]]))
+    }
+
+    /// Whether the repository has more than `max_index_entries` files in its index, for
+    /// modules that are too slow in large repositories. A limit below 1 means no limit, and so
+    /// does a `starship-allow` file in the `.git` directory.
+    pub fn exceeds_size(&self, max_index_entries: i64) -> bool {
+        if max_index_entries < 1 || self.path.join("starship-allow").exists() {
+            return false;
+        }
+        let exceeds = self
+            .index_entries()
+            .map_or(false, |entries| i64::from(entries) > max_index_entries);
+        if exceeds {
+            log::debug!("The index has more than {max_index_entries} entries, skipping");
+        }
+        exceeds
+    }
+
+    /// The web page of the repository on its forge (e.g. GitHub), guessed from the URL
+    /// of the remote of the current branch, or of `origin`.
+    pub fn web_url(&self) -> Option<String> {
+        let name = self
+            .remote
+            .as_ref()
+            .and_then(|remote| remote.name.as_deref())
+            .unwrap_or("origin");
+        let repository = self.open();
+        let remote = repository.find_remote(name).ok()?;
+        let url = remote.url(git::remote::Direction::Fetch)?;
+        web_url(url.host()?, &String::from_utf8_lossy(&url.path))
+    }
+}
+
+#[cfg(feature = "git")]
+fn web_url(host: &str, path: &str) -> Option<String> {
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    (!path.is_empty()).then(|| format!("https://{host}/{path}"))
 }
 
 /// Remote repository
@@ -578,8 +863,13 @@ pub struct Remote {
 pub struct ScanDir<'a> {
     dir_contents: &'a DirContents,
     files: &'a [&'a str],
+    files_not: &'a [&'a str],
     folders: &'a [&'a str],
     extensions: &'a [&'a str],
+    content: &'a [DetectContent<'a>],
+    /// The context of the scan, which holds the directories above the current one
+    context: Option<&'a Context<'a>>,
+    scan_ancestors: bool,
 }
 
 impl<'a> ScanDir<'a> {
@@ -589,6 +879,13 @@ impl<'a> ScanDir<'a> {
         self
     }
 
+    /// Files whose presence prevents the match, as with the `!` prefix in `set_files`
+    #[must_use]
+    pub const fn set_files_not(mut self, files: &'a [&'a str]) -> Self {
+        self.files_not = files;
+        self
+    }
+
     #[must_use]
     pub const fn set_extensions(mut self, extensions: &'a [&'a str]) -> Self {
         self.extensions = extensions;
@@ -601,22 +898,59 @@ impl<'a> ScanDir<'a> {
         self
     }
 
+    /// Files whose contents have to match a regex, see `DetectContent`
+    #[must_use]
+    pub const fn set_content(mut self, content: &'a [DetectContent<'a>]) -> Self {
+        self.content = content;
+        self
+    }
+
+    /// Also scan the directories above the current one, up to the root of its repository
+    #[must_use]
+    pub const fn set_scan_ancestors(mut self, scan_ancestors: bool) -> Self {
+        self.scan_ancestors = scan_ancestors;
+        self
+    }
+
     /// based on the current `PathBuf` check to see
     /// if any of this criteria match or exist and returning a boolean
+    ///
+    /// When ancestors are scanned, the closest directory with a file/folder/ext
+    /// we're looking for, or one we've said we don't want, decides the match.
     pub fn is_match(&self) -> bool {
+        let ancestors = match self.context {
+            Some(context) if self.scan_ancestors => context.ancestor_dir_contents(),
+            _ => &[],
+        };
+        std::iter::once(self.dir_contents)
+            .chain(ancestors)
+            .find_map(|dir_contents| self.match_dir(dir_contents))
+            .unwrap_or(false)
+    }
+
+    fn match_dir(&self, dir_contents: &DirContents) -> Option<bool> {
         // if there exists a file with a file/folder/ext we've said we don't want,
         // fail the match straight away
-        self.dir_contents.has_no_negative_extension(self.extensions)
-            && self.dir_contents.has_no_negative_file_name(self.files)
-            && self.dir_contents.has_no_negative_folder(self.folders)
-            && (self
-                .dir_contents
-                .has_any_positive_extension(self.extensions)
-                || self.dir_contents.has_any_positive_file_name(self.files)
-                || self.dir_contents.has_any_positive_folder(self.folders))
+        if !dir_contents.has_no_negative_extension(self.extensions)
+            || !dir_contents.has_no_negative_file_name(self.files)
+            || !dir_contents.has_no_negative_folder(self.folders)
+            || self
+                .files_not
+                .iter()
+                .any(|name| dir_contents.has_file_name(name))
+        {
+            return Some(false);
+        }
+
+        let is_match = dir_contents.has_any_positive_extension(self.extensions)
+            || dir_contents.has_any_positive_file_name(self.files)
+            || dir_contents.has_any_positive_folder(self.folders)
+            || dir_contents.has_any_matching_content(self.content);
+        is_match.then_some(true)
     }
 }
 
+#[cfg(feature = "git")]
 fn get_current_branch(repository: &Repository) -> Option<String> {
     let name = repository.head_name().ok()??;
     let shorthand = name.shorten();
@@ -624,6 +958,7 @@ fn get_current_branch(repository: &Repository) -> Option<String> {
     Some(shorthand.to_string())
 }
 
+#[cfg(feature = "git")]
 fn get_remote_repository_info(
     repository: &Repository,
     branch_name: Option<&str>,
@@ -651,6 +986,10 @@ pub enum Shell {
     Tcsh,
     Nu,
     Xonsh,
+    Murex,
+    Osh,
+    Ysh,
+    Rc,
     Cmd,
     Unknown,
 }
@@ -661,6 +1000,8 @@ pub enum Target {
     Main,
     Right,
     Continuation,
+    Select,
+    Trace,
 }
 
 /// Properties as passed on from the shell as arguments
@@ -669,7 +1010,7 @@ pub struct Properties {
     /// The status code of the previously run command as an unsigned or signed 32bit integer
     #[clap(short = 's', long = "status")]
     pub status_code: Option<String>,
-    /// Bash, Fish and Zsh support returning codes for each process in a pipeline.
+    /// Bash, Elvish, Fish and Zsh support returning codes for each process in a pipeline.
     #[clap(long, value_delimiter = ' ')]
     pub pipestatus: Option<Vec<String>>,
     /// The width of the current interactive terminal.
@@ -685,12 +1026,36 @@ pub struct Properties {
     /// The execution duration of the last command, in milliseconds
     #[clap(short = 'd', long)]
     pub cmd_duration: Option<String>,
+    /// The name of the last command, used to look up per-command settings
+    #[clap(long)]
+    pub cmd_name: Option<String>,
     /// The keymap of fish/zsh/cmd
     #[clap(short = 'k', long, default_value = "viins")]
     pub keymap: String,
     /// The number of currently running jobs
     #[clap(short, long, default_value_t, value_parser=parse_jobs)]
     pub jobs: i64,
+    /// The number of jobs that are stopped, out of `jobs`
+    #[clap(long, default_value_t, value_parser=parse_jobs)]
+    pub stopped_jobs: i64,
+    /// The command of the most recent background job
+    #[clap(long)]
+    pub last_job: Option<String>,
+    /// Mark the prompt for terminals with shell integration, like the `shell_integration` option
+    #[clap(long)]
+    pub shell_integration: bool,
+    /// Render the prompt for PATH on another machine, reached with `ssh DESTINATION`
+    #[clap(long, value_name = "DESTINATION", requires = "path")]
+    pub remote: Option<String>,
+    /// The version of the running shell
+    #[clap(long)]
+    pub shell_version: Option<String>,
+    /// Whether the running shell is a login shell
+    #[clap(long)]
+    pub login_shell: bool,
+    /// Whether the running shell is a restricted shell
+    #[clap(long)]
+    pub restricted_shell: bool,
 }
 
 impl Default for Properties {
@@ -702,8 +1067,16 @@ impl Default for Properties {
             path: None,
             logical_path: None,
             cmd_duration: None,
+            cmd_name: None,
             keymap: "viins".to_string(),
             jobs: 0,
+            stopped_jobs: 0,
+            last_job: None,
+            shell_integration: false,
+            remote: None,
+            shell_version: None,
+            login_shell: false,
+            restricted_shell: false,
         }
     }
 }
@@ -732,8 +1105,23 @@ fn parse_width(width: &str) -> Result<usize, ParseIntError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use std::io;
 
+    #[test]
+    #[cfg(feature = "git")]
+    fn test_web_url() {
+        assert_eq!(
+            web_url("github.com", "/starship/starship.git"),
+            Some("https://github.com/starship/starship".to_string())
+        );
+        assert_eq!(
+            web_url("gitlab.com", "group/project/"),
+            Some("https://gitlab.com/group/project".to_string())
+        );
+        assert_eq!(web_url("example.com", "/"), None);
+    }
+
     fn testdir(paths: &[&str]) -> Result<tempfile::TempDir, std::io::Error> {
         let dir = tempfile::tempdir()?;
         for path in paths {
@@ -754,8 +1142,12 @@ mod tests {
         assert!(!ScanDir {
             dir_contents: &empty_dc,
             files: &["package.json"],
+            files_not: &[],
             extensions: &["js"],
             folders: &["node_modules"],
+            content: &[],
+            context: None,
+            scan_ancestors: false,
         }
         .is_match());
         empty.close()?;
@@ -765,8 +1157,12 @@ mod tests {
         assert!(!ScanDir {
             dir_contents: &rust_dc,
             files: &["package.json"],
+            files_not: &[],
             extensions: &["js"],
             folders: &["node_modules"],
+            content: &[],
+            context: None,
+            scan_ancestors: false,
         }
         .is_match());
         rust.close()?;
@@ -776,8 +1172,12 @@ mod tests {
         assert!(!ScanDir {
             dir_contents: &java_dc,
             files: &["package.json"],
+            files_not: &[],
             extensions: &["js"],
             folders: &["node_modules"],
+            content: &[],
+            context: None,
+            scan_ancestors: false,
         }
         .is_match());
         java.close()?;
@@ -787,8 +1187,12 @@ mod tests {
         assert!(ScanDir {
             dir_contents: &node_dc,
             files: &["package.json"],
+            files_not: &[],
             extensions: &["js"],
             folders: &["node_modules"],
+            content: &[],
+            context: None,
+            scan_ancestors: false,
         }
         .is_match());
         node.close()?;
@@ -798,8 +1202,12 @@ mod tests {
         assert!(ScanDir {
             dir_contents: &tarballs_dc,
             files: &[],
+            files_not: &[],
             extensions: &["tar.gz"],
             folders: &[],
+            content: &[],
+            context: None,
+            scan_ancestors: false,
         }
         .is_match());
         tarballs.close()?;
@@ -809,8 +1217,12 @@ mod tests {
         assert!(!ScanDir {
             dir_contents: &dont_match_ext_dc,
             files: &[],
+            files_not: &[],
             extensions: &["js", "!notfound", "!ts"],
             folders: &[],
+            content: &[],
+            context: None,
+            scan_ancestors: false,
         }
         .is_match());
         dont_match_ext.close()?;
@@ -820,8 +1232,12 @@ mod tests {
         assert!(!ScanDir {
             dir_contents: &dont_match_file_dc,
             files: &["goodfile", "!notfound", "!evilfile"],
+            files_not: &[],
             extensions: &[],
             folders: &[],
+            content: &[],
+            context: None,
+            scan_ancestors: false,
         }
         .is_match());
         dont_match_file.close()?;
@@ -831,12 +1247,31 @@ mod tests {
         assert!(!ScanDir {
             dir_contents: &dont_match_folder_dc,
             files: &[],
+            files_not: &[],
             extensions: &[],
             folders: &["gooddir", "!notfound", "!evildir"],
+            content: &[],
+            context: None,
+            scan_ancestors: false,
         }
         .is_match());
         dont_match_folder.close()?;
 
+        let dont_match_files_not = testdir(&["goodfile", "evilfile"])?;
+        let dont_match_files_not_dc = DirContents::from_path(dont_match_files_not.path())?;
+        assert!(!ScanDir {
+            dir_contents: &dont_match_files_not_dc,
+            files: &["goodfile"],
+            files_not: &["notfound", "evilfile"],
+            extensions: &[],
+            folders: &[],
+            content: &[],
+            context: None,
+            scan_ancestors: false,
+        }
+        .is_match());
+        dont_match_files_not.close()?;
+
         Ok(())
     }
 
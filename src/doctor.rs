@@ -0,0 +1,324 @@
+use crate::bug_report::get_config_path;
+use crate::config::StarshipConfig;
+use crate::context::{Context, Properties, Target};
+use crate::print::compute_modules;
+use crate::utils;
+use nu_ansi_term::{Color, Style};
+use regex::Regex;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A problem found by `starship doctor`, along with how to fix it
+#[derive(Debug, PartialEq, Eq)]
+struct Finding {
+    problem: String,
+    fix: String,
+}
+
+impl Finding {
+    fn new(problem: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            problem: problem.into(),
+            fix: fix.into(),
+        }
+    }
+}
+
+pub fn diagnose() {
+    let shell = env::var("STARSHIP_SHELL")
+        .ok()
+        .filter(|shell| !shell.is_empty())
+        .or_else(|| {
+            env::var_os("SHELL").and_then(|shell| {
+                Path::new(&shell)
+                    .file_stem()
+                    .map(|name| name.to_string_lossy().to_string())
+            })
+        });
+
+    let shell_findings = match &shell {
+        Some(shell) => match get_config_path(shell) {
+            Some(path) => {
+                let content = fs::read_to_string(&path).unwrap_or_default();
+                check_shell_config(shell, &path, &content)
+            }
+            None => vec![],
+        },
+        None => vec![Finding::new(
+            "Unable to tell which shell is in use",
+            "Run `starship doctor` from a shell where starship is initialized",
+        )],
+    };
+
+    let path_var = env::var_os("PATH").unwrap_or_default();
+    let path_findings = check_path(&env::split_paths(&path_var).collect::<Vec<_>>());
+
+    let sections = [
+        ("Shell configuration", shell_findings),
+        ("Conflicting prompts", check_environment()),
+        ("Locale", check_locale()),
+        ("PATH", path_findings),
+        ("Version commands", check_version_commands()),
+    ];
+
+    let mut problems = 0;
+    for (title, findings) in sections {
+        if findings.is_empty() {
+            println!("{} {title}", Color::Green.bold().paint("✓"));
+            continue;
+        }
+        println!("{} {title}", Color::Yellow.bold().paint("!"));
+        for finding in &findings {
+            println!("    {}", finding.problem);
+            println!("    {} {}", Style::new().bold().paint("Fix:"), finding.fix);
+        }
+        problems += findings.len();
+    }
+
+    if uses_nerd_font() {
+        println!(
+            "\nYour configuration uses Nerd Font symbols. If one between these brackets shows as a \
+             box or a question mark, install a Nerd Font from https://www.nerdfonts.com and \
+             select it in your terminal: [{NERD_FONT_SAMPLE}]"
+        );
+    }
+    if problems == 0 {
+        println!("No problems were found.");
+    }
+}
+
+const NERD_FONT_SAMPLE: &str = "\u{e0a0} \u{f113} \u{e7a8}";
+
+fn init_line(shell: &str) -> String {
+    match shell {
+        "fish" => "starship init fish | source".to_string(),
+        "elvish" => "eval (starship init elvish)".to_string(),
+        "nu" => "use ~/.cache/starship/init.nu".to_string(),
+        "xonsh" => "execx($(starship init xonsh))".to_string(),
+        "tcsh" => "eval `starship init tcsh`".to_string(),
+        "powershell" | "pwsh" => "Invoke-Expression (&starship init powershell)".to_string(),
+        "cmd" => "load(io.popen('starship init cmd'):read(\"*a\"))()".to_string(),
+        _ => format!("eval \"$(starship init {shell})\""),
+    }
+}
+
+/// Look for the starship init line and for anything after it that replaces the prompt again
+fn check_shell_config(shell: &str, path: &Path, content: &str) -> Vec<Finding> {
+    let file = path.display();
+    let lines: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && !line.starts_with("--"))
+        .collect();
+
+    let Some(init) = lines.iter().position(|line| line.contains("starship init")) else {
+        return vec![Finding::new(
+            format!("starship is not initialized in {file}"),
+            format!("Add `{}` to the end of {file}", init_line(shell)),
+        )];
+    };
+
+    let mut findings = vec![];
+    let prompt_variables = match shell {
+        "bash" | "osh" | "ysh" => r"^(export\s+)?(PS1|PROMPT_COMMAND)=",
+        "zsh" => r"^(export\s+)?(PS1|PROMPT|RPS1|RPROMPT)=|^prompt\s+\w+",
+        "fish" => r"^function\s+fish_(right_)?prompt\b",
+        "powershell" | "pwsh" => r"^function\s+(global:)?prompt\b",
+        _ => r"^$^",
+    };
+    let prompt_variables = Regex::new(prompt_variables).unwrap();
+    let other_prompts = Regex::new(
+        r"oh-my-zsh\.sh|oh-my-posh|powerlevel10k|spaceship|pure\.zsh|bash-it\.sh|liquidprompt",
+    )
+    .unwrap();
+
+    for line in &lines[init + 1..] {
+        // Appending to `PROMPT_COMMAND` keeps the hook starship adds
+        if prompt_variables.is_match(line) && !line.contains("$PROMPT_COMMAND") {
+            findings.push(Finding::new(
+                format!("`{line}` comes after `starship init` and replaces its prompt"),
+                format!("Move `starship init` to the end of {file}"),
+            ));
+        } else if other_prompts.is_match(line) {
+            findings.push(Finding::new(
+                format!("`{line}` loads another prompt after `starship init`"),
+                format!("Remove it, or move `starship init` to the end of {file}"),
+            ));
+        }
+    }
+
+    if shell == "zsh" {
+        let theme = Regex::new(r#"^ZSH_THEME=["']?([^"'\s]+)"#).unwrap();
+        if let Some(name) = lines.iter().find_map(|line| theme.captures(line)) {
+            findings.push(Finding::new(
+                format!("The oh-my-zsh theme `{}` is set", &name[1]),
+                format!("Set `ZSH_THEME=\"\"` in {file} so only starship draws the prompt"),
+            ));
+        }
+    }
+
+    findings
+}
+
+/// Prompt frameworks that are loaded in the current shell alongside starship
+fn check_environment() -> Vec<Finding> {
+    let mut findings = vec![];
+    if env::var("ZSH_THEME").map_or(false, |theme| !theme.is_empty()) {
+        findings.push(Finding::new(
+            "An oh-my-zsh theme is active in this shell",
+            "Set `ZSH_THEME=\"\"` before oh-my-zsh is loaded",
+        ));
+    }
+    if env::var_os("POSH_THEME").is_some() {
+        findings.push(Finding::new(
+            "oh-my-posh is active in this shell",
+            "Remove the `oh-my-posh init` line from your shell configuration",
+        ));
+    }
+    findings
+}
+
+fn check_locale() -> Vec<Finding> {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()));
+    match locale {
+        Some(locale)
+            if !locale.to_lowercase().contains("utf-8")
+                && !locale.to_lowercase().contains("utf8") =>
+        {
+            vec![Finding::new(
+                format!("The locale `{locale}` is not UTF-8, so symbols may not show"),
+                "Set `LANG` to a UTF-8 locale, like `en_US.UTF-8`",
+            )]
+        }
+        _ => vec![],
+    }
+}
+
+fn uses_nerd_font() -> bool {
+    StarshipConfig::initialize()
+        .config
+        .and_then(|config| toml::to_string(&config).ok())
+        .map_or(false, |config| config.chars().any(is_private_use))
+}
+
+/// Nerd Fonts place their symbols in the private use areas of Unicode
+fn is_private_use(c: char) -> bool {
+    matches!(c, '\u{e000}'..='\u{f8ff}' | '\u{f0000}'..='\u{10ffff}')
+}
+
+fn check_path(paths: &[PathBuf]) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = paths
+        .iter()
+        .filter(|path| !path.as_os_str().is_empty() && !path.is_dir())
+        .map(|path| {
+            Finding::new(
+                format!("{} is in PATH, but is not a directory", path.display()),
+                "Remove it from PATH",
+            )
+        })
+        .collect();
+
+    let starship_in_path = which::which_in(
+        "starship",
+        Some(env::join_paths(paths).unwrap_or_default()),
+        ".",
+    )
+    .ok()
+    .and_then(|path| dunce::canonicalize(path).ok());
+    let current = env::current_exe().and_then(dunce::canonicalize).ok();
+    if let (Some(in_path), Some(current)) = (starship_in_path, current) {
+        if in_path != current {
+            findings.push(Finding::new(
+                format!(
+                    "`starship` in PATH is {}, not this one at {}",
+                    in_path.display(),
+                    current.display()
+                ),
+                "Remove the older install, or reorder PATH so the intended one comes first",
+            ));
+        }
+    }
+    findings
+}
+
+/// Render the prompt in the current directory and report the commands modules could not find
+fn check_version_commands() -> Vec<Finding> {
+    let context = Context::new(Properties::default(), Target::Main);
+    compute_modules(&context);
+    utils::missing_commands()
+        .into_iter()
+        .map(|command| {
+            Finding::new(
+                format!("`{command}` was not found in PATH"),
+                format!("Install `{command}`, or add the directory it is in to PATH"),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shell_config_without_init() {
+        let findings = check_shell_config("bash", Path::new(".bashrc"), "alias ll='ls -l'\n");
+
+        assert_eq!(
+            findings,
+            vec![Finding::new(
+                "starship is not initialized in .bashrc",
+                "Add `eval \"$(starship init bash)\"` to the end of .bashrc"
+            )]
+        );
+    }
+
+    #[test]
+    fn shell_config_overriding_prompt() {
+        let config = "# eval \"$(starship init zsh)\" is below\n\
+                      ZSH_THEME=\"robbyrussell\"\n\
+                      eval \"$(starship init zsh)\"\n\
+                      source $ZSH/oh-my-zsh.sh\n\
+                      PROMPT='%~ %# '\n";
+        let problems: Vec<_> = check_shell_config("zsh", Path::new(".zshrc"), config)
+            .into_iter()
+            .map(|finding| finding.problem)
+            .collect();
+
+        assert_eq!(
+            problems,
+            vec![
+                "`source $ZSH/oh-my-zsh.sh` loads another prompt after `starship init`",
+                "`PROMPT='%~ %# '` comes after `starship init` and replaces its prompt",
+                "The oh-my-zsh theme `robbyrussell` is set",
+            ]
+        );
+    }
+
+    #[test]
+    fn shell_config_appending_to_prompt_command() {
+        let config = "eval \"$(starship init bash)\"\n\
+                      PROMPT_COMMAND=\"history -a;$PROMPT_COMMAND\"\n";
+
+        assert!(check_shell_config("bash", Path::new(".bashrc"), config).is_empty());
+    }
+
+    #[test]
+    fn path_with_missing_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing");
+        let findings = check_path(&[dir.path().to_path_buf(), missing.clone()]);
+
+        assert_eq!(
+            findings,
+            vec![Finding::new(
+                format!("{} is in PATH, but is not a directory", missing.display()),
+                "Remove it from PATH"
+            )]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::context::{Context, Properties, Target};
+use crate::print::compute_modules;
+use crate::utils::{create_command, display_command, exec_timeout, CommandOutput};
+
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Runs the commands modules need through `Context::exec_cmd`
+///
+/// Replacing the executor of a context makes the prompt independent of the programs that are
+/// installed, e.g. to render it from a recording.
+pub trait CommandExecutor: Send + Sync {
+    fn exec(
+        &self,
+        cmd: &OsStr,
+        args: &[&OsStr],
+        current_dir: &Path,
+        time_limit: Duration,
+    ) -> Option<CommandOutput>;
+}
+
+/// Runs the commands on the system, the default outside of tests
+pub struct SystemExecutor;
+
+impl CommandExecutor for SystemExecutor {
+    fn exec(
+        &self,
+        cmd: &OsStr,
+        args: &[&OsStr],
+        current_dir: &Path,
+        time_limit: Duration,
+    ) -> Option<CommandOutput> {
+        let mut cmd = create_command(cmd).ok()?;
+        cmd.args(args).current_dir(current_dir);
+        exec_timeout(&mut cmd, time_limit)
+    }
+}
+
+/// Answers with the outputs mocked in `utils::mock_cmd`, and runs other commands on the system
+#[cfg(test)]
+pub struct MockExecutor;
+
+#[cfg(test)]
+impl CommandExecutor for MockExecutor {
+    fn exec(
+        &self,
+        cmd: &OsStr,
+        args: &[&OsStr],
+        current_dir: &Path,
+        time_limit: Duration,
+    ) -> Option<CommandOutput> {
+        crate::utils::mock_cmd(cmd, args)
+            .unwrap_or_else(|| SystemExecutor.exec(cmd, args, current_dir, time_limit))
+    }
+}
+
+/// The recorded outputs of commands, by the command line that was run
+type Recording = BTreeMap<String, Option<CommandOutput>>;
+
+/// Passes commands on to another executor and keeps their outputs, for `starship record`
+pub struct RecordingExecutor {
+    inner: Box<dyn CommandExecutor>,
+    recording: Mutex<Recording>,
+}
+
+impl RecordingExecutor {
+    pub fn new(inner: impl CommandExecutor + 'static) -> Self {
+        Self {
+            inner: Box::new(inner),
+            recording: Mutex::new(Recording::new()),
+        }
+    }
+
+    /// The recorded outputs as JSON, in the format `ReplayExecutor` reads
+    pub fn to_json(&self) -> String {
+        let recording = self
+            .recording
+            .lock()
+            .expect("Recording mutex was poisoned!");
+        serde_json::to_string_pretty(&*recording).expect("Unable to serialize the recording")
+    }
+}
+
+impl CommandExecutor for RecordingExecutor {
+    fn exec(
+        &self,
+        cmd: &OsStr,
+        args: &[&OsStr],
+        current_dir: &Path,
+        time_limit: Duration,
+    ) -> Option<CommandOutput> {
+        let output = self.inner.exec(cmd, args, current_dir, time_limit);
+        if let Ok(mut recording) = self.recording.lock() {
+            recording.insert(display_command(cmd, args), output.clone());
+        }
+        output
+    }
+}
+
+/// Answers with recorded outputs only, so the prompt renders the same on every machine
+pub struct ReplayExecutor {
+    recording: Recording,
+}
+
+impl ReplayExecutor {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Ok(Self {
+            recording: serde_json::from_str(json)?,
+        })
+    }
+
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Self::from_json(&json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+impl CommandExecutor for ReplayExecutor {
+    fn exec(
+        &self,
+        cmd: &OsStr,
+        args: &[&OsStr],
+        _current_dir: &Path,
+        _time_limit: Duration,
+    ) -> Option<CommandOutput> {
+        let command = display_command(cmd, args);
+        self.recording.get(&command).cloned().unwrap_or_else(|| {
+            log::debug!("{command:?} was not recorded, treating it as failed");
+            None
+        })
+    }
+}
+
+/// Render the prompt and write the outputs of the commands it ran to `output`, or stdout
+pub fn record(args: Properties, output: Option<&Path>) {
+    let recorder = Arc::new(RecordingExecutor::new(SystemExecutor));
+    let mut context = Context::new(args, Target::Main);
+    context.executor = recorder.clone();
+    compute_modules(&context);
+
+    let json = recorder.to_json();
+    match output {
+        Some(path) => {
+            if let Err(err) = fs::write(path, json + "\n") {
+                eprintln!("Unable to write the recording to {path:?}: {err}");
+                std::process::exit(1);
+            }
+        }
+        None => println!("{json}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+
+    #[test]
+    fn recorded_outputs_replay() {
+        let recorder = RecordingExecutor::new(MockExecutor);
+        let output = recorder.exec(
+            OsStr::new("bun"),
+            &[OsStr::new("--version")],
+            Path::new("."),
+            Duration::from_millis(500),
+        );
+        assert_eq!(output.unwrap().stdout, "0.1.4\n");
+
+        let replay = ReplayExecutor::from_json(&recorder.to_json()).unwrap();
+        let actual = ModuleRenderer::new("bun")
+            .with_project_file("bun.lockb", "")
+            .executor(replay)
+            .collect();
+        let expected = Some(format!("via {}", Color::Red.bold().paint("🍞 v0.1.4 ")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn commands_missing_from_recording_fail() {
+        let replay = ReplayExecutor::from_json(r#"{"bun --version": null}"#).unwrap();
+        let actual = ModuleRenderer::new("bun")
+            .with_project_file("bun.lockb", "")
+            .config(toml::toml! {
+                [bun]
+                format = "$version"
+            })
+            .executor(replay)
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+}
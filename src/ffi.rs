@@ -0,0 +1,151 @@
+//! A C interface to `api::render_prompt`, for programs that can't use the Rust API
+//!
+//! It is only built with the `ffi` feature, as a shared library with:
+//!
+//! ```sh
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! Requests and responses are JSON strings, so the interface doesn't change when the API
+//! gains options.
+
+use crate::api::{render_prompt, user_config, RenderContext, Segment, Target};
+use serde::{Deserialize, Serialize};
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic;
+use std::path::PathBuf;
+
+/// The JSON object passed to `starship_render_prompt`, all fields are optional
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Request {
+    path: PathBuf,
+    /// `main`, `right`, `continuation`, `select` or `trace`
+    target: Option<String>,
+    width: Option<usize>,
+    status: Option<i32>,
+    cmd_duration: Option<u128>,
+    jobs: i64,
+    /// The configuration as a JSON object, instead of the user's `starship.toml`
+    config: Option<toml::Value>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Response {
+    Segments { segments: Vec<Segment> },
+    Error { error: String },
+}
+
+fn render(request: &str) -> Result<Vec<Segment>, String> {
+    let request: Request = serde_json::from_str(request).map_err(|err| err.to_string())?;
+    let target = match request.target.as_deref() {
+        None | Some("main") => Target::Main,
+        Some("right") => Target::Right,
+        Some("continuation") => Target::Continuation,
+        Some("select") => Target::Select,
+        Some("trace") => Target::Trace,
+        Some(other) => return Err(format!("Unknown target {other:?}")),
+    };
+    let defaults = RenderContext::default();
+    let context = RenderContext {
+        path: request.path,
+        target,
+        width: request.width.unwrap_or(defaults.width),
+        status: request.status,
+        cmd_duration: request.cmd_duration,
+        jobs: request.jobs,
+    };
+    let config = request.config.or_else(user_config);
+    Ok(render_prompt(config, context))
+}
+
+fn respond(request: &str) -> String {
+    let response = match panic::catch_unwind(|| render(request)) {
+        Ok(Ok(segments)) => Response::Segments { segments },
+        Ok(Err(error)) => Response::Error { error },
+        Err(_) => Response::Error {
+            error: "Rendering the prompt panicked".to_string(),
+        },
+    };
+    serde_json::to_string(&response).expect("Failed to serialize the response")
+}
+
+/// Renders the prompt described by a JSON request, and returns a JSON object with either the
+/// `segments` of the prompt, as in `starship prompt --format=json`, or an `error`.
+///
+/// # Safety
+///
+/// `request` has to be a valid, nul-terminated string. The returned string has to be freed
+/// with `starship_free_string`.
+#[no_mangle]
+pub unsafe extern "C" fn starship_render_prompt(request: *const c_char) -> *mut c_char {
+    let response = if request.is_null() {
+        respond("{}")
+    } else {
+        respond(&CStr::from_ptr(request).to_string_lossy())
+    };
+    CString::new(response)
+        .expect("JSON strings don't contain nul bytes")
+        .into_raw()
+}
+
+/// Frees a string returned by `starship_render_prompt`.
+///
+/// # Safety
+///
+/// `string` has to be returned by `starship_render_prompt`, and not be freed already.
+#[no_mangle]
+pub unsafe extern "C" fn starship_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(request: &str) -> serde_json::Value {
+        let request = CString::new(request).unwrap();
+        unsafe {
+            let response = starship_render_prompt(request.as_ptr());
+            let json = CStr::from_ptr(response).to_str().unwrap().to_owned();
+            starship_free_string(response);
+            serde_json::from_str(&json).unwrap()
+        }
+    }
+
+    #[test]
+    fn render_prompt_over_c_abi() {
+        let response = call(
+            r#"{"status": 1, "config": {"format": "$character", "character": {"error_symbol": "[x](red)"}}}"#,
+        );
+
+        assert_eq!(
+            response["segments"][0],
+            serde_json::json!({
+                "module": "character",
+                "text": "x",
+                "fill": false,
+                "fg": "red",
+                "bg": null,
+                "bold": false,
+                "dimmed": false,
+                "italic": false,
+                "underline": false,
+                "link": null,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_request() {
+        assert_eq!(
+            call(r#"{"target": "left"}"#),
+            serde_json::json!({ "error": "Unknown target \"left\"" })
+        );
+        assert!(call("not json")["error"].is_string());
+    }
+}
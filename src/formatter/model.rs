@@ -23,6 +23,22 @@ pub enum FormatElement<'a> {
     Variable(Cow<'a, str>),
     TextGroup(TextGroup<'a>),
     Conditional(Vec<FormatElement<'a>>),
+    Padded(Padding, Vec<FormatElement<'a>>),
+}
+
+/// Pads the rendered format elements to a minimum width, e.g. `${cmd_duration:>10}`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Padding {
+    pub fill: char,
+    pub align: Alignment,
+    pub width: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    Right,
+    Center,
 }
 
 #[derive(Clone)]
@@ -41,6 +57,7 @@ impl<'a> VariableHolder<Cow<'a, str>> for FormatElement<'a> {
             }
             FormatElement::TextGroup(textgroup) => textgroup.format.get_variables(),
             FormatElement::Conditional(format) => format.get_variables(),
+            FormatElement::Padded(_, format) => format.get_variables(),
             _ => Default::default(),
         }
     }
@@ -13,7 +13,14 @@ struct IdentParser;
 fn parse_value(value: Pair<Rule>) -> FormatElement {
     match value.as_rule() {
         Rule::text => FormatElement::Text(parse_text(value).into()),
-        Rule::variable => FormatElement::Variable(parse_variable(value).into()),
+        Rule::variable => {
+            let mut inner_rules = value.into_inner();
+            let variable = FormatElement::Variable(inner_rules.next().unwrap().as_str().into());
+            match inner_rules.next() {
+                Some(padding) => FormatElement::Padded(parse_padding(padding), vec![variable]),
+                None => variable,
+            }
+        }
         Rule::textgroup => FormatElement::TextGroup(parse_textgroup(value)),
         Rule::conditional => {
             FormatElement::Conditional(parse_format(value.into_inner().next().unwrap()))
@@ -37,6 +44,29 @@ fn parse_variable(variable: Pair<Rule>) -> &str {
     variable.into_inner().next().unwrap().as_str()
 }
 
+fn parse_padding(padding: Pair<Rule>) -> Padding {
+    let mut result = Padding {
+        fill: ' ',
+        align: Alignment::Left,
+        width: 0,
+    };
+    for pair in padding.into_inner() {
+        match pair.as_rule() {
+            Rule::padding_fill => result.fill = pair.as_str().chars().next().unwrap(),
+            Rule::padding_align => {
+                result.align = match pair.as_str() {
+                    ">" => Alignment::Right,
+                    "^" => Alignment::Center,
+                    _ => Alignment::Left,
+                }
+            }
+            Rule::padding_width => result.width = pair.as_str().parse().unwrap(),
+            _ => unreachable!(),
+        }
+    }
+    result
+}
+
 fn parse_text(text: Pair<Rule>) -> String {
     text.into_inner()
         .flat_map(|pair| pair.as_str().chars())
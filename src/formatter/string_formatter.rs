@@ -193,6 +193,76 @@ impl<'a> StringFormatter<'a> {
         self
     }
 
+    /// Replaces variables with the elements of the format string they map to, in place.
+    ///
+    /// Unlike `StringFormatter::map_meta`, the inlined format strings may contain further
+    /// variables to inline. A variable that (indirectly) includes itself is dropped.
+    ///
+    /// See `StringFormatter::map` for description on the parameters.
+    #[must_use]
+    pub fn inline<M>(mut self, mapper: M) -> Self
+    where
+        M: Fn(&str) -> Option<&'a str>,
+    {
+        fn inline_elements<'a>(
+            elements: Vec<FormatElement<'a>>,
+            mapper: &dyn Fn(&str) -> Option<&'a str>,
+            stack: &mut Vec<String>,
+        ) -> Vec<FormatElement<'a>> {
+            let mut inlined = Vec::new();
+            for element in elements {
+                match element {
+                    FormatElement::Variable(name) => match mapper(&name) {
+                        Some(_) if stack.iter().any(|parent| *parent == name) => {
+                            log::warn!("Format variable `{name}` includes itself");
+                        }
+                        Some(format) => match parse(format) {
+                            Ok(format) => {
+                                stack.push(name.to_string());
+                                inlined.extend(inline_elements(format, mapper, stack));
+                                stack.pop();
+                            }
+                            Err(error) => log::warn!("Error parsing `{name}`:\n{error}"),
+                        },
+                        None => inlined.push(FormatElement::Variable(name)),
+                    },
+                    FormatElement::TextGroup(TextGroup { format, style }) => {
+                        inlined.push(FormatElement::TextGroup(TextGroup {
+                            format: inline_elements(format, mapper, stack),
+                            style,
+                        }));
+                    }
+                    FormatElement::Conditional(format) => inlined.push(FormatElement::Conditional(
+                        inline_elements(format, mapper, stack),
+                    )),
+                    FormatElement::Padded(padding, format) => inlined.push(FormatElement::Padded(
+                        padding,
+                        inline_elements(format, mapper, stack),
+                    )),
+                    text => inlined.push(text),
+                }
+            }
+            inlined
+        }
+
+        self.format = inline_elements(self.format, &mapper, &mut Vec::new());
+
+        // Recache the variables, keeping the values of those mapped already
+        let mut variables = VariableMapType::new();
+        for key in self.format.get_variables() {
+            let value = self.variables.remove(key.as_ref()).flatten();
+            variables.insert(key.to_string(), value);
+        }
+        let mut style_variables = StyleVariableMapType::new();
+        for key in self.format.get_style_variables() {
+            let value = self.style_variables.remove(key.as_ref()).flatten();
+            style_variables.insert(key.to_string(), value);
+        }
+        self.variables = variables;
+        self.style_variables = style_variables;
+        self
+    }
+
     /// Maps variable name to an array of segments
     ///
     /// See `StringFormatter::map` for description on the parameters.
@@ -228,6 +298,20 @@ impl<'a> StringFormatter<'a> {
         self
     }
 
+    /// Maps the keys of the session state (`$state.<key>`) that no other mapper has set
+    fn map_session_state(&mut self, context: &Context) {
+        let state_value = |key: &str| {
+            let key = key.strip_prefix("state.")?;
+            context.get_session_state().get(key).cloned()
+        };
+        for (key, value) in self.variables.iter_mut().filter(|(_, v)| v.is_none()) {
+            *value = state_value(key).map(|v| Ok(VariableValue::Plain(v.into())));
+        }
+        for (key, value) in self.style_variables.iter_mut().filter(|(_, v)| v.is_none()) {
+            *value = state_value(key).map(|v| Ok(v.into()));
+        }
+    }
+
     /// Parse the format string and consume self.
     ///
     /// This method will throw an Error in the following conditions:
@@ -235,31 +319,36 @@ impl<'a> StringFormatter<'a> {
     /// - Format string in meta variables fails to parse
     /// - Variable mapper returns an error.
     pub fn parse(
-        self,
+        mut self,
         default_style: Option<Style>,
         context: Option<&Context>,
     ) -> Result<Vec<Segment>, StringFormatterError> {
+        if let Some(context) = context {
+            self.map_session_state(context);
+        }
+
         fn parse_textgroup<'a>(
             textgroup: TextGroup<'a>,
             variables: &'a VariableMapType<'a>,
             style_variables: &'a StyleVariableMapType<'a>,
             context: Option<&Context>,
         ) -> Result<Vec<Segment>, StringFormatterError> {
-            let style = parse_style(textgroup.style, style_variables, context);
-            parse_format(
-                textgroup.format,
-                style.transpose()?,
-                variables,
-                style_variables,
-                context,
-            )
+            let (style, link) = parse_style(textgroup.style, style_variables, context)?;
+            let mut segments =
+                parse_format(textgroup.format, style, variables, style_variables, context)?;
+            if let Some(link) = link {
+                for segment in &mut segments {
+                    segment.set_link_if_empty(&link);
+                }
+            }
+            Ok(segments)
         }
 
         fn parse_style<'a>(
             style: Vec<StyleElement>,
             variables: &'a StyleVariableMapType<'a>,
             context: Option<&Context>,
-        ) -> Option<Result<Style, StringFormatterError>> {
+        ) -> Result<(Option<Style>, Option<String>), StringFormatterError> {
             let style_strings = style
                 .into_iter()
                 .map(|style| match style {
@@ -272,14 +361,15 @@ impl<'a> StringFormatter<'a> {
                         }
                     }
                 })
-                .collect::<Result<Vec<Cow<str>>, StringFormatterError>>();
-            style_strings
-                .map(|style_strings| {
-                    let style_string: String =
-                        style_strings.iter().flat_map(|s| s.chars()).collect();
-                    parse_style_string(&style_string, context)
-                })
-                .transpose()
+                .collect::<Result<Vec<Cow<str>>, StringFormatterError>>()?;
+            let style_string: String = style_strings.iter().flat_map(|s| s.chars()).collect();
+            let (style_string, link) = split_link(&style_string);
+            // A text group that only sets a link keeps the style of its parent
+            let style = match (style_string.is_empty(), &link) {
+                (true, Some(_)) => None,
+                _ => parse_style_string(&style_string, context),
+            };
+            Ok((style, link))
         }
 
         fn parse_format<'a>(
@@ -396,6 +486,28 @@ impl<'a> StringFormatter<'a> {
                                 Ok(Vec::new())
                             }
                         }
+                        FormatElement::Padded(padding, format) => {
+                            let segments =
+                                parse_format(format, style, variables, style_variables, context)?;
+                            let width: usize = segments.iter().map(Segment::width_graphemes).sum();
+                            let missing = padding.width.saturating_sub(width);
+                            let (before, after) = match padding.align {
+                                Alignment::Left => (0, missing),
+                                Alignment::Right => (missing, 0),
+                                Alignment::Center => (missing / 2, missing - missing / 2),
+                            };
+                            let fill = |count: usize| match count {
+                                0 => Vec::new(),
+                                _ => Segment::from_text(
+                                    style,
+                                    shell_prompt_escape(
+                                        padding.fill.to_string().repeat(count),
+                                        context.map_or(Shell::Unknown, |c| c.shell),
+                                    ),
+                                ),
+                            };
+                            Ok([fill(before), segments, fill(after)].concat())
+                        }
                     }
                 })
                 .collect();
@@ -412,6 +524,24 @@ impl<'a> StringFormatter<'a> {
     }
 }
 
+/// Takes the `link:<url>` token out of a style string, returning the rest of the style
+/// string and the URL
+fn split_link(style_string: &str) -> (String, Option<String>) {
+    let mut link = None;
+    let style_string = style_string
+        .split_whitespace()
+        .filter(|token| match token.strip_prefix("link:") {
+            Some(url) => {
+                link = Some(url).filter(|url| !url.is_empty()).map(str::to_string);
+                false
+            }
+            None => true,
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    (style_string, link)
+}
+
 impl<'a> VariableHolder<String> for StringFormatter<'a> {
     fn get_variables(&self) -> BTreeSet<String> {
         self.variables.keys().cloned().collect()
@@ -449,8 +579,8 @@ where
     // Handle other interpretable characters
     match shell {
         // Bash might interpret backslashes, backticks and $
-        // see #658 for more details
-        Shell::Bash => text
+        // see #658 for more details. The Oils shells evaluate PS1 the same way.
+        Shell::Bash | Shell::Osh | Shell::Ysh => text
             .into()
             .replace('\\', r"\\")
             .replace('$', r"\$")
@@ -501,6 +631,27 @@ mod tests {
         match_next!(result_iter, "text", Some(Color::Red.bold()));
     }
 
+    #[test]
+    fn test_textgroup_link() {
+        const FORMAT_STR: &str =
+            "[text](red link:https://starship.rs bold) [link](link:file:///tmp)";
+        let formatter = StringFormatter::new(FORMAT_STR).unwrap().map(empty_mapper);
+        let result = formatter.parse(None, None).unwrap();
+        let mut result_iter = result.iter();
+        let segment = result_iter.next().unwrap();
+        assert_eq!(segment.value(), "text");
+        assert_eq!(segment.style(), Some(Color::Red.bold()));
+        assert_eq!(segment.link(), Some("https://starship.rs"));
+        assert_eq!(result_iter.next().unwrap().link(), None);
+        // A link alone keeps the style of the parent
+        let segment = result_iter.next().unwrap();
+        assert_eq!(segment.style(), None);
+        assert_eq!(
+            segment.to_string(),
+            "\u{1b}]8;;file:///tmp\u{7}link\u{1b}]8;;\u{7}"
+        );
+    }
+
     #[test]
     fn test_variable_only() {
         const FORMAT_STR: &str = "$var1";
@@ -544,6 +695,37 @@ mod tests {
         match_next!(result_iter, "${env:PWD}", None);
     }
 
+    #[test]
+    fn test_session_state_variable() -> std::io::Result<()> {
+        const FORMAT_STR: &str = "[$state.mode]($state.style) $version.";
+
+        let cache_dir = tempfile::tempdir()?;
+        std::fs::create_dir(cache_dir.path().join("state"))?;
+        std::fs::write(
+            cache_dir.path().join("state/abc123.toml"),
+            "mode = \"focus\"\nstyle = \"red\"\n",
+        )?;
+        let mut context = crate::test::default_context();
+        context
+            .env
+            .insert("STARSHIP_CACHE", cache_dir.path().to_string_lossy().into());
+        context.env.insert("STARSHIP_SESSION_KEY", "abc123".into());
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map(|variable| match variable {
+                "version" => Some(Ok("v1")),
+                _ => None,
+            });
+        let result = formatter.parse(None, Some(&context)).unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, "focus", Some(Color::Red.normal()));
+        match_next!(result_iter, " ", None);
+        match_next!(result_iter, "v1", None);
+        match_next!(result_iter, ".", None);
+        cache_dir.close()
+    }
+
     #[test]
     fn test_escaped_chars() {
         const FORMAT_STR: &str = r#"\\\[\$text\]\(red bold\)"#;
@@ -554,6 +736,24 @@ mod tests {
         match_next!(result_iter, r#"\[$text](red bold)"#, None);
     }
 
+    #[test]
+    fn test_raw_literal() {
+        const FORMAT_STR: &str = r"a'''$b [c](d) \''' ''''''e [$f'''](red)";
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map(|var| match var {
+                "f" => Some(Ok("f")),
+                _ => None,
+            });
+        assert_eq!(formatter.get_variables(), BTreeSet::from(["f".to_owned()]));
+        let result = formatter.parse(None, None).unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, r"a$b [c](d) \ e ", None);
+        match_next!(result_iter, "f", Some(Color::Red.normal()));
+        match_next!(result_iter, "'''", Some(Color::Red.normal()));
+    }
+
     #[test]
     fn test_nested_textgroup() {
         const FORMAT_STR: &str = "outer [middle [inner](blue)](red bold)";
@@ -635,6 +835,62 @@ mod tests {
         match_next!(result_iter, "$b", None);
     }
 
+    #[test]
+    fn test_inlined_variable() {
+        const FORMAT_STR: &str = "$all";
+        const FORMAT_STR__ALL: &str = "[$a](red)$b$loop";
+        const FORMAT_STR__B: &str = "($b)";
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .inline(|var| match var {
+                "all" => Some(FORMAT_STR__ALL),
+                "b" | "loop" => Some(FORMAT_STR__B),
+                _ => None,
+            })
+            .map(|var| match var {
+                "a" => Some(Ok("$a")),
+                _ => None,
+            });
+        assert_eq!(formatter.get_variables(), BTreeSet::from(["a".to_owned()]));
+        let result = formatter.parse(None, None).unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, "$a", Some(Color::Red.normal()));
+        assert!(result_iter.next().is_none());
+    }
+
+    #[test]
+    fn test_padded_variable() {
+        const FORMAT_STR: &str = "${a:5}|${a:>5}|${b:-^6}|${b:2}|${env:HOST}";
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map(|var| match var {
+                "a" => Some(Ok("12")),
+                "b" => Some(Ok("abc")),
+                "env:HOST" => Some(Ok("host")),
+                _ => None,
+            });
+        let result: String = formatter
+            .parse(None, None)
+            .unwrap()
+            .iter()
+            .map(Segment::value)
+            .collect();
+        assert_eq!(result, "12   |   12|-abc--|abc|host");
+    }
+
+    #[test]
+    fn test_padded_empty_variable() {
+        const FORMAT_STR: &str = "[${a:>3}](red)>";
+
+        let formatter = StringFormatter::new(FORMAT_STR).unwrap();
+        let result = formatter.parse(None, None).unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, "   ", Some(Color::Red.normal()));
+        match_next!(result_iter, ">", None);
+    }
+
     #[test]
     fn test_multiple_mapper() {
         const FORMAT_STR: &str = "$a$b$c";
@@ -823,6 +1079,17 @@ mod tests {
             test
         );
     }
+
+    #[test]
+    fn test_oils_escape() {
+        let test = r"\$(echo `a`)";
+        for shell in [Shell::Osh, Shell::Ysh] {
+            assert_eq!(
+                shell_prompt_escape(test.to_owned(), shell),
+                r"\\\$(echo \`a\`)"
+            );
+        }
+    }
     #[test]
     fn test_zsh_escape() {
         let test = "10%";
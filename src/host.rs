@@ -0,0 +1,348 @@
+use crate::executor::CommandExecutor;
+use crate::utils::{self, create_command, exec_timeout, CommandOutput};
+use once_cell::sync::OnceCell;
+
+use std::collections::HashMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fmt::Debug;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// An entry of a directory listed by a `Host`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostDirEntry {
+    pub name: OsString,
+    pub is_dir: bool,
+}
+
+/// The machine the prompt is rendered for
+///
+/// The environment and the files the context reads come from its host, so the prompt of a
+/// directory on another machine can be rendered by replacing it, e.g. with `SshHost`.
+pub trait Host: Debug + Send + Sync {
+    fn get_env(&self, key: &str) -> Option<OsString>;
+
+    fn home_dir(&self) -> Option<PathBuf>;
+
+    /// The entries of a directory, or the ones that were found until `timeout` passed
+    fn read_dir(&self, path: &Path, timeout: Duration) -> io::Result<Vec<HostDirEntry>>;
+
+    /// The contents of a file, or only its first `limit` bytes
+    fn read_file(&self, path: &Path, limit: Option<u64>) -> io::Result<Vec<u8>>;
+
+    /// Whether this is the machine starship runs on, whose files can also be read directly
+    fn is_local(&self) -> bool {
+        false
+    }
+}
+
+/// The machine starship runs on
+#[derive(Debug)]
+pub struct LocalHost;
+
+impl Host for LocalHost {
+    fn get_env(&self, key: &str) -> Option<OsString> {
+        env::var_os(key)
+    }
+
+    fn home_dir(&self) -> Option<PathBuf> {
+        utils::home_dir()
+    }
+
+    fn read_dir(&self, path: &Path, timeout: Duration) -> io::Result<Vec<HostDirEntry>> {
+        let start = Instant::now();
+        Ok(fs::read_dir(path)?
+            .enumerate()
+            .take_while(|(n, _)| {
+                cfg!(test) // ignore timeout during tests
+                || n & 0xFF != 0 // only check timeout once every 2^8 entries
+                || start.elapsed() < timeout
+            })
+            .filter_map(|(_, entry)| entry.ok())
+            .map(|entry| HostDirEntry {
+                name: entry.file_name(),
+                is_dir: entry.path().is_dir(),
+            })
+            .collect())
+    }
+
+    fn read_file(&self, path: &Path, limit: Option<u64>) -> io::Result<Vec<u8>> {
+        let mut contents = Vec::new();
+        let file = fs::File::open(path)?;
+        match limit {
+            Some(limit) => file.take(limit).read_to_end(&mut contents)?,
+            None => (&file).read_to_end(&mut contents)?,
+        };
+        Ok(contents)
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+}
+
+/// A machine reached with `ssh`, for `--remote`
+///
+/// Every read runs a command over ssh, so connections are shared with `ControlMaster` to
+/// keep this fast enough for a prompt.
+#[derive(Debug)]
+pub struct SshHost {
+    destination: String,
+    env: OnceCell<HashMap<OsString, OsString>>,
+}
+
+impl SshHost {
+    pub fn new(destination: String) -> Self {
+        Self {
+            destination,
+            env: OnceCell::new(),
+        }
+    }
+
+    fn run(&self, command: &str, time_limit: Duration) -> Option<CommandOutput> {
+        let mut cmd = create_command("ssh").ok()?;
+        cmd.args([
+            "-o",
+            "BatchMode=yes",
+            "-o",
+            "ControlMaster=auto",
+            "-o",
+            "ControlPath=~/.ssh/starship-%C",
+            "-o",
+            "ControlPersist=60",
+        ])
+        .arg(&self.destination)
+        .arg("--")
+        .arg(command);
+        exec_timeout(&mut cmd, time_limit)
+    }
+
+    fn env(&self) -> &HashMap<OsString, OsString> {
+        self.env.get_or_init(|| {
+            self.run("env", Duration::from_secs(2))
+                .map(|output| parse_env(&output.stdout))
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Host for SshHost {
+    fn get_env(&self, key: &str) -> Option<OsString> {
+        self.env().get(OsStr::new(key)).cloned()
+    }
+
+    fn home_dir(&self) -> Option<PathBuf> {
+        self.get_env("HOME").map(PathBuf::from)
+    }
+
+    fn read_dir(&self, path: &Path, timeout: Duration) -> io::Result<Vec<HostDirEntry>> {
+        let command = format!("ls -1Ap -- {}", quote(&path.to_string_lossy()));
+        self.run(&command, timeout)
+            .map(|output| parse_ls(&output.stdout))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Unable to list directory"))
+    }
+
+    fn read_file(&self, path: &Path, limit: Option<u64>) -> io::Result<Vec<u8>> {
+        let path = quote(&path.to_string_lossy());
+        let command = match limit {
+            Some(limit) => format!("head -c {limit} -- {path}"),
+            None => format!("cat -- {path}"),
+        };
+        self.run(&command, Duration::from_secs(2))
+            .map(|output| output.stdout.into_bytes())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Unable to read file"))
+    }
+}
+
+/// Runs the commands of modules on an `SshHost`, in the directory of the prompt
+pub struct SshExecutor {
+    host: SshHost,
+}
+
+impl SshExecutor {
+    pub fn new(destination: String) -> Self {
+        Self {
+            host: SshHost::new(destination),
+        }
+    }
+}
+
+impl CommandExecutor for SshExecutor {
+    fn exec(
+        &self,
+        cmd: &OsStr,
+        args: &[&OsStr],
+        current_dir: &Path,
+        time_limit: Duration,
+    ) -> Option<CommandOutput> {
+        let command = std::iter::once(cmd)
+            .chain(args.iter().copied())
+            .map(|arg| quote(&arg.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let command = format!("cd {} && {command}", quote(&current_dir.to_string_lossy()));
+        self.host.run(&command, time_limit)
+    }
+}
+
+/// Quote a word for the remote shell
+fn quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
+
+fn parse_env(output: &str) -> HashMap<OsString, OsString> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.into(), value.into()))
+        .collect()
+}
+
+/// Parse the output of `ls -1Ap`, which marks directories with a trailing `/`
+fn parse_ls(output: &str) -> Vec<HostDirEntry> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.strip_suffix('/') {
+            Some(name) => HostDirEntry {
+                name: name.into(),
+                is_dir: true,
+            },
+            None => HostDirEntry {
+                name: line.into(),
+                is_dir: false,
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+
+    /// A host with a few files, none of which exist on the machine running the tests
+    #[derive(Debug)]
+    struct MemoryHost {
+        files: HashMap<PathBuf, &'static str>,
+    }
+
+    impl Host for MemoryHost {
+        fn get_env(&self, _key: &str) -> Option<OsString> {
+            None
+        }
+
+        fn home_dir(&self) -> Option<PathBuf> {
+            Some(PathBuf::from("/home/astronaut"))
+        }
+
+        fn read_dir(&self, path: &Path, _timeout: Duration) -> io::Result<Vec<HostDirEntry>> {
+            Ok(self
+                .files
+                .keys()
+                .filter(|file| file.parent() == Some(path))
+                .map(|file| HostDirEntry {
+                    name: file.file_name().unwrap().to_owned(),
+                    is_dir: false,
+                })
+                .collect())
+        }
+
+        fn read_file(&self, path: &Path, _limit: Option<u64>) -> io::Result<Vec<u8>> {
+            self.files
+                .get(path)
+                .map(|contents| contents.as_bytes().to_vec())
+                .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+        }
+    }
+
+    #[test]
+    fn render_for_other_host() {
+        let host = MemoryHost {
+            files: HashMap::from([(PathBuf::from("/srv/app/bun.lockb"), "")]),
+        };
+        let actual = ModuleRenderer::new("bun")
+            .path("/srv/app")
+            .host(host)
+            .collect();
+        let expected = Some(format!("via {}", Color::Red.bold().paint("🍞 v0.1.4 ")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[cfg(feature = "git")]
+    fn local_repo_is_not_shown_for_other_host() -> io::Result<()> {
+        use crate::test::{fixture_repo, FixtureProvider};
+
+        let repo_dir = fixture_repo(FixtureProvider::Git)?;
+        let render = |host: Option<MemoryHost>| {
+            let renderer = ModuleRenderer::new("git_branch").path(repo_dir.path());
+            match host {
+                Some(host) => renderer.host(host).collect(),
+                None => renderer.collect(),
+            }
+        };
+
+        assert!(render(None).is_some());
+        let host = MemoryHost {
+            files: HashMap::new(),
+        };
+        assert_eq!(render(Some(host)), None);
+        repo_dir.close()
+    }
+
+    #[test]
+    fn local_files_are_not_read_for_other_host() {
+        let render = |name| {
+            ModuleRenderer::new(name)
+                .config(toml::toml! {
+                    [os]
+                    disabled = false
+                    [reboot_required]
+                    disabled = false
+                    [updates]
+                    disabled = false
+                    file = "/tmp/updates"
+                })
+                .host(MemoryHost {
+                    files: HashMap::from([(PathBuf::from("/tmp/updates"), "3")]),
+                })
+                .collect()
+        };
+
+        assert_eq!(render("os"), None);
+        assert_eq!(render("reboot_required"), None);
+        assert!(render("updates").unwrap().contains('3'));
+    }
+
+    #[test]
+    fn parse_remote_listing() {
+        assert_eq!(
+            parse_ls("Cargo.toml\nsrc/\n.git/\n"),
+            vec![
+                HostDirEntry {
+                    name: "Cargo.toml".into(),
+                    is_dir: false
+                },
+                HostDirEntry {
+                    name: "src".into(),
+                    is_dir: true
+                },
+                HostDirEntry {
+                    name: ".git".into(),
+                    is_dir: true
+                },
+            ]
+        );
+        assert_eq!(
+            parse_env("HOME=/home/astronaut\nPS1=a=b\n").get(OsStr::new("PS1")),
+            Some(&OsString::from("a=b"))
+        );
+        assert_eq!(quote("it's"), r"'it'\''s'");
+    }
+}
@@ -1,3 +1,6 @@
+use crate::config::{ModuleConfig, StarshipConfig};
+use crate::configs::StarshipRootConfig;
+use crate::formatter::{StringFormatter, VariableHolder};
 use crate::utils::create_command;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
@@ -180,6 +183,16 @@ pub fn init_stub(shell_name: &str) -> io::Result<()> {
             r#"execx($({} init xonsh --print-full-init))"#,
             starship.sprint_posix()?
         ),
+        "murex" => print_script(MUREX_INIT, &starship.sprint_posix()?),
+        "osh" => print!(
+            r#"eval "$({} init osh --print-full-init)""#,
+            starship.sprint_posix()?
+        ),
+        "ysh" => print!(
+            r#"eval $({} init ysh --print-full-init)"#,
+            starship.sprint_posix()?
+        ),
+        "rc" => print_script(RC_INIT, &starship.sprint_posix()?),
         "cmd" => print_script(CMDEXE_INIT, &StarshipPath::init()?.sprint_cmdexe()?),
         _ => {
             eprintln!(
@@ -194,6 +207,10 @@ pub fn init_stub(shell_name: &str) -> io::Result<()> {
                  * zsh\n\
                  * nu\n\
                  * xonsh\n\
+                 * murex\n\
+                 * osh\n\
+                 * ysh\n\
+                 * rc\n\
                  * cmd\n\
                  \n\
                  Please open an issue in the starship repo if you would like to \
@@ -220,6 +237,8 @@ pub fn init_main(shell_name: &str) -> io::Result<()> {
         "elvish" => print_script(ELVISH_INIT, &starship_path.sprint_posix()?),
         "tcsh" => print_script(TCSH_INIT, &starship_path.sprint_posix()?),
         "xonsh" => print_script(XONSH_INIT, &starship_path.sprint_posix()?),
+        "osh" => print_script(OSH_INIT, &starship_path.sprint_posix()?),
+        "ysh" => print_script(YSH_INIT, &starship_path.sprint_posix()?),
         _ => {
             println!(
                 "printf \"Shell name detection failed on phase two init.\\n\
@@ -232,7 +251,30 @@ pub fn init_main(shell_name: &str) -> io::Result<()> {
 }
 
 fn print_script(script: &str, path: &str) {
-    let script = script.replace("::STARSHIP::", path);
+    let config = StarshipConfig::initialize();
+    let root_config = config
+        .config
+        .as_ref()
+        .map_or_else(StarshipRootConfig::default, StarshipRootConfig::load);
+    // A continuation prompt without variables is the same for every prompt
+    let dynamic_continuation = StringFormatter::new(&root_config.continuation_prompt)
+        .map_or(false, |formatter| !formatter.get_variables().is_empty());
+
+    let script = script
+        .replace("::STARSHIP::", path)
+        .replace(
+            "::SHELL_INTEGRATION::",
+            &root_config.shell_integration.to_string(),
+        )
+        .replace("::DYNAMIC_CONTINUATION::", &dynamic_continuation.to_string())
+        .replace(
+            "::SELECT_PROMPT::",
+            &(!root_config.select_prompt.is_empty()).to_string(),
+        )
+        .replace(
+            "::TRACE_PROMPT::",
+            &(!root_config.trace_prompt.is_empty()).to_string(),
+        );
     print!("{script}");
 }
 
@@ -248,7 +290,9 @@ to avoid the cost of an additional shell fork every shell draw.
 Note that the init scripts are not in their final form--they are processed by
 `starship init` prior to emitting the final form. In this processing, some tokens
 are replaced, e.g. `::STARSHIP::` is replaced by the full path to the
-starship binary.
+starship binary, `::SHELL_INTEGRATION::` by the `shell_integration` option,
+`::DYNAMIC_CONTINUATION::` by whether `continuation_prompt` uses any variables, and
+`::SELECT_PROMPT::` and `::TRACE_PROMPT::` by whether those prompts are configured.
 */
 
 const BASH_INIT: &str = include_str!("starship.bash");
@@ -269,6 +313,14 @@ const NU_INIT: &str = include_str!("starship.nu");
 
 const XONSH_INIT: &str = include_str!("starship.xsh");
 
+const MUREX_INIT: &str = include_str!("starship.mx");
+
+const OSH_INIT: &str = include_str!("starship.osh");
+
+const YSH_INIT: &str = include_str!("starship.ysh");
+
+const RC_INIT: &str = include_str!("starship.rc");
+
 const CMDEXE_INIT: &str = include_str!("starship.lua");
 
 #[cfg(test)]
@@ -24,15 +24,54 @@ starship_preexec() {
     if [ "$STARSHIP_PREEXEC_READY" = "true" ]; then
         STARSHIP_PREEXEC_READY=false
         STARSHIP_START_TIME=$(::STARSHIP:: time)
+        STARSHIP_CMD_NAME=${BASH_COMMAND%% *}
+        STARSHIP_CMD_COUNT=$((STARSHIP_CMD_COUNT + 1))
+        # Mark the start of the command for terminals with shell integration
+        [[ $STARSHIP_SHELL_INTEGRATION == true ]] && printf '\e]133;C\a'
     fi
 
     : "$PREV_LAST_ARG"
 }
 
+# In vi mode, readline can't redraw the prompt when the keymap changes, but it can show a
+# mode string at the start of the last prompt line. Render the `character` module for each
+# keymap into those mode strings instead. Mode strings need readline 7 (bash 4.4).
+# The `character` module only depends on whether the last command succeeded, so the mode
+# strings are only rendered again when that changes.
+starship_vi_mode() {
+    if [[ :$SHELLOPTS: != *:vi:* ]] || (( BASH_VERSINFO[0] * 100 + BASH_VERSINFO[1] < 404 )); then
+        if [[ $STARSHIP_KEYMAP ]]; then
+            bind "set show-mode-in-prompt off"
+            STARSHIP_KEYMAP="" STARSHIP_VI_MODE_STATUS=""
+        fi
+        return
+    fi
+
+    local success=$(( STARSHIP_CMD_STATUS == 0 ))
+    [[ $STARSHIP_VI_MODE_STATUS == "$success" ]] && return
+    STARSHIP_VI_MODE_STATUS=$success
+
+    local keymap rest out esc_re=$'^([^\e]*)(\e\\[[0-9;:]*m)(.*)$'
+    for keymap in vi-insert:ins vi-command:cmd; do
+        rest=$(::STARSHIP:: module character --status="$STARSHIP_CMD_STATUS" --keymap="${keymap%:*}")
+        # Escape the string for readline and mark color sequences as non-printing
+        rest=${rest//\\/\\\\} rest=${rest//\"/\\\"} out=""
+        while [[ $rest =~ $esc_re ]]; do
+            out+="${BASH_REMATCH[1]}\\1${BASH_REMATCH[2]}\\2"
+            rest=${BASH_REMATCH[3]}
+        done
+        bind "set vi-${keymap#*:}-mode-string \"$out$rest\""
+    done
+    bind "set show-mode-in-prompt on"
+    STARSHIP_KEYMAP="readline"
+}
+
 # Will be run before the prompt is drawn
 starship_precmd() {
     # Save the status, because commands in this pipeline will change $?
     STARSHIP_CMD_STATUS=$? STARSHIP_PIPE_STATUS=(${PIPESTATUS[@]})
+    # The history number the next command will get, as shown by `\!`
+    STARSHIP_HISTORY_INDEX=$HISTCMD
     if [[ "${#BP_PIPESTATUS[@]}" -gt "${#STARSHIP_PIPE_STATUS[@]}" ]]; then
         STARSHIP_PIPE_STATUS=(${BP_PIPESTATUS[@]})
     fi
@@ -42,6 +81,11 @@ starship_precmd() {
     # like z/autojump, which background certain jobs, do not cause spurious background jobs
     # to be displayed by starship. Also avoids forking to run `wc`, slightly improving perf.
     for job in $(jobs -p); do [[ $job ]] && ((NUM_JOBS++)); done
+    local NUM_STOPPED_JOBS=0
+    for job in $(jobs -ps); do [[ $job ]] && ((NUM_STOPPED_JOBS++)); done
+    # Extract the command of the current job from e.g. `[1]+  Stopped    vim foo`
+    local LAST_JOB="" LAST_JOB_RE='^\[[0-9]+\]\+ +[^ ]+( \([^)]*\))? +(.*[^ &])'
+    [[ $(jobs %+ 2>/dev/null) =~ $LAST_JOB_RE ]] && LAST_JOB="${BASH_REMATCH[2]}"
 
     # Run the bash precmd function, if it's set. If not set, evaluates to no-op
     "${starship_precmd_user_func-:}"
@@ -52,14 +96,20 @@ starship_precmd() {
 
     eval "$_PRESERVED_PROMPT_COMMAND"
 
+    starship_vi_mode
+
     # Prepare the timer data, if needed.
     if [[ $STARSHIP_START_TIME ]]; then
         STARSHIP_END_TIME=$(::STARSHIP:: time)
         STARSHIP_DURATION=$((STARSHIP_END_TIME - STARSHIP_START_TIME))
-        PS1="$(::STARSHIP:: prompt --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="${STARSHIP_PIPE_STATUS[*]}" --jobs="$NUM_JOBS" --cmd-duration=$STARSHIP_DURATION)"
+        PS1="$(::STARSHIP:: prompt --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="${STARSHIP_PIPE_STATUS[*]}" --jobs="$NUM_JOBS" --stopped-jobs="$NUM_STOPPED_JOBS" --last-job="$LAST_JOB" --keymap="$STARSHIP_KEYMAP" "${STARSHIP_SHELL_ARGS[@]}" --cmd-duration=$STARSHIP_DURATION --cmd-name="$STARSHIP_CMD_NAME")"
         unset STARSHIP_START_TIME
     else
-        PS1="$(::STARSHIP:: prompt --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="${STARSHIP_PIPE_STATUS[*]}" --jobs="$NUM_JOBS")"
+        PS1="$(::STARSHIP:: prompt --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="${STARSHIP_PIPE_STATUS[*]}" --jobs="$NUM_JOBS" --stopped-jobs="$NUM_STOPPED_JOBS" --last-job="$LAST_JOB" --keymap="$STARSHIP_KEYMAP" "${STARSHIP_SHELL_ARGS[@]}")"
+    fi
+    # A continuation prompt with variables is rendered again for every prompt
+    if [[ $STARSHIP_DYNAMIC_CONTINUATION == true ]]; then
+        PS2="$(::STARSHIP:: prompt --continuation --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="${STARSHIP_PIPE_STATUS[*]}" --jobs="$NUM_JOBS" --stopped-jobs="$NUM_STOPPED_JOBS" --last-job="$LAST_JOB" --keymap="$STARSHIP_KEYMAP" "${STARSHIP_SHELL_ARGS[@]}")"
     fi
     STARSHIP_PREEXEC_READY=true  # Signal that we can safely restart the timer
 }
@@ -106,6 +156,21 @@ shopt -s checkwinsize
 STARSHIP_START_TIME=$(::STARSHIP:: time)
 export STARSHIP_SHELL="bash"
 
+# Detect the shell version and mode once, as they can't change during the session
+# These are passed as prompt arguments rather than exported, so child shells don't inherit them
+STARSHIP_SHELL_ARGS=(--shell-version="${BASH_VERSINFO[0]}.${BASH_VERSINFO[1]}.${BASH_VERSINFO[2]}")
+if shopt -q login_shell; then STARSHIP_SHELL_ARGS+=(--login-shell); fi
+if shopt -q restricted_shell; then STARSHIP_SHELL_ARGS+=(--restricted-shell); fi
+
+# Count the commands run in this session, and expose the history number for `!n` expansions
+export STARSHIP_CMD_COUNT=0 STARSHIP_HISTORY_INDEX
+
+# Whether `shell_integration` was enabled when starship was initialized
+STARSHIP_SHELL_INTEGRATION=::SHELL_INTEGRATION::
+
+# Whether `continuation_prompt` uses variables, so that it depends on the prompt
+STARSHIP_DYNAMIC_CONTINUATION=::DYNAMIC_CONTINUATION::
+
 # Set up the session key that will be used to store logs
 STARSHIP_SESSION_KEY="$RANDOM$RANDOM$RANDOM$RANDOM$RANDOM"; # Random generates a number b/w 0 - 32767
 STARSHIP_SESSION_KEY="${STARSHIP_SESSION_KEY}0000000000000000" # Pad it to 16+ chars.
@@ -114,3 +179,11 @@ export STARSHIP_SESSION_KEY=${STARSHIP_SESSION_KEY:0:16}; # Trim to 16-digits if
 # Set the continuation prompt
 PS2="$(::STARSHIP:: prompt --continuation)"
 
+# Set the prompts of `select` and `set -x`, if they are configured
+if [[ ::SELECT_PROMPT:: == true ]]; then
+    PS3="$(::STARSHIP:: prompt --select)"
+fi
+if [[ ::TRACE_PROMPT:: == true ]]; then
+    PS4="$(::STARSHIP:: prompt --trace)"
+fi
+
@@ -9,7 +9,10 @@ function fish_prompt
     set STARSHIP_CMD_STATUS $status
     # Account for changes in variable name between v2.7 and v3.0
     set STARSHIP_DURATION "$CMD_DURATION$cmd_duration"
+    set STARSHIP_CMD_NAME (string split ' ' -- "$history[1]")[1]
     set STARSHIP_JOBS (count (jobs -p))
+    set STARSHIP_STOPPED_JOBS (count (jobs | string match -r '\tstopped\t'))
+    set STARSHIP_LAST_JOB (jobs --last --command 2>/dev/null)
     if test "$TRANSIENT" = "1"
         # Clear from cursor to end of screen as `commandline -f repaint` does not do this
         # See https://github.com/fish-shell/fish-shell/issues/8418
@@ -20,7 +23,7 @@ function fish_prompt
             printf "\e[1;32m❯\e[0m "
         end
     else
-        ::STARSHIP:: prompt --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="$STARSHIP_CMD_PIPESTATUS" --keymap=$STARSHIP_KEYMAP --cmd-duration=$STARSHIP_DURATION --jobs=$STARSHIP_JOBS
+        ::STARSHIP:: prompt --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="$STARSHIP_CMD_PIPESTATUS" --keymap=$STARSHIP_KEYMAP --cmd-duration=$STARSHIP_DURATION --cmd-name="$STARSHIP_CMD_NAME" --jobs=$STARSHIP_JOBS --stopped-jobs=$STARSHIP_STOPPED_JOBS --last-job="$STARSHIP_LAST_JOB" $STARSHIP_SHELL_ARGS
     end
 end
 
@@ -35,7 +38,10 @@ function fish_right_prompt
     set STARSHIP_CMD_STATUS $status
     # Account for changes in variable name between v2.7 and v3.0
     set STARSHIP_DURATION "$CMD_DURATION$cmd_duration"
+    set STARSHIP_CMD_NAME (string split ' ' -- "$history[1]")[1]
     set STARSHIP_JOBS (count (jobs -p))
+    set STARSHIP_STOPPED_JOBS (count (jobs | string match -r '\tstopped\t'))
+    set STARSHIP_LAST_JOB (jobs --last --command 2>/dev/null)
     if test "$TRANSIENT" = "1"
         if type -q starship_transient_rprompt_func
             starship_transient_rprompt_func
@@ -43,7 +49,7 @@ function fish_right_prompt
             printf ""
         end
     else
-        ::STARSHIP:: prompt --right --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="$STARSHIP_CMD_PIPESTATUS" --keymap=$STARSHIP_KEYMAP --cmd-duration=$STARSHIP_DURATION --jobs=$STARSHIP_JOBS
+        ::STARSHIP:: prompt --right --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="$STARSHIP_CMD_PIPESTATUS" --keymap=$STARSHIP_KEYMAP --cmd-duration=$STARSHIP_DURATION --cmd-name="$STARSHIP_CMD_NAME" --jobs=$STARSHIP_JOBS --stopped-jobs=$STARSHIP_STOPPED_JOBS --last-job="$STARSHIP_LAST_JOB" $STARSHIP_SHELL_ARGS
     end
 end
 
@@ -55,6 +61,26 @@ builtin functions -e fish_mode_prompt
 
 set -gx STARSHIP_SHELL "fish"
 
+# Detect the shell version and mode once, as they can't change during the session
+# These are passed as prompt arguments rather than exported, so child shells don't inherit them
+set -g STARSHIP_SHELL_ARGS --shell-version=$version
+if status is-login
+    set -a STARSHIP_SHELL_ARGS --login-shell
+end
+
+# Count the commands run in this session
+set -gx STARSHIP_CMD_COUNT 0
+function __starship_count_command --on-event fish_postexec
+    set -gx STARSHIP_CMD_COUNT (math $STARSHIP_CMD_COUNT + 1)
+end
+
+# Mark the start of commands for terminals with shell integration
+if test "::SHELL_INTEGRATION::" = true
+    function __starship_mark_command --on-event fish_preexec
+        printf '\e]133;C\a'
+    end
+end
+
 # Transience related functions
 function reset-transient --on-event fish_postexec
     set -g TRANSIENT 0
@@ -49,16 +49,23 @@ function starship_prompt:rightfilter(prompt)
   ):read("*a")
 end
 
-if starship_transient_prompt_func ~= nil then
-  function starship_prompt:transientfilter(prompt)
+-- The transient prompt functions are looked up when the prompt is replaced, so they
+-- can also be defined after this script is loaded
+function starship_prompt:transientfilter(prompt)
+  if starship_transient_prompt_func ~= nil then
     return starship_transient_prompt_func(prompt)
   end
+  return io.popen([[::STARSHIP::]].." module character"
+    .." --status="..os.geterrorlevel()
+    .." --keymap="..rl.getvariable('keymap')
+  ):read("*a")
 end
 
-if starship_transient_rprompt_func ~= nil then
-  function starship_prompt:transientrightfilter(prompt)
+function starship_prompt:transientrightfilter(prompt)
+  if starship_transient_rprompt_func ~= nil then
     return starship_transient_rprompt_func(prompt)
   end
+  return ""
 end
 
 local characterset = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz"
@@ -24,7 +24,7 @@ $null = New-Module starship {
     }
 
     function Invoke-Native {
-        param($Executable, $Arguments)
+        param($Executable, $Arguments, $State)
         $startInfo = New-Object System.Diagnostics.ProcessStartInfo -ArgumentList $Executable -Property @{
             StandardOutputEncoding = [System.Text.Encoding]::UTF8;
             RedirectStandardOutput = $true;
@@ -52,6 +52,10 @@ $null = New-Module starship {
             $startInfo.Arguments = $escaped -Join ' ';
         }
         $process = [System.Diagnostics.Process]::Start($startInfo)
+        if ($State) {
+            # Let the caller kill the process when it cancels an async prompt
+            $State.Process = $process
+        }
 
         # Read the output and error streams asynchronously
         # Avoids potential deadlocks when the child process fills one of the buffers
@@ -100,6 +104,73 @@ $null = New-Module starship {
         $script:TransientPrompt = $false
     }
 
+    # Renders the prompt in a background runspace, and redraws the prompt once it's done.
+    # A render still running for an earlier prompt is cancelled first.
+    function Start-AsyncRender {
+        param($Arguments)
+        Stop-AsyncRender
+
+        $state = [hashtable]::Synchronized(@{ Done = $false })
+        $powershell = [PowerShell]::Create()
+        $null = $powershell.AddScript(${function:Invoke-Native}.ToString()).AddParameters(@{
+            Executable = ::STARSHIP::;
+            Arguments = $Arguments;
+            State = $state;
+        })
+        $id = "Starship.AsyncRender.$([guid]::NewGuid())"
+        $null = Register-ObjectEvent -InputObject $powershell -EventName InvocationStateChanged -SourceIdentifier $id -MessageData $state -Action {
+            if ($EventArgs.InvocationStateInfo.State -in 'Completed', 'Failed') {
+                $Event.MessageData.Done = $true
+                [Microsoft.PowerShell.PSConsoleReadLine]::InvokePrompt()
+            }
+        }
+        $script:AsyncRender = @{ Id = $id; PowerShell = $powershell; State = $state; Handle = $powershell.BeginInvoke() }
+    }
+
+    function Stop-AsyncRender {
+        if ($null -eq $script:AsyncRender) {
+            return
+        }
+        Unregister-Event -SourceIdentifier $script:AsyncRender.Id -ErrorAction Ignore
+        if (-not $script:AsyncRender.State.Done) {
+            try { $script:AsyncRender.State.Process.Kill() } catch {}
+            $script:AsyncRender.PowerShell.Stop()
+        }
+        $script:AsyncRender.PowerShell.Dispose()
+        $script:AsyncRender = $null
+    }
+
+    # Returns the prompt rendered in the background if it's ready, or else starts rendering it
+    # and returns the last prompt in the meantime
+    function Get-AsyncPrompt {
+        param($Arguments)
+        if ($script:AsyncRender -and $script:AsyncRender.State.Done) {
+            try {
+                $script:CachedPrompt = $script:AsyncRender.PowerShell.EndInvoke($script:AsyncRender.Handle) -join ''
+            } catch {}
+            Stop-AsyncRender
+            return $script:CachedPrompt
+        }
+
+        if ($null -eq $script:CachedPrompt) {
+            # Nothing to show in the meantime on the first prompt
+            $script:CachedPrompt = Invoke-Native -Executable ::STARSHIP:: -Arguments $Arguments
+            return $script:CachedPrompt
+        }
+        Start-AsyncRender -Arguments $Arguments
+        $script:CachedPrompt
+    }
+
+    function Enable-AsyncPrompt {
+        $script:AsyncPrompt = $true
+    }
+
+    function Disable-AsyncPrompt {
+        $script:AsyncPrompt = $false
+        Stop-AsyncRender
+        $script:CachedPrompt = $null
+    }
+
     function global:prompt {
         $origDollarQuestion = $global:?
         $origLastExitCode = $global:LASTEXITCODE
@@ -140,6 +211,7 @@ $null = New-Module starship {
         }
 
         $arguments += "--status=$($lastExitCodeForPrompt)"
+        $arguments += "--keymap=$($script:ViMode)"
 
         # Invoke Starship
         $promptText = if ($script:TransientPrompt) {
@@ -149,10 +221,19 @@ $null = New-Module starship {
             } else {
                 "$([char]0x1B)[1;32m❯$([char]0x1B)[0m "
             }
+        } elseif ($script:AsyncPrompt) {
+            Get-AsyncPrompt -Arguments $arguments
         } else {
             Invoke-Native -Executable ::STARSHIP:: -Arguments $arguments
         }
 
+        # A continuation prompt with variables is rendered again for every prompt
+        if ("::DYNAMIC_CONTINUATION::" -eq "true") {
+            Set-PSReadLineOption -ContinuationPrompt (
+                Invoke-Native -Executable ::STARSHIP:: -Arguments ($arguments + "--continuation")
+            )
+        }
+
         # Set the number of extra lines in the prompt for PSReadLine prompt redraw.
         Set-PSReadLineOption -ExtraPromptLineCount ($promptText.Split("`n").Length - 1)
 
@@ -187,8 +268,20 @@ $null = New-Module starship {
     $ENV:VIRTUAL_ENV_DISABLE_PROMPT=1
 
     $script:TransientPrompt = $false
+    $script:AsyncPrompt = $false
+    $script:AsyncRender = $null
+    $script:CachedPrompt = $null
     $script:DoesUseLists = (Get-PSReadLineOption).PredictionViewStyle -eq 'ListView'
 
+    # In vi mode, redraw the prompt whenever PSReadLine switches between insert and command mode
+    $script:ViMode = "insert"
+    if ((Get-PSReadLineOption).EditMode -eq 'Vi') {
+        Set-PSReadLineOption -ViModeIndicator Script -ViModeChangeHandler {
+            $script:ViMode = "$($args[0])".ToLower()
+            [Microsoft.PowerShell.PSConsoleReadLine]::InvokePrompt()
+        }
+    }
+
     if ($PSVersionTable.PSVersion.Major -gt 5) {
         $ENV:STARSHIP_SHELL = "pwsh"
     } else {
@@ -209,5 +302,7 @@ $null = New-Module starship {
     Export-ModuleMember -Function @(
         "Enable-TransientPrompt"
         "Disable-TransientPrompt"
+        "Enable-AsyncPrompt"
+        "Disable-AsyncPrompt"
     )
 }
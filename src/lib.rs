@@ -5,20 +5,30 @@ extern crate shadow_rs;
 
 shadow!(shadow);
 
-// Lib is present to allow for benchmarking
+// Lib is present to allow for benchmarking, and for embedding the prompt with `api`
+pub mod api;
+pub mod bench;
 pub mod bug_report;
 pub mod config;
 pub mod configs;
 pub mod configure;
 pub mod context;
+pub mod doctor;
+pub mod executor;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod formatter;
+pub mod host;
 pub mod init;
 pub mod logger;
 pub mod module;
 mod modules;
+pub mod presets;
 pub mod print;
 mod segment;
 mod serde_utils;
+pub mod state;
+pub mod timer;
 mod utils;
 
 #[cfg(test)]
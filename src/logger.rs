@@ -3,31 +3,88 @@ use log::{Level, LevelFilter, Metadata, Record};
 use nu_ansi_term::Color;
 use once_cell::sync::OnceCell;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     env,
     fs::{self, File, OpenOptions},
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Mutex,
 };
 
 pub struct StarshipLogger {
     log_file: OnceCell<Mutex<File>>,
     log_file_path: PathBuf,
-    log_file_content: HashSet<String>,
-    log_level: Level,
+    log_file_content: OnceCell<HashSet<String>>,
+    log_filter: LogFilter,
+    json: bool,
 }
 
-impl Default for StarshipLogger {
+/// The levels to log at, parsed from `STARSHIP_LOG`
+///
+/// The variable holds a comma-separated list of directives, which are either a level that
+/// applies to all modules, or `module=level` for a single module, e.g. `warn,git_status=trace`.
+/// Modules can be named by the last part of their path (`git_status`) or by their full path
+/// (`starship::modules::git_status`) or by a parent path (`starship::modules`).
+#[derive(Debug, PartialEq, Eq)]
+struct LogFilter {
+    level: Level,
+    modules: Vec<(String, Level)>,
+}
+
+impl Default for LogFilter {
     fn default() -> Self {
-        let log_dir = env::var_os("STARSHIP_CACHE")
-            .map(PathBuf::from)
-            .unwrap_or_else(|| {
-                utils::home_dir()
-                    .expect("Unable to find home directory")
-                    .join(".cache/starship")
-            });
+        Self {
+            level: Level::Warn,
+            modules: Vec::new(),
+        }
+    }
+}
+
+impl LogFilter {
+    fn parse(spec: &str) -> Self {
+        let mut filter = Self::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => filter
+                    .modules
+                    .push((module.trim().to_string(), parse_level(level))),
+                None => filter.level = parse_level(directive),
+            }
+        }
+        filter
+    }
+
+    /// A directive naming the module itself wins over one naming a parent of it, and the
+    /// closest parent wins over the ones further up
+    fn level_for(&self, module_path: &str) -> Level {
+        let named = self.modules.iter().rev().find(|(module, _)| {
+            module_path == module || module_path.ends_with(&format!("::{module}"))
+        });
+        let parent = || {
+            self.modules
+                .iter()
+                .filter(|(module, _)| module_path.starts_with(&format!("{module}::")))
+                .max_by_key(|(module, _)| module.len())
+        };
+        named
+            .or_else(parent)
+            .map_or(self.level, |(_, level)| *level)
+    }
+}
+
+fn parse_level(level: &str) -> Level {
+    match level.trim().to_lowercase().as_str() {
+        "trace" => Level::Trace,
+        "debug" => Level::Debug,
+        "info" => Level::Info,
+        "error" => Level::Error,
+        _ => Level::Warn,
+    }
+}
 
+impl Default for StarshipLogger {
+    fn default() -> Self {
+        let log_dir = log_dir();
         fs::create_dir_all(&log_dir)
             .unwrap_or_else(|err| panic!("Unable to create log dir {log_dir:?}: {err:?}!"));
         let session_log_file = log_dir.join(format!(
@@ -36,31 +93,24 @@ impl Default for StarshipLogger {
         ));
 
         Self {
-            log_file_content: fs::read_to_string(&session_log_file)
-                .unwrap_or_default()
-                .lines()
-                .map(std::string::ToString::to_string)
-                .collect(),
+            log_file_content: OnceCell::new(),
             log_file: OnceCell::new(),
             log_file_path: session_log_file,
-            log_level: env::var("STARSHIP_LOG")
-                .map(|level| match level.to_lowercase().as_str() {
-                    "trace" => Level::Trace,
-                    "debug" => Level::Debug,
-                    "info" => Level::Info,
-                    "warn" => Level::Warn,
-                    "error" => Level::Error,
-                    _ => Level::Warn,
-                })
-                .unwrap_or_else(|_| Level::Warn),
+            log_filter: env::var("STARSHIP_LOG")
+                .map(|spec| LogFilter::parse(&spec))
+                .unwrap_or_default(),
+            json: env::var("STARSHIP_LOG_FORMAT").map_or(false, |format| format == "json"),
         }
     }
 }
 
 impl StarshipLogger {
-    /// Override the minimum log level
+    /// Override the minimum log level of all modules
     pub fn set_log_level(&mut self, level: log::Level) {
-        self.log_level = level;
+        self.log_filter = LogFilter {
+            level,
+            modules: Vec::new(),
+        };
     }
 
     /// Override the log level path
@@ -68,11 +118,25 @@ impl StarshipLogger {
     pub fn set_log_file_path(&mut self, path: PathBuf) {
         self.log_file_path = path;
     }
+
+    /// The lines already in the log file, which are not printed again
+    ///
+    /// The file is only read once something is to be printed, so prompts that log nothing
+    /// don't pay for reading the whole session log.
+    fn log_file_content(&self) -> &HashSet<String> {
+        self.log_file_content.get_or_init(|| {
+            fs::read_to_string(&self.log_file_path)
+                .unwrap_or_default()
+                .lines()
+                .map(std::string::ToString::to_string)
+                .collect()
+        })
+    }
 }
 
 impl log::Log for StarshipLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= self.log_level
+        metadata.level() <= self.log_filter.level_for(metadata.target())
     }
 
     fn log(&self, record: &Record) {
@@ -82,6 +146,8 @@ impl log::Log for StarshipLogger {
             record.module_path().unwrap_or_default(),
             record.args()
         );
+        // Read before the record is written, or it would count as already logged
+        let print = self.enabled(record.metadata()) && !self.log_file_content().contains(&to_print);
 
         if record.metadata().level() <= Level::Warn {
             self.log_file
@@ -106,7 +172,20 @@ impl log::Log for StarshipLogger {
                 .expect("Unable to write to the log file!");
         }
 
-        if self.enabled(record.metadata()) && !self.log_file_content.contains(to_print.as_str()) {
+        if !print {
+            return;
+        }
+
+        if self.json {
+            eprintln!(
+                "{}",
+                serde_json::json!({
+                    "level": record.level().as_str(),
+                    "module": record.module_path().unwrap_or_default(),
+                    "message": record.args().to_string(),
+                })
+            );
+        } else {
             eprintln!(
                 "[{}] - ({}): {}",
                 match record.level() {
@@ -132,7 +211,137 @@ impl log::Log for StarshipLogger {
     }
 }
 
+fn log_dir() -> PathBuf {
+    env::var_os("STARSHIP_CACHE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            utils::home_dir()
+                .expect("Unable to find home directory")
+                .join(".cache/starship")
+        })
+}
+
+/// The log file of the current session, or the most recently written one when no session key
+/// is set, e.g. when `starship logs` is run outside of an initialized shell
+fn session_log_file(log_dir: &Path) -> Option<PathBuf> {
+    match env::var("STARSHIP_SESSION_KEY") {
+        Ok(key) if !key.is_empty() => Some(log_dir.join(format!("session_{key}.log"))),
+        _ => fs::read_dir(log_dir)
+            .ok()?
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                name.starts_with("session_") && name.ends_with(".log")
+            })
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .max()
+            .map(|(_, path)| path),
+    }
+}
+
+/// Collapse repeated log lines, keeping the order of their most recent occurrence
+fn dedup_log_lines<'a>(
+    content: &'a str,
+    module: Option<&str>,
+    limit: usize,
+) -> Vec<(&'a str, usize)> {
+    let filter = module.map(|module| LogFilter {
+        level: Level::Error,
+        modules: vec![(module.to_string(), Level::Trace)],
+    });
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut lines = Vec::new();
+    for line in content.lines().rev().filter(|line| !line.is_empty()) {
+        if let Some(filter) = &filter {
+            let module_path = line
+                .split_once('(')
+                .and_then(|(_, rest)| rest.split_once(')'))
+                .map_or("", |(path, _)| path);
+            if filter.level_for(module_path) != Level::Trace {
+                continue;
+            }
+        }
+        let count = counts.entry(line).or_default();
+        if *count == 0 {
+            lines.push(line);
+        }
+        *count += 1;
+    }
+    let mut lines: Vec<_> = lines
+        .into_iter()
+        .take(limit)
+        .map(|line| (line, counts[line]))
+        .collect();
+    lines.reverse();
+    lines
+}
+
+/// Print the most recent warnings and errors of the current session, without repeats
+pub fn print_logs(module: Option<&str>, limit: usize) {
+    let Some(path) = session_log_file(&log_dir()) else {
+        println!("No session logs were found");
+        return;
+    };
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let lines = dedup_log_lines(&content, module, limit);
+    if lines.is_empty() {
+        println!("No warnings were logged in {}", path.display());
+        return;
+    }
+    for (line, count) in lines {
+        if count > 1 {
+            println!("{line} {}", Color::DarkGray.paint(format!("(x{count})")));
+        } else {
+            println!("{line}");
+        }
+    }
+}
+
 pub fn init() {
     log::set_boxed_logger(Box::new(StarshipLogger::default())).unwrap();
     log::set_max_level(LevelFilter::Trace);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_filter_with_module_levels() {
+        let filter = LogFilter::parse("error, git_status=trace,starship::modules=debug");
+
+        assert_eq!(filter.level_for("starship::print"), Level::Error);
+        assert_eq!(
+            filter.level_for("starship::modules::git_status"),
+            Level::Trace
+        );
+        assert_eq!(filter.level_for("starship::modules::rust"), Level::Debug);
+        assert_eq!(LogFilter::parse("nonsense"), LogFilter::default());
+    }
+
+    #[test]
+    fn log_lines_are_deduplicated() {
+        let content = "[WARN] - (starship::modules::rust): a\n\
+                       [WARN] - (starship::utils): b\n\
+                       [WARN] - (starship::modules::rust): a\n\
+                       [ERROR] - (starship::modules::git_status): c\n";
+
+        assert_eq!(
+            dedup_log_lines(content, None, 10),
+            vec![
+                ("[WARN] - (starship::utils): b", 1),
+                ("[WARN] - (starship::modules::rust): a", 2),
+                ("[ERROR] - (starship::modules::git_status): c", 1),
+            ]
+        );
+        assert_eq!(
+            dedup_log_lines(content, None, 1),
+            vec![("[ERROR] - (starship::modules::git_status): c", 1)]
+        );
+        assert_eq!(
+            dedup_log_lines(content, Some("rust"), 10),
+            vec![("[WARN] - (starship::modules::rust): a", 2)]
+        );
+    }
+}
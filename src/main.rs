@@ -2,6 +2,7 @@
 
 use clap::crate_authors;
 use std::io;
+use std::path::PathBuf;
 use std::thread::available_parallelism;
 use std::time::SystemTime;
 
@@ -29,8 +30,10 @@ struct Cli {
 
 #[derive(Subcommand, Debug)]
 enum Commands {
+    /// Render a module, or the whole prompt, many times and print how long it took
+    Bench(bench::BenchOptions),
     /// Create a pre-populated GitHub issue with information about your configuration
-    BugReport,
+    BugReport(bug_report::ReportOptions),
     /// Generate starship shell completions for your shell to stdout
     Completions {
         #[clap(value_enum)]
@@ -44,14 +47,31 @@ enum Commands {
         /// Value to place into that key
         value: Option<String>,
     },
+    /// Checks for common problems with the prompt and shows how to fix them
+    Doctor,
     /// Explains the currently showing modules
-    Explain(Properties),
+    Explain {
+        /// Answer the commands modules run with the outputs in a file from `starship record`
+        #[clap(long)]
+        replay: Option<PathBuf>,
+        #[clap(flatten)]
+        properties: Properties,
+    },
     ///  Prints the shell function used to execute starship
     Init {
         shell: String,
         #[clap(long)]
         print_full_init: bool,
     },
+    /// Prints the most recent warnings and errors of the current session, without repeats
+    Logs {
+        /// Only show the logs of this module, e.g. `git_status`
+        #[clap(short, long)]
+        module: Option<String>,
+        /// The maximum number of distinct lines to show
+        #[clap(short = 'n', long, default_value_t = 20)]
+        lines: usize,
+    },
     ///  Prints a specific prompt module
     Module {
         /// The name of the module to be printed
@@ -63,7 +83,14 @@ enum Commands {
         #[clap(flatten)]
         properties: Properties,
     },
-    /// Prints a preset config
+    /// Switch the prompt to a mode in `prompt_modes` for the current shell session
+    Mode {
+        /// The mode to switch to, or back from if it is active. Without it, the prompt goes
+        /// back to `format`
+        name: Option<String>,
+    },
+    /// Prints a preset config, or manages presets with a subcommand
+    #[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Preset {
         /// The name of preset to be printed
         #[clap(required_unless_present("list"), value_enum)]
@@ -71,6 +98,8 @@ enum Commands {
         /// List out all preset names
         #[clap(short, long)]
         list: bool,
+        #[clap(subcommand)]
+        action: Option<PresetAction>,
     },
     /// Prints the computed starship configuration
     PrintConfig {
@@ -88,29 +117,109 @@ enum Commands {
         /// Print the continuation prompt (instead of the standard left prompt)
         #[clap(long, conflicts_with = "right")]
         continuation: bool,
+        /// Print the prompt of `select` loops, PS3 (instead of the standard left prompt)
+        #[clap(long, conflicts_with_all = ["right", "continuation"])]
+        select: bool,
+        /// Print the prompt of traced commands, PS4 (instead of the standard left prompt)
+        #[clap(long, conflicts_with_all = ["right", "continuation", "select"])]
+        trace: bool,
+        /// The format to print the prompt in
+        #[clap(long, value_enum, default_value_t)]
+        format: print::PromptFormat,
+        /// Keep the ANSI colors, but leave out the escapes only shells understand, e.g. for rlwrap
+        #[clap(long)]
+        no_escapes: bool,
+        #[clap(flatten)]
+        properties: Properties,
+    },
+    /// Render the prompt and save the outputs of the commands it ran, for `explain --replay`
+    Record {
+        /// The file to write the outputs to, instead of stdout
+        #[clap(short, long)]
+        output: Option<PathBuf>,
         #[clap(flatten)]
         properties: Properties,
     },
     /// Generate random session key
     Session,
+    /// Read or change values kept for the current shell session, shown with `$state.<key>`
+    State {
+        #[clap(subcommand)]
+        action: StateAction,
+    },
     /// Prints time in milliseconds
     #[clap(hide = true)]
     Time,
+    /// Start or stop a focus timer, shown by the `timer` module
+    Timer {
+        #[clap(subcommand)]
+        action: TimerAction,
+    },
     /// Prints timings of all active modules
     Timings(Properties),
-    /// Toggle a given starship module
+    /// Toggle a given starship module, or a group of modules for the current shell session
     Toggle {
-        /// The name of the module to be toggled
+        /// The name of the module or group to be toggled
         name: String,
         /// The key of the config to be toggled
         #[clap(default_value = "disabled")]
         value: String,
+        /// Hide or show the module for the current shell session, instead of changing the config
+        #[clap(long)]
+        session: bool,
     },
     #[cfg(feature = "config-schema")]
     /// Generate a schema for the starship configuration as JSON-schema
     ConfigSchema,
 }
 
+#[derive(Subcommand, Debug)]
+enum StateAction {
+    /// Print the value of a key
+    Get { key: String },
+    /// Set a key to a value, or remove it if no value is given
+    Set { key: String, value: Option<String> },
+}
+
+#[derive(Subcommand, Debug)]
+enum PresetAction {
+    /// Merge a preset into the current configuration
+    Apply {
+        /// The name of a built-in or installed preset, a file or a URL
+        source: String,
+    },
+    /// Show the changes applying a preset would make to the current configuration
+    Diff {
+        /// The name of a built-in or installed preset, a file or a URL
+        source: String,
+    },
+    /// Install a preset from a file or a URL, so that it can be applied by name
+    Install {
+        /// The file or URL of the preset
+        source: String,
+        /// The name to install the preset as, defaults to its file name
+        #[clap(short, long)]
+        name: Option<String>,
+        /// The expected sha256 checksum of the preset, as hex
+        #[clap(long)]
+        sha256: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum TimerAction {
+    /// Start a timer, replacing the running one
+    Start {
+        /// How long the timer runs for, e.g. `25m` or `1h30m`
+        duration: String,
+        /// A label to show alongside the countdown
+        #[clap(short, long)]
+        label: Option<String>,
+    },
+    /// Stop the running timer
+    Stop,
+}
+
 fn main() {
     // Configure the current terminal on windows to support ANSI escape sequences.
     #[cfg(windows)]
@@ -167,13 +276,19 @@ fn main() {
             properties,
             right,
             continuation,
+            select,
+            trace,
+            format,
+            no_escapes,
         } => {
-            let target = match (right, continuation) {
-                (true, _) => Target::Right,
-                (_, true) => Target::Continuation,
-                (_, _) => Target::Main,
+            let target = match (right, continuation, select, trace) {
+                (true, _, _, _) => Target::Right,
+                (_, true, _, _) => Target::Continuation,
+                (_, _, true, _) => Target::Select,
+                (_, _, _, true) => Target::Trace,
+                (_, _, _, _) => Target::Main,
             };
-            print::prompt(properties, target)
+            print::prompt(properties, target, format, no_escapes)
         }
         Commands::Module {
             name,
@@ -191,7 +306,18 @@ fn main() {
                 print::module(&module_name, properties);
             }
         }
-        Commands::Preset { name, list } => print::preset_command(name, list),
+        Commands::Logs { module, lines } => logger::print_logs(module.as_deref(), lines),
+        Commands::Mode { name } => state::mode(name.as_deref()),
+        Commands::Preset { name, list, action } => match action {
+            Some(PresetAction::Apply { source }) => presets::apply(&source),
+            Some(PresetAction::Diff { source }) => presets::diff(&source),
+            Some(PresetAction::Install {
+                source,
+                name,
+                sha256,
+            }) => presets::install(&source, name.as_deref(), sha256.as_deref()),
+            None => print::preset_command(name, list),
+        },
         Commands::Config { name, value } => {
             if let Some(name) = name {
                 if let Some(value) = value {
@@ -203,8 +329,13 @@ fn main() {
             }
         }
         Commands::PrintConfig { default, name } => configure::print_configuration(default, &name),
-        Commands::Toggle { name, value } => configure::toggle_configuration(&name, &value),
-        Commands::BugReport => bug_report::create(),
+        Commands::Toggle {
+            name,
+            value,
+            session,
+        } => state::toggle(&name, &value, session),
+        Commands::Bench(options) => bench::bench(options),
+        Commands::BugReport(options) => bug_report::create(options),
         Commands::Time => {
             match SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
@@ -214,7 +345,25 @@ fn main() {
                 None => println!("{}", -1),
             }
         }
-        Commands::Explain(props) => print::explain(props),
+        Commands::Timer { action } => match action {
+            TimerAction::Start { duration, label } => timer::start(&duration, label),
+            TimerAction::Stop => timer::stop(),
+        },
+        Commands::State { action } => match action {
+            StateAction::Get { key } => state::get(&key),
+            StateAction::Set { key, value } => state::set(&key, value),
+        },
+        Commands::Doctor => doctor::diagnose(),
+        Commands::Explain { replay, properties } => {
+            let executor = replay.map(|path| {
+                executor::ReplayExecutor::from_file(&path).unwrap_or_else(|err| {
+                    eprintln!("Unable to read the recording {path:?}: {err}");
+                    std::process::exit(1);
+                })
+            });
+            print::explain(properties, executor)
+        }
+        Commands::Record { output, properties } => executor::record(properties, output.as_deref()),
         Commands::Timings(props) => print::timings(props),
         Commands::Completions { shell } => generate(
             shell,
@@ -9,6 +9,7 @@ use std::time::Duration;
 // List of all modules
 // Default ordering is handled in configs/starship_root.rs
 pub const ALL_MODULES: &[&str] = &[
+    "arch",
     "aws",
     "azure",
     #[cfg(feature = "battery")]
@@ -22,12 +23,17 @@ pub const ALL_MODULES: &[&str] = &[
     "cobol",
     "conda",
     "container",
+    "context",
+    "cpp_package_manager",
     "crystal",
+    "cuda",
     "daml",
     "dart",
+    "database",
     "deno",
     "directory",
     "docker_context",
+    "dotenv",
     "dotnet",
     "elixir",
     "elm",
@@ -35,29 +41,40 @@ pub const ALL_MODULES: &[&str] = &[
     "erlang",
     "fill",
     "gcloud",
+    #[cfg(feature = "git")]
     "git_branch",
+    #[cfg(feature = "git")]
     "git_commit",
+    #[cfg(feature = "git")]
     "git_metrics",
+    #[cfg(feature = "git")]
     "git_state",
+    #[cfg(feature = "git")]
     "git_status",
     "golang",
+    "gpu",
     "guix_shell",
     "haskell",
     "haxe",
+    "healthcheck",
     "helm",
     "hg_branch",
+    "history",
     "hostname",
     "java",
     "jobs",
     "julia",
+    "jupyter",
     "kotlin",
     "kubernetes",
+    "license",
     "line_break",
     "localip",
     "lua",
     "memory_usage",
     "meson",
     "nim",
+    "nix_generation",
     "nix_shell",
     "nodejs",
     "ocaml",
@@ -71,6 +88,7 @@ pub const ALL_MODULES: &[&str] = &[
     "purescript",
     "python",
     "raku",
+    "reboot_required",
     "red",
     "rlang",
     "ruby",
@@ -83,8 +101,11 @@ pub const ALL_MODULES: &[&str] = &[
     "status",
     "sudo",
     "swift",
+    "systemd",
     "terraform",
     "time",
+    "timer",
+    "updates",
     "username",
     "vagrant",
     "vcsh",
@@ -165,9 +186,10 @@ impl<'a> Module<'a> {
         }
 
         match shell {
-            Shell::Bash => ansi_strings_modified(ansi_strings, shell),
+            Shell::Bash | Shell::Osh | Shell::Ysh => ansi_strings_modified(ansi_strings, shell),
             Shell::Zsh => ansi_strings_modified(ansi_strings, shell),
             Shell::Tcsh => ansi_strings_modified(ansi_strings, shell),
+            Shell::Rc => ansi_strings_modified(ansi_strings, shell),
             _ => ansi_strings,
         }
     }
@@ -218,12 +240,19 @@ where
     if chunks.is_empty() {
         current
     } else {
-        let fill_size = term_width
-            .and_then(|tw| if tw > used { Some(tw - used) } else { None })
-            .map(|remaining| remaining / chunks.len());
+        let weights: Vec<usize> = chunks
+            .iter()
+            .enumerate()
+            .map(|(index, (_, fill))| fill.weight(index))
+            .collect();
+        let total_weight: usize = weights.iter().sum();
+        let remaining = term_width.and_then(|tw| if tw > used { Some(tw - used) } else { None });
         chunks
             .into_iter()
-            .flat_map(|(strs, fill)| {
+            .zip(weights)
+            .flat_map(|((strs, fill), weight)| {
+                let fill_size = remaining
+                    .map(|remaining| (remaining * weight).checked_div(total_weight).unwrap_or(0));
                 strs.into_iter()
                     .chain(std::iter::once(fill.ansi_string(fill_size)))
             })
@@ -302,4 +331,28 @@ mod tests {
 
         assert!(!module.is_empty());
     }
+
+    #[test]
+    fn test_fills_share_width_by_weight() {
+        let fill = Segment::weighted_fill(None, "-", vec![1, 2]);
+        let mut segments = Segment::from_text(None, "a");
+        segments.push(fill.clone());
+        segments.extend(Segment::from_text(None, "b"));
+        segments.push(fill.clone());
+        segments.extend(Segment::from_text(None, "c"));
+        segments.push(Segment::LineTerm);
+        segments.extend(Segment::from_text(None, "d"));
+        segments.push(fill);
+        let module = Module {
+            config: None,
+            name: "unit_test".to_string(),
+            description: "This is a unit test".to_string(),
+            segments,
+            duration: Duration::default(),
+        };
+
+        let actual =
+            AnsiStrings(&module.ansi_strings_for_shell(Shell::Unknown, Some(9))).to_string();
+        assert_eq!(actual, "a--b----c\nd--------");
+    }
 }
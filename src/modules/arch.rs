@@ -0,0 +1,208 @@
+use std::env;
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::arch::ArchConfig;
+use crate::formatter::StringFormatter;
+use crate::utils::{context_path, read_file};
+
+/// Creates a module with the CPU architecture starship runs as
+///
+/// The module is highlighted when the architecture is emulated, e.g. an `x86_64` shell running
+/// through Rosetta 2 on Apple Silicon, or an `aarch64` container running through qemu-user.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("arch");
+    let config = ArchConfig::try_load(module.config);
+
+    // As we default to disabled=true, we have to check here after loading our config module,
+    // before it was only checking against whatever is in the config starship.toml
+    if config.disabled {
+        return None;
+    }
+
+    // The architecture is the one of this machine
+    if !context.is_local() {
+        return None;
+    }
+
+    let arch = env::consts::ARCH;
+    let emulation = get_emulation(context, arch);
+    if config.only_emulated && emulation.is_none() {
+        return None;
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" if emulation.is_some() => Some(Ok(config.emulated_style)),
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "arch" => Some(Ok(arch.to_string())),
+                "host_arch" => emulation
+                    .as_ref()
+                    .map(|emulation| Ok(emulation.host_arch.clone())),
+                "emulator" => emulation
+                    .as_ref()
+                    .map(|emulation| Ok(emulation.emulator.to_string())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `arch`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+struct Emulation {
+    host_arch: String,
+    emulator: &'static str,
+}
+
+fn get_emulation(context: &Context, arch: &str) -> Option<Emulation> {
+    if cfg!(target_os = "macos") {
+        // `sysctl.proc_translated` is 1 for processes translated by Rosetta 2
+        let translated = context.exec_cmd("sysctl", &["-n", "sysctl.proc_translated"])?;
+        return (translated.stdout.trim() == "1").then(|| Emulation {
+            host_arch: "aarch64".to_string(),
+            emulator: "Rosetta",
+        });
+    }
+
+    // qemu-user emulates `uname` but not procfs, so the kernel still reports the host here
+    let host_arch = read_file(context_path(context, "/proc/sys/kernel/arch")).ok()?;
+    let host_arch = normalize_arch(host_arch.trim());
+    if is_native(arch, host_arch) {
+        return None;
+    }
+
+    let binfmt = context_path(context, "/proc/sys/fs/binfmt_misc").join(format!("qemu-{arch}"));
+    Some(Emulation {
+        host_arch: host_arch.to_string(),
+        emulator: if binfmt.exists() { "qemu" } else { "emulation" },
+    })
+}
+
+/// Maps the machine names of the kernel to the names Rust uses, e.g. `i686` to `x86`
+fn normalize_arch(machine: &str) -> &str {
+    match machine {
+        "i386" | "i486" | "i586" | "i686" => "x86",
+        "arm64" => "aarch64",
+        "ppc64" | "ppc64le" => "powerpc64",
+        "ppc" => "powerpc",
+        machine if machine.starts_with("armv") => "arm",
+        machine => machine,
+    }
+}
+
+/// Whether binaries for `arch` run natively on `host_arch`, including 32-bit binaries on 64-bit
+/// hosts of the same family
+fn is_native(arch: &str, host_arch: &str) -> bool {
+    arch == host_arch
+        || matches!(
+            (arch, host_arch),
+            ("x86", "x86_64") | ("arm", "aarch64") | ("powerpc", "powerpc64")
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use crate::utils;
+    use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
+
+    #[test]
+    fn test_is_native() {
+        assert!(is_native("x86_64", normalize_arch("x86_64")));
+        assert!(is_native("x86", normalize_arch("x86_64")));
+        assert!(is_native("arm", normalize_arch("aarch64")));
+        assert!(!is_native("aarch64", normalize_arch("x86_64")));
+        assert!(!is_native("x86_64", normalize_arch("arm64")));
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("arch").collect();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn native_arch() -> io::Result<()> {
+        let renderer = ModuleRenderer::new("arch").config(toml::toml! {
+            [arch]
+            disabled = false
+        });
+        let kernel_arch = renderer.root_path().join("proc/sys/kernel/arch");
+        fs::create_dir_all(kernel_arch.parent().unwrap())?;
+        utils::write_file(&kernel_arch, format!("{}\n", env::consts::ARCH))?;
+
+        let expected = Some(format!(
+            "{} ",
+            Color::White.bold().dimmed().paint(env::consts::ARCH)
+        ));
+        assert_eq!(expected, renderer.collect());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn qemu_user_emulation() -> io::Result<()> {
+        let renderer = ModuleRenderer::new("arch").config(toml::toml! {
+            [arch]
+            disabled = false
+            only_emulated = true
+            format = "[$arch on $host_arch via $emulator]($style)"
+        });
+        let proc_sys = renderer.root_path().join("proc/sys");
+        fs::create_dir_all(proc_sys.join("kernel"))?;
+        fs::create_dir_all(proc_sys.join("fs/binfmt_misc"))?;
+        utils::write_file(proc_sys.join("kernel/arch"), "s390x\n")?;
+        utils::write_file(
+            proc_sys
+                .join("fs/binfmt_misc")
+                .join(format!("qemu-{}", env::consts::ARCH)),
+            "enabled\n",
+        )?;
+
+        let expected = Some(format!(
+            "{}",
+            Color::Red
+                .bold()
+                .paint(format!("{} on s390x via qemu", env::consts::ARCH))
+        ));
+        assert_eq!(expected, renderer.collect());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn only_emulated_hides_native_arch() -> io::Result<()> {
+        let renderer = ModuleRenderer::new("arch").config(toml::toml! {
+            [arch]
+            disabled = false
+            only_emulated = true
+        });
+        let kernel_arch = renderer.root_path().join("proc/sys/kernel/arch");
+        fs::create_dir_all(kernel_arch.parent().unwrap())?;
+        utils::write_file(&kernel_arch, format!("{}\n", env::consts::ARCH))?;
+
+        assert_eq!(None, renderer.collect());
+        Ok(())
+    }
+}
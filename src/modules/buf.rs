@@ -10,8 +10,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_buf_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_extensions(&config.detect_extensions)
         .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     if !is_buf_project {
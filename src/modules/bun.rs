@@ -13,8 +13,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_bun_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_extensions(&config.detect_extensions)
         .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     if !is_bun_project {
@@ -85,13 +88,12 @@ mod tests {
     }
 
     #[test]
-    fn folder_with_bun_file() -> io::Result<()> {
-        let dir = tempfile::tempdir()?;
-        File::create(dir.path().join("bun.lockb"))?.sync_all()?;
-        let actual = ModuleRenderer::new("bun").path(dir.path()).collect();
+    fn folder_with_bun_file() {
+        let actual = ModuleRenderer::new("bun")
+            .with_project_file("bun.lockb", "")
+            .collect();
         let expected = Some(format!("via {}", Color::Red.bold().paint("🍞 v0.1.4 ")));
         assert_eq!(expected, actual);
-        dir.close()
     }
 
     #[test]
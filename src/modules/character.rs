@@ -19,7 +19,6 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         Insert,
     }
     const ASSUMED_MODE: ShellEditMode = ShellEditMode::Insert;
-    // TODO: extend config to more modes
 
     let mut module = context.new_module("character");
     let config: CharacterConfig = CharacterConfig::try_load(module.config);
@@ -29,21 +28,42 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let keymap = props.keymap.as_str();
     let exit_success = exit_code == "0";
 
+    // In vi mode, bash can't redraw the prompt when the keymap changes, so the init
+    // script has readline show the character through its mode strings instead.
+    // Nushell shows it the same way, through its prompt indicators.
+    if matches!(
+        (context.shell, keymap),
+        (Shell::Bash, "readline") | (Shell::Nu, "indicator")
+    ) {
+        return None;
+    }
+
     // Match shell "keymap" names to normalized vi modes
     // NOTE: in vi mode, fish reports normal mode as "default".
     // Unfortunately, this is also the name of the non-vi default mode.
     // We do some environment detection in src/init.rs to translate.
     // The result: in non-vi fish, keymap is always reported as "insert"
     let mode = match (&context.shell, keymap) {
-        (Shell::Fish, "default") | (Shell::Zsh, "vicmd") | (Shell::Cmd, "vi") => {
-            ShellEditMode::Normal
-        }
+        (Shell::Fish, "default")
+        | (Shell::Zsh, "vicmd")
+        | (Shell::Cmd, "vi")
+        | (Shell::Bash, "vi-command" | "vi-move")
+        | (Shell::PowerShell, "command")
+        | (Shell::Nu, "normal") => ShellEditMode::Normal,
         (Shell::Fish, "visual") => ShellEditMode::Visual,
         (Shell::Fish, "replace") => ShellEditMode::Replace,
         (Shell::Fish, "replace_one") => ShellEditMode::ReplaceOne,
         _ => ASSUMED_MODE,
     };
 
+    let mode_name = match mode {
+        ShellEditMode::Normal => "normal",
+        ShellEditMode::Visual => "visual",
+        ShellEditMode::Replace => "replace",
+        ShellEditMode::ReplaceOne => "replace_one",
+        ShellEditMode::Insert => "insert",
+    };
+
     let symbol = match mode {
         ShellEditMode::Normal => config.vimcmd_symbol,
         ShellEditMode::Visual => config.vimcmd_visual_symbol,
@@ -64,6 +84,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "symbol" => Some(symbol),
                 _ => None,
             })
+            .map(|variable| match variable {
+                "mode" => Some(Ok(mode_name)),
+                _ => None,
+            })
             .parse(None, Some(context))
     });
 
@@ -260,4 +284,97 @@ mod test {
             .collect();
         assert_eq!(expected_other, actual);
     }
+
+    #[test]
+    fn bash_keymap() {
+        let expected_vicmd = Some(format!("{} ", Color::Green.bold().paint("❮")));
+        let expected_insert = Some(format!("{} ", Color::Green.bold().paint("❯")));
+
+        for keymap in ["vi-command", "vi-move"] {
+            let actual = ModuleRenderer::new("character")
+                .shell(Shell::Bash)
+                .keymap(keymap)
+                .collect();
+            assert_eq!(expected_vicmd, actual);
+        }
+
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::Bash)
+            .keymap("vi-insert")
+            .collect();
+        assert_eq!(expected_insert, actual);
+
+        // readline shows the character through its mode strings
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::Bash)
+            .keymap("readline")
+            .collect();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn powershell_keymap() {
+        let expected_vicmd = Some(format!("{} ", Color::Green.bold().paint("❮")));
+        let expected_insert = Some(format!("{} ", Color::Green.bold().paint("❯")));
+
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::PowerShell)
+            .keymap("command")
+            .collect();
+        assert_eq!(expected_vicmd, actual);
+
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::PowerShell)
+            .keymap("insert")
+            .collect();
+        assert_eq!(expected_insert, actual);
+    }
+
+    #[test]
+    fn nu_keymap() {
+        let expected_vicmd = Some(format!("{} ", Color::Green.bold().paint("❮")));
+        let expected_insert = Some(format!("{} ", Color::Green.bold().paint("❯")));
+
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::Nu)
+            .keymap("normal")
+            .collect();
+        assert_eq!(expected_vicmd, actual);
+
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::Nu)
+            .keymap("insert")
+            .collect();
+        assert_eq!(expected_insert, actual);
+
+        // the prompt indicators show the character
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::Nu)
+            .keymap("indicator")
+            .collect();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn mode_variable() {
+        let cases = [
+            (Shell::Fish, "default", "normal"),
+            (Shell::Fish, "visual", "visual"),
+            (Shell::Fish, "replace", "replace"),
+            (Shell::Fish, "replace_one", "replace_one"),
+            (Shell::Zsh, "viins", "insert"),
+        ];
+
+        for (shell, keymap, mode) in cases {
+            let actual = ModuleRenderer::new("character")
+                .config(toml::toml! {
+                    [character]
+                    format = "$mode"
+                })
+                .shell(shell)
+                .keymap(keymap)
+                .collect();
+            assert_eq!(Some(mode.to_string()), actual);
+        }
+    }
 }
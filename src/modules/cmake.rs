@@ -12,8 +12,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_cmake_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_extensions(&config.detect_extensions)
         .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     if !is_cmake_project {
@@ -72,7 +75,7 @@ fn parse_cmake_version(cmake_version: &str) -> Option<String> {
 mod tests {
     use crate::test::ModuleRenderer;
     use nu_ansi_term::Color;
-    use std::fs::File;
+    use std::fs::{self, File};
     use std::io;
 
     #[test]
@@ -103,4 +106,35 @@ mod tests {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn buildfolder_with_detect_content() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let render = || {
+            ModuleRenderer::new("cmake")
+                .path(dir.path())
+                .config(toml::toml! {
+                    [cmake]
+                    detect_files = []
+                    detect_content = [
+                        { file = "CMakeCache.txt", regex = "CMAKE_GENERATOR:INTERNAL=Ninja" },
+                    ]
+                })
+                .collect()
+        };
+
+        fs::write(
+            dir.path().join("CMakeCache.txt"),
+            "CMAKE_GENERATOR:INTERNAL=Unix Makefiles\n",
+        )?;
+        assert_eq!(None, render());
+
+        fs::write(
+            dir.path().join("CMakeCache.txt"),
+            "CMAKE_GENERATOR:INTERNAL=Ninja\n",
+        )?;
+        let expected = Some(format!("via {}", Color::Blue.bold().paint("△ v3.17.3 ")));
+        assert_eq!(expected, render());
+        dir.close()
+    }
 }
@@ -1,33 +1,53 @@
 use super::{Context, Module, ModuleConfig};
 
 use crate::configs::cmd_duration::CmdDurationConfig;
-use crate::formatter::StringFormatter;
-use crate::utils::render_time;
+use crate::formatter::{StringFormatter, VariableHolder};
+use crate::utils::{create_command, render_time, write_file_atomic};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::process::{Command, Stdio};
 
 /// Outputs the time it took the last command to execute
 ///
 /// Will only print if last command took more than a certain amount of time to
-/// execute. Default is two seconds, but can be set by config option `min_time`.
+/// execute. Default is two seconds, but can be set by config option `min_time`,
+/// or per command with `min_time_by_command`.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("cmd_duration");
     let config: CmdDurationConfig = CmdDurationConfig::try_load(module.config);
 
-    if config.min_time < 0 {
+    let cmd_name = context.get_cmd_name();
+    let min_time = cmd_name
+        .and_then(|name| config.min_time_by_command.get(name))
+        .copied()
+        .unwrap_or(config.min_time);
+
+    if min_time < 0 {
         log::warn!(
             "min_time in [cmd_duration] ({}) was less than zero",
-            config.min_time
+            min_time
         );
         return None;
     }
 
     let elapsed = context.get_cmd_duration()?;
-    let config_min = config.min_time as u128;
+    let formatter = StringFormatter::new(config.format);
+
+    // Every run is recorded, not only the ones long enough to be shown,
+    // so that the average stays representative of the command
+    let delta = match (&formatter, cmd_name) {
+        (Ok(formatter), Some(cmd_name)) if formatter.get_variables().contains("delta") => {
+            update_average(context, &config, cmd_name, elapsed)
+        }
+        _ => None,
+    };
 
-    if elapsed < config_min {
+    if elapsed < min_time as u128 {
         return None;
     }
 
-    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+    let parsed = formatter.and_then(|formatter| {
         formatter
             .map_style(|variable| match variable {
                 "style" => Some(Ok(config.style)),
@@ -35,6 +55,9 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "duration" => Some(Ok(render_time(elapsed, config.show_milliseconds))),
+                "delta" => delta
+                    .map(|delta| render_delta(delta, config.show_milliseconds))
+                    .map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -51,67 +74,374 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(undistract_me(module, &config, context, elapsed))
 }
 
-#[cfg(not(feature = "notify"))]
-fn undistract_me<'a, 'b>(
-    module: Module<'a>,
-    _config: &'b CmdDurationConfig,
-    _context: &'a Context,
-    _elapsed: u128,
-) -> Module<'a> {
-    module
+/// Rolling averages of past command durations, persisted across prompts
+#[derive(Default, Deserialize, Serialize)]
+struct DurationHistory {
+    last: Option<LastRun>,
+    #[serde(default)]
+    commands: BTreeMap<String, CommandAverage>,
 }
 
-#[cfg(feature = "notify")]
-fn undistract_me<'a, 'b>(
+#[derive(Deserialize, Serialize)]
+struct LastRun {
+    session: String,
+    command: String,
+    duration: u64,
+    delta: Option<i64>,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+struct CommandAverage {
+    average: u64,
+    samples: u64,
+}
+
+/// Adds the run to the rolling average of the command, and returns how much
+/// longer (or shorter) it took than the average before it
+fn update_average(
+    context: &Context,
+    config: &CmdDurationConfig,
+    cmd_name: &str,
+    elapsed: u128,
+) -> Option<i64> {
+    if config.average_window == 0 {
+        return None;
+    }
+
+    let path = context.get_cache_dir()?.join("cmd_duration.toml");
+    let elapsed = u64::try_from(elapsed).ok()?;
+    let session = context.get_env("STARSHIP_SESSION_KEY").unwrap_or_default();
+
+    let mut history: DurationHistory = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default();
+
+    // The same run is rendered again when the prompt is redrawn, or by the right prompt
+    if let Some(last) = history.last.as_ref().filter(|last| {
+        last.session == session && last.command == cmd_name && last.duration == elapsed
+    }) {
+        return last.delta;
+    }
+
+    let previous = history.commands.get(cmd_name).copied();
+    let delta = previous.map(|previous| elapsed as i64 - previous.average as i64);
+
+    let samples = previous
+        .map_or(0, |previous| previous.samples)
+        .saturating_add(1)
+        .min(config.average_window as u64);
+    let average = previous.map_or(elapsed, |previous| {
+        (previous.average as i64 + (elapsed as i64 - previous.average as i64) / samples as i64)
+            as u64
+    });
+    history
+        .commands
+        .insert(cmd_name.to_string(), CommandAverage { average, samples });
+    history.last = Some(LastRun {
+        session,
+        command: cmd_name.to_string(),
+        duration: elapsed,
+        delta,
+    });
+
+    // Sessions finishing commands at the same time may still drop one of their samples, which
+    // only makes the averages move a little slower
+    let written = toml::to_string(&history)
+        .map_err(|err| err.to_string())
+        .and_then(|content| write_file_atomic(&path, content).map_err(|err| err.to_string()));
+    if let Err(err) = written {
+        log::warn!("Unable to save command durations to {:?}: {}", path, err);
+    }
+
+    delta
+}
+
+fn render_delta(delta: i64, show_millis: bool) -> String {
+    let sign = if delta < 0 { '-' } else { '+' };
+    format!(
+        "{}{}",
+        sign,
+        render_time(u128::from(delta.unsigned_abs()), show_millis)
+    )
+}
+
+/// The ways a notification can be delivered once a command finishes
+#[derive(Debug, PartialEq, Eq)]
+enum NotificationBackend<'a> {
+    /// Native notifications through `notify-rust`
+    #[cfg(feature = "notify")]
+    Native,
+    NotifySend,
+    Osascript,
+    Toast,
+    Webhook(&'a str),
+}
+
+impl<'a> NotificationBackend<'a> {
+    fn from_config(config: &CmdDurationConfig<'a>) -> Option<Self> {
+        match config.notification_backend {
+            "auto" => Some(Self::platform_default()),
+            "notify-send" => Some(Self::NotifySend),
+            "osascript" => Some(Self::Osascript),
+            "toast" => Some(Self::Toast),
+            "webhook" => {
+                let url = config.notification_webhook_url.map(Self::Webhook);
+                if url.is_none() {
+                    log::warn!("notification_webhook_url in [cmd_duration] must be set to use the webhook backend");
+                }
+                url
+            }
+            backend => {
+                log::warn!(
+                    "Unknown notification_backend in [cmd_duration]: {}",
+                    backend
+                );
+                None
+            }
+        }
+    }
+
+    #[cfg(feature = "notify")]
+    fn platform_default() -> Self {
+        Self::Native
+    }
+
+    #[cfg(not(feature = "notify"))]
+    fn platform_default() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::Osascript
+        } else if cfg!(windows) {
+            Self::Toast
+        } else {
+            Self::NotifySend
+        }
+    }
+
+    /// Whether the notification is shown on the local desktop
+    fn is_desktop(&self) -> bool {
+        !matches!(self, Self::Webhook(_))
+    }
+}
+
+fn undistract_me<'a>(
     module: Module<'a>,
-    config: &'b CmdDurationConfig,
+    config: &CmdDurationConfig,
     context: &'a Context,
     elapsed: u128,
 ) -> Module<'a> {
-    use notify_rust::{Notification, Timeout};
     use nu_ansi_term::{unstyle, AnsiStrings};
 
-    if config.show_notifications && config.min_time_to_notify as u128 <= elapsed {
-        if cfg!(target_os = "linux") {
-            let in_graphical_session = ["DISPLAY", "WAYLAND_DISPLAY", "MIR_SOCKET"]
-                .iter()
-                .find_map(|&var| context.get_env(var).filter(|val| !val.is_empty()))
-                .is_some();
+    if !should_notify(context, config, elapsed) {
+        return module;
+    }
 
-            if !in_graphical_session {
-                return module;
-            };
-        }
+    let backend = match NotificationBackend::from_config(config) {
+        Some(backend) => backend,
+        None => return module,
+    };
 
-        let body = format!(
-            "Command execution {}",
-            unstyle(&AnsiStrings(&module.ansi_strings()))
-        );
+    if cfg!(target_os = "linux") && backend.is_desktop() {
+        let in_graphical_session = ["DISPLAY", "WAYLAND_DISPLAY", "MIR_SOCKET"]
+            .iter()
+            .find_map(|&var| context.get_env(var).filter(|val| !val.is_empty()))
+            .is_some();
 
-        let timeout = match config.notification_timeout {
-            Some(v) => Timeout::Milliseconds(v),
-            None => Timeout::Default,
+        if !in_graphical_session {
+            return module;
         };
+    }
 
-        let mut notification = Notification::new();
-        notification
-            .summary("Command finished")
-            .body(&body)
-            .icon("utilities-terminal")
-            .timeout(timeout);
+    let body = format!(
+        "Command execution {}",
+        unstyle(&AnsiStrings(&module.ansi_strings()))
+    );
 
-        if let Err(err) = notification.show() {
-            log::trace!("Cannot show notification: {}", err);
-        }
+    if let Err(err) = send_notification(&backend, config, context, &body, elapsed) {
+        log::trace!("Cannot show notification: {}", err);
     }
 
     module
 }
 
+/// Checks the duration, command filters and window focus against the config
+fn should_notify(context: &Context, config: &CmdDurationConfig, elapsed: u128) -> bool {
+    if !config.show_notifications || (config.min_time_to_notify as u128) > elapsed {
+        return false;
+    }
+
+    let cmd_name = context.get_cmd_name();
+    let is_listed = |commands: &[&str]| cmd_name.map_or(false, |name| commands.contains(&name));
+    if is_listed(&config.notification_ignored_commands)
+        || (!config.notification_commands.is_empty() && !is_listed(&config.notification_commands))
+    {
+        return false;
+    }
+
+    // Only skip the notification when the terminal is known to be focused
+    !(config.notification_only_when_unfocused && is_terminal_focused(context) == Some(true))
+}
+
+/// Checks whether the terminal window running the shell has the focus
+///
+/// This is detected by comparing the active window to `WINDOWID` on X11,
+/// and the frontmost application to `TERM_PROGRAM` on macOS.
+fn is_terminal_focused(context: &Context) -> Option<bool> {
+    if let Some(window_id) = context.get_env("WINDOWID") {
+        let active = context.exec_cmd("xdotool", &["getactivewindow"])?;
+        return Some(active.stdout.trim() == window_id.trim());
+    }
+
+    if cfg!(target_os = "macos") {
+        let app = match context.get_env("TERM_PROGRAM")?.as_str() {
+            "Apple_Terminal" => "Terminal".to_string(),
+            "iTerm.app" => "iTerm2".to_string(),
+            app => app.to_string(),
+        };
+        let frontmost = context.exec_cmd(
+            "osascript",
+            &["-e", "tell application \"System Events\" to get name of first process whose frontmost is true"],
+        )?;
+        return Some(frontmost.stdout.trim().eq_ignore_ascii_case(&app));
+    }
+
+    None
+}
+
+const TOAST_SCRIPT: &str = "\
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null
+$template = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02)
+$text = $template.GetElementsByTagName('text')
+$text.Item(0).AppendChild($template.CreateTextNode($env:STARSHIP_NOTIFICATION_SUMMARY)) > $null
+$text.Item(1).AppendChild($template.CreateTextNode($env:STARSHIP_NOTIFICATION_BODY)) > $null
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('starship').Show([Windows.UI.Notifications.ToastNotification]::new($template))";
+
+const SUMMARY: &str = "Command finished";
+
+fn send_notification(
+    backend: &NotificationBackend,
+    config: &CmdDurationConfig,
+    context: &Context,
+    body: &str,
+    elapsed: u128,
+) -> std::io::Result<()> {
+    let mut command = match backend {
+        #[cfg(feature = "notify")]
+        NotificationBackend::Native => return show_native_notification(config, body),
+        NotificationBackend::NotifySend => {
+            let mut command = create_command("notify-send")?;
+            command.args(["--app-name=starship", "--icon=utilities-terminal"]);
+            if let Some(timeout) = config.notification_timeout {
+                command.arg(format!("--expire-time={timeout}"));
+            }
+            command.args([SUMMARY, body]);
+            command
+        }
+        NotificationBackend::Osascript => {
+            let mut command = create_command("osascript")?;
+            // Pass the text as arguments, to avoid having to escape it in the script
+            command.args([
+                "-e",
+                "on run argv",
+                "-e",
+                "display notification (item 2 of argv) with title (item 1 of argv)",
+                "-e",
+                "end run",
+                SUMMARY,
+                body,
+            ]);
+            command
+        }
+        NotificationBackend::Toast => {
+            let mut command = create_command("powershell")?;
+            command
+                .args(["-NoProfile", "-NonInteractive", "-Command", TOAST_SCRIPT])
+                .env("STARSHIP_NOTIFICATION_SUMMARY", SUMMARY)
+                .env("STARSHIP_NOTIFICATION_BODY", body);
+            command
+        }
+        NotificationBackend::Webhook(url) => webhook_command(context, body, elapsed, url)?,
+    };
+
+    // Don't wait for the notification to be delivered, to avoid delaying the prompt
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+}
+
+#[cfg(feature = "network")]
+fn webhook_command(
+    context: &Context,
+    body: &str,
+    elapsed: u128,
+    url: &str,
+) -> std::io::Result<Command> {
+    let payload = serde_json::json!({
+        "summary": SUMMARY,
+        "body": body,
+        "command": context.get_cmd_name(),
+        "duration": elapsed as u64,
+    });
+    let mut command = create_command("curl")?;
+    command.args([
+        "--silent",
+        "--max-time",
+        "10",
+        "--header",
+        "Content-Type: application/json",
+        "--data",
+        &payload.to_string(),
+        url,
+    ]);
+    Ok(command)
+}
+
+#[cfg(not(feature = "network"))]
+fn webhook_command(
+    _context: &Context,
+    _body: &str,
+    _elapsed: u128,
+    _url: &str,
+) -> std::io::Result<Command> {
+    log::warn!(
+        "The webhook notification_backend needs starship to be built with the `network` feature"
+    );
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "starship was built without the `network` feature",
+    ))
+}
+
+#[cfg(feature = "notify")]
+fn show_native_notification(config: &CmdDurationConfig, body: &str) -> std::io::Result<()> {
+    use notify_rust::{Notification, Timeout};
+
+    let timeout = match config.notification_timeout {
+        Some(v) => Timeout::Milliseconds(v),
+        None => Timeout::Default,
+    };
+
+    Notification::new()
+        .summary(SUMMARY)
+        .body(body)
+        .icon("utilities-terminal")
+        .timeout(timeout)
+        .show()
+        .map(drop)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::test::ModuleRenderer;
+    use super::{should_notify, NotificationBackend};
+    use crate::configs::cmd_duration::CmdDurationConfig;
+    use crate::test::{default_context, ModuleRenderer};
+    use crate::utils::CommandOutput;
     use nu_ansi_term::Color;
+    use std::io;
 
     #[test]
     fn config_blank_duration_1s() {
@@ -188,4 +518,172 @@ mod tests {
         let expected = Some(format!("underwent {} ", Color::Yellow.bold().paint("5s")));
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn config_min_time_by_command() {
+        let render = |cmd_name: &str| {
+            ModuleRenderer::new("cmd_duration")
+                .config(toml::toml! {
+                    [cmd_duration]
+                    min_time_by_command = { cargo = 5000, rsync = 500 }
+                })
+                .cmd_name(cmd_name)
+                .cmd_duration(3000)
+                .collect()
+        };
+
+        let shown = Some(format!("took {} ", Color::Yellow.bold().paint("3s")));
+        assert_eq!(None, render("cargo"));
+        assert_eq!(shown, render("/usr/bin/rsync"));
+        assert_eq!(shown, render("vim"));
+    }
+
+    #[test]
+    fn config_min_time_by_command_duration_1s() {
+        let actual = ModuleRenderer::new("cmd_duration")
+            .config(toml::toml! {
+                [cmd_duration]
+                min_time_by_command = { rsync = 500 }
+            })
+            .cmd_name("rsync")
+            .cmd_duration(1000)
+            .collect();
+
+        let expected = Some(format!("took {} ", Color::Yellow.bold().paint("1s")));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn delta_against_average() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let render = |session: &str, duration: u64| {
+            ModuleRenderer::new("cmd_duration")
+                .config(toml::toml! {
+                    [cmd_duration]
+                    format = "$duration( $delta)"
+                })
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .env("STARSHIP_SESSION_KEY", session)
+                .cmd_name("cargo")
+                .cmd_duration(duration)
+                .collect()
+        };
+
+        assert_eq!(Some("10s".to_string()), render("a", 10_000));
+        assert_eq!(Some("13s +3s".to_string()), render("a", 13_000));
+        // Averaged over both runs, at 11.5s
+        assert_eq!(Some("8s -3s".to_string()), render("b", 8_000));
+        cache_dir.close()
+    }
+
+    #[test]
+    fn delta_redrawn_prompt_is_counted_once() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let render = |duration: u64| {
+            ModuleRenderer::new("cmd_duration")
+                .config(toml::toml! {
+                    [cmd_duration]
+                    format = "$duration( $delta)"
+                })
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .env("STARSHIP_SESSION_KEY", "a")
+                .cmd_name("cargo")
+                .cmd_duration(duration)
+                .collect()
+        };
+
+        assert_eq!(Some("10s".to_string()), render(10_000));
+        assert_eq!(Some("14s +4s".to_string()), render(14_000));
+        assert_eq!(Some("14s +4s".to_string()), render(14_000));
+        cache_dir.close()
+    }
+
+    fn notifying_config<'a>() -> CmdDurationConfig<'a> {
+        CmdDurationConfig {
+            show_notifications: true,
+            ..CmdDurationConfig::default()
+        }
+    }
+
+    #[test]
+    fn notify_after_min_time_to_notify() {
+        let context = default_context();
+        let config = notifying_config();
+
+        assert!(!should_notify(&context, &config, 44_000));
+        assert!(should_notify(&context, &config, 45_000));
+        assert!(!should_notify(
+            &context,
+            &CmdDurationConfig::default(),
+            45_000
+        ));
+    }
+
+    #[test]
+    fn notify_command_filters() {
+        let mut context = default_context();
+        context.properties.cmd_name = Some("vim".to_string());
+
+        let ignored = CmdDurationConfig {
+            notification_ignored_commands: vec!["vim", "ssh"],
+            ..notifying_config()
+        };
+        assert!(!should_notify(&context, &ignored, 60_000));
+
+        let listed = CmdDurationConfig {
+            notification_commands: vec!["cargo", "make"],
+            ..notifying_config()
+        };
+        assert!(!should_notify(&context, &listed, 60_000));
+
+        context.properties.cmd_name = Some("cargo".to_string());
+        assert!(should_notify(&context, &listed, 60_000));
+        assert!(should_notify(&context, &ignored, 60_000));
+    }
+
+    #[test]
+    fn notify_only_when_unfocused() {
+        let config = CmdDurationConfig {
+            notification_only_when_unfocused: true,
+            ..notifying_config()
+        };
+        let with_active_window = |window: &str| {
+            let mut context = default_context();
+            context.env.insert("WINDOWID", "41943046".to_string());
+            context.cmd.insert(
+                "xdotool getactivewindow",
+                Some(CommandOutput {
+                    stdout: format!("{window}\n"),
+                    stderr: String::new(),
+                }),
+            );
+            should_notify(&context, &config, 60_000)
+        };
+
+        assert!(!with_active_window("41943046"));
+        assert!(with_active_window("52428806"));
+    }
+
+    #[test]
+    fn notification_backends() {
+        let backend = |backend, url| {
+            NotificationBackend::from_config(&CmdDurationConfig {
+                notification_backend: backend,
+                notification_webhook_url: url,
+                ..CmdDurationConfig::default()
+            })
+        };
+
+        assert_eq!(
+            Some(NotificationBackend::NotifySend),
+            backend("notify-send", None)
+        );
+        assert_eq!(Some(NotificationBackend::Toast), backend("toast", None));
+        assert_eq!(
+            Some(NotificationBackend::Webhook("https://example.com/hook")),
+            backend("webhook", Some("https://example.com/hook"))
+        );
+        assert_eq!(None, backend("webhook", None));
+        assert_eq!(None, backend("carrier-pigeon", None));
+    }
 }
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use super::{Context, Module, ModuleConfig};
 
 use super::utils::directory::truncate;
@@ -6,10 +8,19 @@ use crate::formatter::StringFormatter;
 
 /// Creates a module with the current Conda environment
 ///
-/// Will display the Conda environment iff `$CONDA_DEFAULT_ENV` is set.
+/// Will display the Conda environment iff `$CONDA_DEFAULT_ENV` is set, or `$CONDA_PREFIX` when
+/// the environment was activated by micromamba.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     // Reference implementation: https://github.com/denysdovhan/spaceship-prompt/blob/master/sections/conda.zsh
-    let conda_env = context.get_env("CONDA_DEFAULT_ENV").unwrap_or_default();
+    let tool = get_tool(context);
+    let conda_env = context
+        .get_env("CONDA_DEFAULT_ENV")
+        .filter(|env| !env.trim().is_empty())
+        .or_else(|| {
+            let prefix = context.get_env("CONDA_PREFIX")?;
+            (tool == "micromamba").then(|| prefix_env_name(context, &prefix))
+        })
+        .unwrap_or_default();
     if conda_env.trim().is_empty() {
         return None;
     }
@@ -21,7 +32,17 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    let conda_env = truncate(&conda_env, config.truncation_length).unwrap_or(conda_env);
+    let truncate_env = |env: String| truncate(&env, config.truncation_length).unwrap_or(env);
+    let stack = get_env_stack(context, conda_env.clone())
+        .into_iter()
+        .map(truncate_env)
+        .collect::<Vec<_>>();
+    let conda_env = truncate_env(conda_env);
+    let env_stack = if config.show_full_stack || stack.len() == 1 {
+        stack.join(config.stack_separator)
+    } else {
+        format!("{}+{}", conda_env, stack.len() - 1)
+    };
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -34,7 +55,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "environment" => Some(Ok(conda_env.as_str())),
+                "environment" => Some(Ok(conda_env.clone())),
+                "env_stack" => Some(Ok(env_stack.clone())),
+                "depth" => Some(Ok(stack.len().to_string())),
+                "tool" => Some(Ok(tool.to_string())),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -51,6 +75,74 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Returns which of `conda`, `mamba` or `micromamba` manages the environments
+fn get_tool(context: &Context) -> &'static str {
+    let exe_name = |var: &str| {
+        context.get_env(var).and_then(|exe| {
+            Path::new(&exe)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_lowercase())
+        })
+    };
+    match exe_name("MAMBA_EXE").as_deref() {
+        Some("micromamba") => "micromamba",
+        Some("mamba") => "mamba",
+        _ => "conda",
+    }
+}
+
+/// Names an environment from its prefix, like conda does in `$CONDA_DEFAULT_ENV`
+fn prefix_env_name(context: &Context, prefix: &str) -> String {
+    let path = Path::new(prefix);
+    let is_root = ["CONDA_ROOT", "MAMBA_ROOT_PREFIX"]
+        .iter()
+        .filter_map(|var| context.get_env(var))
+        .any(|root| Path::new(&root) == path);
+    let is_named_env = path
+        .parent()
+        .and_then(Path::file_name)
+        .map_or(false, |dir| dir == "envs");
+
+    if is_root {
+        String::from("base")
+    } else if is_named_env {
+        path.file_name().map_or_else(
+            || prefix.to_string(),
+            |name| name.to_string_lossy().to_string(),
+        )
+    } else {
+        prefix.to_string()
+    }
+}
+
+/// Returns the environments stacked with `conda activate --stack`, from the bottom up
+///
+/// Each activation increments `$CONDA_SHLVL` and saves the previous prefix in
+/// `$CONDA_PREFIX_<level>`, and stacked activations also set `$CONDA_STACKED_<level>`.
+fn get_env_stack(context: &Context, current_env: String) -> Vec<String> {
+    let level = context
+        .get_env("CONDA_SHLVL")
+        .and_then(|level| level.trim().parse::<usize>().ok())
+        .unwrap_or_default();
+
+    let mut stack = vec![current_env];
+    for level in (2..=level).rev() {
+        if context
+            .get_env(format!("CONDA_STACKED_{}", level))
+            .as_deref()
+            != Some("true")
+        {
+            break;
+        }
+        match context.get_env(format!("CONDA_PREFIX_{}", level - 1)) {
+            Some(prefix) => stack.push(prefix_env_name(context, &prefix)),
+            None => break,
+        }
+    }
+    stack.reverse();
+    stack
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
@@ -104,4 +196,43 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn stacked_envs() {
+        let render = |show_full_stack: bool| {
+            ModuleRenderer::new("conda")
+                .env("CONDA_DEFAULT_ENV", "astronauts")
+                .env("CONDA_SHLVL", "3")
+                .env("CONDA_ROOT", "/opt/conda")
+                .env("CONDA_PREFIX_1", "/opt/conda")
+                .env("CONDA_PREFIX_2", "/opt/conda/envs/tools")
+                .env("CONDA_STACKED_3", "true")
+                .env("CONDA_PREFIX", "/opt/conda/envs/astronauts")
+                .config(toml::toml! {
+                    [conda]
+                    format = "$env_stack $depth"
+                    show_full_stack = show_full_stack
+                })
+                .collect()
+        };
+
+        // `tools` replaced `base` instead of being stacked on top of it
+        assert_eq!(Some("tools > astronauts 2".to_string()), render(true));
+        assert_eq!(Some("astronauts+1 2".to_string()), render(false));
+    }
+
+    #[test]
+    fn micromamba_env() {
+        let actual = ModuleRenderer::new("conda")
+            .env("MAMBA_EXE", "/home/user/.local/bin/micromamba")
+            .env("MAMBA_ROOT_PREFIX", "/home/user/micromamba")
+            .env("CONDA_PREFIX", "/home/user/micromamba/envs/astronauts")
+            .config(toml::toml! {
+                [conda]
+                format = "$tool $environment"
+            })
+            .collect();
+
+        assert_eq!(Some("micromamba astronauts".to_string()), actual);
+    }
 }
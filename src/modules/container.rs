@@ -5,69 +5,151 @@ pub fn module<'a>(_context: &'a Context) -> Option<Module<'a>> {
     None
 }
 
-#[cfg(target_os = "linux")]
-pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
-    use super::ModuleConfig;
-    use crate::configs::container::ContainerConfig;
-    use crate::formatter::StringFormatter;
-    use crate::utils::{self, read_file};
+#[cfg(not(target_os = "linux"))]
+pub fn container_name(_context: &Context) -> Option<String> {
+    None
+}
 
-    pub fn container_name(context: &Context) -> Option<String> {
-        use crate::utils::context_path;
+/// The application and runtime of a Flatpak sandbox or Snap confinement
+#[cfg(target_os = "linux")]
+struct SandboxInfo {
+    app_id: String,
+    runtime: Option<String>,
+}
 
-        if context_path(context, "/proc/vz").exists() && !context_path(context, "/proc/bc").exists()
-        {
-            // OpenVZ
-            return Some("OpenVZ".into());
+/// Reads the `[Application]` section of `/.flatpak-info`, which flatpak mounts in every sandbox
+#[cfg(target_os = "linux")]
+fn flatpak_info(context: &Context) -> Option<SandboxInfo> {
+    use crate::utils::{context_path, read_file};
+
+    let contents = read_file(context_path(context, "/.flatpak-info")).ok()?;
+    let mut in_application = false;
+    let mut app_id = None;
+    let mut runtime = None;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_application = line == "[Application]";
+        } else if in_application {
+            match line.split_once('=') {
+                Some(("name", value)) => app_id = Some(value.trim().to_string()),
+                // e.g. `runtime/org.freedesktop.Platform/x86_64/23.08`
+                Some(("runtime", value)) => {
+                    runtime = Some(value.trim().trim_start_matches("runtime/").to_string());
+                }
+                _ => {}
+            }
         }
+    }
 
-        if context_path(context, "/run/host/container-manager").exists() {
-            // OCI
-            return Some("OCI".into());
-        }
+    Some(SandboxInfo {
+        app_id: app_id.unwrap_or_else(|| "flatpak".into()),
+        runtime,
+    })
+}
 
-        // WSL with systemd will set the contents of this file to "wsl"
-        // Avoid showing the container module in that case
-        let systemd_path = context_path(context, "/run/systemd/container");
-        if utils::read_file(systemd_path)
-            .ok()
-            .filter(|s| s.trim() != "wsl")
-            .is_some()
-        {
-            // systemd
-            return Some("Systemd".into());
-        }
+/// Reads the environment snapd sets for confined applications, and the base snap from the
+/// `meta/snap.yaml` of the running snap
+#[cfg(target_os = "linux")]
+fn snap_info(context: &Context) -> Option<SandboxInfo> {
+    use crate::utils::{context_path, read_file};
+
+    let app_id = context
+        .get_env("SNAP_INSTANCE_NAME")
+        .or_else(|| context.get_env("SNAP_NAME"))?;
+    let runtime = context.get_env("SNAP").and_then(|snap| {
+        let snap_yaml = read_file(context_path(context, &snap).join("meta/snap.yaml")).ok()?;
+        snap_yaml.lines().find_map(|line| {
+            let base = line.strip_prefix("base:")?.trim();
+            Some(base.trim_matches(|c| c == '"' || c == '\'').to_string())
+        })
+    });
 
-        let container_env_path = context_path(context, "/run/.containerenv");
+    Some(SandboxInfo { app_id, runtime })
+}
 
-        if container_env_path.exists() {
-            // podman and others
+#[cfg(target_os = "linux")]
+fn sandbox_info(context: &Context) -> Option<SandboxInfo> {
+    flatpak_info(context).or_else(|| snap_info(context))
+}
 
-            let image_res = read_file(container_env_path)
-                .map(|s| {
-                    s.lines()
-                        .find_map(|l| {
-                            l.starts_with("image=\"").then(|| {
-                                let r = l.split_at(7).1;
-                                let name = r.rfind('/').map(|n| r.split_at(n + 1).1);
-                                String::from(name.unwrap_or(r).trim_end_matches('"'))
-                            })
-                        })
-                        .unwrap_or_else(|| "podman".into())
-                })
-                .unwrap_or_else(|_| "podman".into());
+/// Detects the container the current process is running in, if any
+#[cfg(target_os = "linux")]
+pub fn container_name(context: &Context) -> Option<String> {
+    use crate::utils::{self, context_path, read_file};
 
-            return Some(image_res);
-        }
+    // The container is detected from the files of this machine
+    if !context.is_local() {
+        return None;
+    }
 
-        if context_path(context, "/.dockerenv").exists() {
-            // docker
-            return Some("Docker".into());
-        }
+    if context_path(context, "/.flatpak-info").exists() {
+        // Flatpak
+        return Some("Flatpak".into());
+    }
+
+    if context.get_env("SNAP_NAME").is_some() {
+        // Snap
+        return Some("Snap".into());
+    }
+
+    if context_path(context, "/proc/vz").exists() && !context_path(context, "/proc/bc").exists() {
+        // OpenVZ
+        return Some("OpenVZ".into());
+    }
+
+    if context_path(context, "/run/host/container-manager").exists() {
+        // OCI
+        return Some("OCI".into());
+    }
 
-        None
+    // WSL with systemd will set the contents of this file to "wsl"
+    // Avoid showing the container module in that case
+    let systemd_path = context_path(context, "/run/systemd/container");
+    if utils::read_file(systemd_path)
+        .ok()
+        .filter(|s| s.trim() != "wsl")
+        .is_some()
+    {
+        // systemd
+        return Some("Systemd".into());
     }
 
+    let container_env_path = context_path(context, "/run/.containerenv");
+
+    if container_env_path.exists() {
+        // podman and others
+
+        let image_res = read_file(container_env_path)
+            .map(|s| {
+                s.lines()
+                    .find_map(|l| {
+                        l.starts_with("image=\"").then(|| {
+                            let r = l.split_at(7).1;
+                            let name = r.rfind('/').map(|n| r.split_at(n + 1).1);
+                            String::from(name.unwrap_or(r).trim_end_matches('"'))
+                        })
+                    })
+                    .unwrap_or_else(|| "podman".into())
+            })
+            .unwrap_or_else(|_| "podman".into());
+
+        return Some(image_res);
+    }
+
+    if context_path(context, "/.dockerenv").exists() {
+        // docker
+        return Some("Docker".into());
+    }
+
+    None
+}
+
+#[cfg(target_os = "linux")]
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    use super::ModuleConfig;
+    use crate::configs::container::ContainerConfig;
+    use crate::formatter::StringFormatter;
+
     let mut module = context.new_module("container");
     let config: ContainerConfig = ContainerConfig::try_load(module.config);
 
@@ -76,6 +158,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     }
 
     let container_name = container_name(context)?;
+    let sandbox = sandbox_info(context);
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -88,7 +171,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "name" => Some(Ok(&container_name)),
+                "name" => Some(Ok(container_name.as_str())),
+                "app_id" => sandbox.as_ref().map(|sandbox| Ok(sandbox.app_id.as_str())),
+                "runtime" => sandbox
+                    .as_ref()
+                    .and_then(|sandbox| sandbox.runtime.as_deref())
+                    .map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -108,9 +196,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
-    use crate::utils;
     use nu_ansi_term::Color;
-    use std::fs;
 
     #[test]
     fn test_none_if_disabled() {
@@ -128,24 +214,18 @@ mod tests {
     }
 
     fn containerenv(name: Option<&str>) -> std::io::Result<(Option<String>, Option<String>)> {
+        let contents = match name {
+            Some(name) => format!("image=\"{name}\"\n"),
+            None => String::new(),
+        };
+
         let renderer = ModuleRenderer::new("container")
             // For a custom config
             .config(toml::toml! {
                [container]
                disabled = false
-            });
-
-        let root_path = renderer.root_path();
-
-        let containerenv = root_path.join("run/.containerenv");
-
-        fs::create_dir_all(containerenv.parent().unwrap())?;
-
-        let contents = match name {
-            Some(name) => format!("image=\"{name}\"\n"),
-            None => String::new(),
-        };
-        utils::write_file(&containerenv, contents)?;
+            })
+            .with_file("run/.containerenv", contents);
 
         // The output of the module
         let actual = renderer
@@ -193,14 +273,8 @@ mod tests {
             .config(toml::toml! {
                [container]
                disabled = false
-            });
-
-        let root_path = renderer.root_path();
-
-        let systemd_path = root_path.join("run/systemd/container");
-
-        fs::create_dir_all(systemd_path.parent().unwrap())?;
-        utils::write_file(&systemd_path, "systemd-nspawn\n")?;
+            })
+            .with_file("run/systemd/container", "systemd-nspawn\n");
 
         // The output of the module
         let actual = renderer
@@ -230,14 +304,8 @@ mod tests {
             .config(toml::toml! {
                [container]
                disabled = false
-            });
-
-        let root_path = renderer.root_path();
-
-        let systemd_path = root_path.join("run/systemd/container");
-
-        fs::create_dir_all(systemd_path.parent().unwrap())?;
-        utils::write_file(&systemd_path, "wsl\n")?;
+            })
+            .with_file("run/systemd/container", "wsl\n");
 
         // The output of the module
         let actual = renderer
@@ -253,6 +321,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_flatpak() -> std::io::Result<()> {
+        let renderer = ModuleRenderer::new("container").config(toml::toml! {
+           [container]
+           format = "[$name $app_id \\($runtime\\)]($style)"
+        })
+        .with_file(
+            ".flatpak-info",
+            "[Application]\nname=org.gnome.Builder\nruntime=runtime/org.gnome.Sdk/x86_64/45\n\n[Instance]\ninstance-id=1234\n",
+        );
+
+        let actual = renderer.collect();
+        let expected = Some(format!(
+            "{}",
+            Color::Red
+                .bold()
+                .dimmed()
+                .paint("Flatpak org.gnome.Builder (org.gnome.Sdk/x86_64/45)")
+        ));
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_snap() -> std::io::Result<()> {
+        let renderer = ModuleRenderer::new("container")
+            .with_env([("SNAP_NAME", "code"), ("SNAP", "/snap/code/150")])
+            .config(toml::toml! {
+               [container]
+               format = "[$name $app_id \\($runtime\\)]($style)"
+            })
+            .with_file(
+                "snap/code/150/meta/snap.yaml",
+                "name: code\nbase: core20\nconfinement: classic\n",
+            );
+
+        let actual = renderer.collect();
+        let expected = Some(format!(
+            "{}",
+            Color::Red.bold().dimmed().paint("Snap code (core20)")
+        ));
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
     #[test]
     #[cfg(not(target_os = "linux"))]
     fn test_containerenv() -> std::io::Result<()> {
@@ -0,0 +1,177 @@
+use super::{Context, Module, ModuleConfig};
+
+use super::container::container_name;
+use super::username::{is_root_user, is_ssh_session};
+use crate::configs::context::ContextConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module summarizing where the prompt is running
+///
+/// Will display the module if any of the following criteria are met:
+///     - The user is connected as an SSH session and `show_ssh` is true
+///     - The shell runs inside a container and `show_container` is true
+///     - The user is root, or running under `sudo`, and `show_root` is true
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("context");
+    let config: ContextConfig = ContextConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let is_ssh = config.show_ssh && is_ssh_session(context);
+    let container = if config.show_container {
+        container_name(context)
+    } else {
+        None
+    };
+    let is_root = config.show_root && (is_root_user() || context.get_env_os("SUDO_USER").is_some());
+
+    if !is_ssh && container.is_none() && !is_root {
+        return None;
+    }
+
+    let hostname = if config.show_hostname {
+        gethostname::gethostname()
+            .into_string()
+            .ok()
+            .map(|host| match config.trim_at {
+                "" => host,
+                trim_at => host.split(trim_at).next().unwrap_or_default().to_string(),
+            })
+    } else {
+        None
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "ssh" => is_ssh.then_some(config.ssh_symbol),
+                "container" => container.as_ref().map(|_| config.container_symbol),
+                "root" => is_root.then_some(config.root_symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "hostname" => hostname.as_ref().map(Ok),
+                "container_name" => container.as_ref().map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `context`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use crate::utils;
+    use nu_ansi_term::Color;
+    use std::fs;
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("context")
+            .env("SSH_CONNECTION", "192.168.1.10 51234 192.168.1.2 22")
+            .collect();
+        let expected = None;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn local_session() {
+        let actual = ModuleRenderer::new("context")
+            .config(toml::toml! {
+                [context]
+                disabled = false
+                show_root = false
+            })
+            .collect();
+        let expected = None;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn ssh_session() {
+        let actual = ModuleRenderer::new("context")
+            .config(toml::toml! {
+                [context]
+                disabled = false
+                show_root = false
+                show_hostname = false
+            })
+            .env("SSH_CONNECTION", "192.168.1.10 51234 192.168.1.2 22")
+            .collect();
+        let expected = Some(format!("{} ", Color::Yellow.bold().paint("🌐")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn ssh_session_toggled_off() {
+        let actual = ModuleRenderer::new("context")
+            .config(toml::toml! {
+                [context]
+                disabled = false
+                show_ssh = false
+                show_root = false
+            })
+            .env("SSH_CONNECTION", "192.168.1.10 51234 192.168.1.2 22")
+            .collect();
+        let expected = None;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn sudo_session() {
+        let actual = ModuleRenderer::new("context")
+            .config(toml::toml! {
+                [context]
+                disabled = false
+                show_hostname = false
+            })
+            .env("SUDO_USER", "astronaut")
+            .collect();
+        let expected = Some(format!("{} ", Color::Yellow.bold().paint("⚡")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn container_and_ssh_session() -> std::io::Result<()> {
+        let renderer = ModuleRenderer::new("context")
+            .config(toml::toml! {
+                [context]
+                disabled = false
+                show_root = false
+                format = "$ssh$container \\[$container_name\\]"
+            })
+            .env("SSH_CONNECTION", "192.168.1.10 51234 192.168.1.2 22");
+
+        let containerenv = renderer.root_path().join("run/.containerenv");
+        fs::create_dir_all(containerenv.parent().unwrap())?;
+        utils::write_file(&containerenv, "image=\"fedora-toolbox:35\"\n")?;
+
+        let actual = renderer.collect();
+        let expected = Some("🌐⬢ [fedora-toolbox:35]".to_string());
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+}
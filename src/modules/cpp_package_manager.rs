@@ -0,0 +1,174 @@
+use std::env;
+use std::path::Path;
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::cpp_package_manager::CppPackageManagerConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module with the C++ package manager of the current project
+///
+/// Shows the Conan profile when a `conanfile.txt` or `conanfile.py` is found and the vcpkg triplet
+/// when a `vcpkg.json` manifest is found. Conan takes precedence when both are present.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("cpp_package_manager");
+    let config = CppPackageManagerConfig::try_load(module.config);
+
+    let is_conan_project = context
+        .try_begin_scan()?
+        .set_files(&config.conan_files)
+        .is_match();
+    let (manager, profile) = if is_conan_project {
+        ("conan", get_conan_profile(context))
+    } else if context
+        .try_begin_scan()?
+        .set_files(&config.vcpkg_files)
+        .is_match()
+    {
+        ("vcpkg", get_vcpkg_triplet(context))
+    } else {
+        return None;
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "manager" => Some(Ok(manager)),
+                "profile" => Some(Ok(profile.as_str())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `cpp_package_manager`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Conan 2 reads the default profile name from `CONAN_DEFAULT_PROFILE`, Conan 1 reads a path
+/// from `CONAN_DEFAULT_PROFILE_PATH`
+fn get_conan_profile(context: &Context) -> String {
+    if let Some(profile) = context.get_env("CONAN_DEFAULT_PROFILE") {
+        return profile;
+    }
+
+    context
+        .get_env("CONAN_DEFAULT_PROFILE_PATH")
+        .and_then(|path| {
+            Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "default".to_string())
+}
+
+/// Uses `VCPKG_DEFAULT_TRIPLET`, falling back to the triplet vcpkg picks for the host
+fn get_vcpkg_triplet(context: &Context) -> String {
+    context
+        .get_env("VCPKG_DEFAULT_TRIPLET")
+        .unwrap_or_else(|| host_triplet(env::consts::ARCH, env::consts::OS))
+}
+
+fn host_triplet(arch: &str, os: &str) -> String {
+    let arch = match arch {
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        other => other,
+    };
+    let os = match os {
+        "macos" => "osx",
+        other => other,
+    };
+    format!("{arch}-{os}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn folder_without_package_manager_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("CMakeLists.txt"))?.sync_all()?;
+        let actual = ModuleRenderer::new("cpp_package_manager")
+            .path(dir.path())
+            .collect();
+        assert_eq!(None, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_conanfile() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("conanfile.py"))?.sync_all()?;
+        let actual = ModuleRenderer::new("cpp_package_manager")
+            .path(dir.path())
+            .collect();
+        let expected = Some(format!(
+            "with {} ",
+            Color::Blue.bold().paint("📦 conan (default)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_conanfile_and_profile() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("conanfile.txt"))?.sync_all()?;
+        let actual = ModuleRenderer::new("cpp_package_manager")
+            .path(dir.path())
+            .env(
+                "CONAN_DEFAULT_PROFILE_PATH",
+                "/home/user/.conan/profiles/clang",
+            )
+            .collect();
+        let expected = Some(format!(
+            "with {} ",
+            Color::Blue.bold().paint("📦 conan (clang)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_vcpkg_manifest() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("vcpkg.json"))?.sync_all()?;
+        let actual = ModuleRenderer::new("cpp_package_manager")
+            .path(dir.path())
+            .env("VCPKG_DEFAULT_TRIPLET", "x64-windows-static")
+            .collect();
+        let expected = Some(format!(
+            "with {} ",
+            Color::Blue.bold().paint("📦 vcpkg (x64-windows-static)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn test_host_triplet() {
+        assert_eq!(host_triplet("x86_64", "linux"), "x64-linux");
+        assert_eq!(host_triplet("aarch64", "macos"), "arm64-osx");
+        assert_eq!(host_triplet("x86", "windows"), "x86-windows");
+    }
+}
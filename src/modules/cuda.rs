@@ -0,0 +1,196 @@
+use std::fs;
+use std::path::Path;
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::cuda::CudaConfig;
+use crate::formatter::StringFormatter;
+use crate::formatter::VersionFormatter;
+use serde_json as json;
+
+/// Creates a module with the version of the CUDA or ROCm toolkit
+///
+/// The version is read from the installation in `$CUDA_HOME` (or `$CUDA_PATH`) or `$ROCM_PATH`
+/// when possible, and otherwise from `nvcc --version` or `hipconfig --version`.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("cuda");
+    let config: CudaConfig = CudaConfig::try_load(module.config);
+
+    let is_gpu_project = context
+        .try_begin_scan()?
+        .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
+        .is_match();
+
+    if !is_gpu_project {
+        return None;
+    }
+
+    let (toolkit, version) = get_cuda_version(context)
+        .map(|version| ("CUDA", Some(version)))
+        .or_else(|| get_rocm_version(context).map(|version| ("ROCm", Some(version))))?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "toolkit" => Some(Ok(toolkit.to_string())),
+                "version" => VersionFormatter::format_module_version(
+                    module.get_name(),
+                    version.as_deref()?,
+                    config.version_format,
+                )
+                .map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `cuda`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn get_cuda_version(context: &Context) -> Option<String> {
+    let from_install = ["CUDA_HOME", "CUDA_PATH"]
+        .iter()
+        .filter_map(|var| context.get_env(var))
+        .find_map(|cuda_home| read_cuda_install_version(Path::new(&cuda_home)));
+
+    from_install.or_else(|| parse_nvcc_version(&context.exec_cmd("nvcc", &["--version"])?.stdout))
+}
+
+/// Reads `version.json` (CUDA 11 and later) or `version.txt` of a CUDA installation
+fn read_cuda_install_version(cuda_home: &Path) -> Option<String> {
+    if let Ok(contents) = fs::read_to_string(cuda_home.join("version.json")) {
+        let version_json: json::Value = json::from_str(&contents).ok()?;
+        let version = version_json.get("cuda")?.get("version")?.as_str()?;
+        return Some(version.to_string());
+    }
+
+    // e.g. `CUDA Version 10.2.89`
+    let contents = fs::read_to_string(cuda_home.join("version.txt")).ok()?;
+    Some(contents.split_whitespace().nth(2)?.to_string())
+}
+
+/// Parses the release of `nvcc --version`, e.g. `Cuda compilation tools, release 12.2, V12.2.140`
+fn parse_nvcc_version(nvcc_version: &str) -> Option<String> {
+    let release = nvcc_version
+        .lines()
+        .find_map(|line| line.split_once("release "))?
+        .1;
+    let version = release.split(',').nth(1).map_or(release, str::trim);
+    Some(version.trim_start_matches('V').to_string())
+}
+
+fn get_rocm_version(context: &Context) -> Option<String> {
+    let from_install = context.get_env("ROCM_PATH").and_then(|rocm_path| {
+        fs::read_to_string(Path::new(&rocm_path).join(".info").join("version")).ok()
+    });
+    let version = match from_install {
+        Some(version) => version,
+        // e.g. `5.7.31921-d1770ee1b`
+        None => context.exec_cmd("hipconfig", &["--version"])?.stdout,
+    };
+    let version = version.trim().split('-').next()?;
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
+    use nu_ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_nvcc_version() {
+        let nvcc_version = "nvcc: NVIDIA (R) Cuda compiler driver
+Copyright (c) 2005-2023 NVIDIA Corporation
+Built on Tue_Aug_15_22:02:13_PDT_2023
+Cuda compilation tools, release 12.2, V12.2.140
+Build cuda_12.2.r12.2/compiler.33191640_0";
+        assert_eq!(
+            parse_nvcc_version(nvcc_version),
+            Some("12.2.140".to_string())
+        );
+    }
+
+    #[test]
+    fn folder_without_gpu_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.c"))?.sync_all()?;
+        let actual = ModuleRenderer::new("cuda").path(dir.path()).collect();
+        assert_eq!(None, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_cu_file_and_cuda_home() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("kernel.cu"))?.sync_all()?;
+        let cuda_home = tempfile::tempdir()?;
+        fs::write(
+            cuda_home.path().join("version.json"),
+            r#"{"cuda": {"name": "CUDA SDK", "version": "12.2.2"}}"#,
+        )?;
+
+        let actual = ModuleRenderer::new("cuda")
+            .path(dir.path())
+            .env("CUDA_HOME", cuda_home.path().to_string_lossy())
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Green.bold().paint("🟩 CUDA v12.2.2 ")
+        ));
+        assert_eq!(expected, actual);
+        cuda_home.close()?;
+        dir.close()
+    }
+
+    #[test]
+    fn conda_environment_with_rocm() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("environment.yml"),
+            "dependencies:\n  - pytorch\n  - pytorch-rocm\n",
+        )?;
+
+        let actual = ModuleRenderer::new("cuda")
+            .path(dir.path())
+            .cmd("nvcc --version", None)
+            .cmd(
+                "hipconfig --version",
+                Some(CommandOutput {
+                    stdout: "5.7.31921-d1770ee1b".to_string(),
+                    stderr: String::default(),
+                }),
+            )
+            .config(toml::toml! {
+                [cuda]
+                format = "$toolkit $version"
+            })
+            .collect();
+        assert_eq!(Some("ROCm v5.7.31921".to_string()), actual);
+        dir.close()
+    }
+}
@@ -5,13 +5,19 @@ use std::process::{Command, Stdio};
 use std::time::Duration;
 use std::time::Instant;
 
+use once_cell::sync::OnceCell;
 use process_control::{ChildExt, Control, Output};
+use serde_json::Value as JsonValue;
+use sha1::{Digest, Sha1};
 
 use super::{Context, Module, ModuleConfig};
 
 use crate::{
-    config::Either, configs::custom::CustomConfig, formatter::StringFormatter,
-    utils::create_command,
+    config::Either,
+    configs::custom::CustomConfig,
+    formatter::StringFormatter,
+    module::ALL_MODULES,
+    utils::{cached_output, create_command, encode_to_hex, parse_refresh_interval},
 };
 
 /// Creates a custom module with some configuration
@@ -36,21 +42,52 @@ pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
 
     let mut module = Module::new(name, config.description, Some(toml_config));
 
-    let mut is_match = context
-        .try_begin_scan()?
-        .set_extensions(&config.detect_extensions)
-        .set_files(&config.detect_files)
-        .set_folders(&config.detect_folders)
-        .is_match();
-
-    if !is_match {
+    let has_requirements = config.require_repo || config.when_module.is_some();
+    let meets_requirements = (!config.require_repo || context.get_repo().is_ok())
+        && config
+            .when_module
+            .map_or(true, |other| is_module_shown(other, context));
+
+    let mut is_match = meets_requirements
+        && context
+            .try_begin_scan()?
+            .set_extensions(&config.detect_extensions)
+            .set_files(&config.detect_files)
+            .set_files_not(&config.detect_files_not)
+            .set_folders(&config.detect_folders)
+            .set_content(&config.detect_content)
+            .set_scan_ancestors(config.scan_ancestors)
+            .is_match();
+
+    if meets_requirements && !is_match {
         is_match = match config.when {
+            // Without any other condition, meeting the requirements is enough to be shown
+            Either::First(false) if has_requirements && !has_detection(&config) => true,
             Either::First(b) => b,
             Either::Second(s) => exec_when(s, &config, context),
         };
     }
 
     if is_match {
+        // The command is only run once, and only if one of its variables is used
+        let output = OnceCell::new();
+        let get_output = || {
+            output
+                .get_or_init(|| exec_command_cached(config.command, context, &config))
+                .as_deref()
+        };
+        let json = OnceCell::new();
+        let get_json = || {
+            json.get_or_init(|| {
+                serde_json::from_str::<JsonValue>(get_output()?)
+                    .map_err(|error| {
+                        log::warn!("Output of `custom.{}` is not valid JSON: {}", name, error);
+                    })
+                    .ok()
+            })
+            .as_ref()
+        };
+
         let parsed = StringFormatter::new(config.format).and_then(|formatter| {
             formatter
                 .map_meta(|var, _| match var {
@@ -63,8 +100,7 @@ pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
                 })
                 .map_no_escaping(|variable| match variable {
                     "output" => {
-                        let output = exec_command(config.command, context, &config)?;
-                        let trimmed = output.trim();
+                        let trimmed = get_output()?.trim();
 
                         if trimmed.is_empty() {
                             None
@@ -74,6 +110,12 @@ pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
                     }
                     _ => None,
                 })
+                .map(|variable| {
+                    if !config.variables.contains(&variable) {
+                        return None;
+                    }
+                    json_variable(get_json()?, variable).map(Ok)
+                })
                 .parse(None, Some(context))
         });
 
@@ -90,6 +132,37 @@ pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Whether any files, extensions or folders are set to detect the module by
+fn has_detection(config: &CustomConfig) -> bool {
+    !config.detect_files.is_empty()
+        || !config.detect_extensions.is_empty()
+        || !config.detect_folders.is_empty()
+}
+
+/// Whether the given built-in module renders anything in the current context
+fn is_module_shown(name: &str, context: &Context) -> bool {
+    if !ALL_MODULES.contains(&name) {
+        log::warn!(
+            "Unknown module {:?} in `when_module` of a custom module",
+            name
+        );
+        return false;
+    }
+
+    super::handle(name, context).map_or(false, |module| !module.is_empty())
+}
+
+/// Looks up a key of the JSON object printed by the command, as a string
+fn json_variable(json: &JsonValue, key: &str) -> Option<String> {
+    let value = match json.get(key)? {
+        JsonValue::Null => return None,
+        JsonValue::String(value) => value.trim().to_string(),
+        value => value.to_string(),
+    };
+
+    (!value.is_empty()).then_some(value)
+}
+
 /// Return the invoking shell, using `shell` and fallbacking in order to `STARSHIP_SHELL` and "sh"/"cmd"
 fn get_shell<'a, 'b>(
     shell_args: &'b [&'a str],
@@ -211,6 +284,10 @@ fn exec_when(cmd: &str, config: &CustomConfig, context: &Context) -> bool {
 
 /// Execute the given command, returning its output on success
 fn exec_command(cmd: &str, context: &Context, config: &CustomConfig) -> Option<String> {
+    if let [program, args @ ..] = config.exec.as_slice() {
+        return exec_native(program, args, context, config);
+    }
+
     log::trace!("Running '{cmd}'");
 
     if let Some(output) = shell_command(cmd, config, context) {
@@ -233,6 +310,62 @@ fn exec_command(cmd: &str, context: &Context, config: &CustomConfig) -> Option<S
     }
 }
 
+/// Run the program given in `exec` directly, without going through a shell
+fn exec_native(
+    program: &str,
+    args: &[&str],
+    context: &Context,
+    config: &CustomConfig,
+) -> Option<String> {
+    if !config.ignore_timeout {
+        return context.exec_cmd(program, args).map(|output| output.stdout);
+    }
+
+    log::trace!("Running {program:?} with args {args:?}");
+    let output = create_command(program)
+        .ok()?
+        .args(args)
+        .current_dir(&context.current_dir)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        log::trace!("Non-zero exit code '{:?}'", output.status.code());
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).into())
+}
+
+/// Execute the given command, reusing its output for `refresh_interval` if set
+///
+/// The output is cached in the starship cache directory, keyed by the command, shell, `exec`
+/// and the current directory, so that it is shared between shell sessions. With `shared_cache`
+/// it is also shared between all directories. If the command fails once the interval has
+/// passed, the previous output keeps being shown.
+fn exec_command_cached(cmd: &str, context: &Context, config: &CustomConfig) -> Option<String> {
+    let interval = match config.refresh_interval.and_then(parse_refresh_interval) {
+        Some(interval) => interval,
+        None => return exec_command(cmd, context, config),
+    };
+
+    let mut hasher = Sha1::new();
+    hasher.update(cmd.as_bytes());
+    for arg in config.shell.0.iter().chain(&config.exec) {
+        hasher.update([0]);
+        hasher.update(arg.as_bytes());
+    }
+    if !config.shared_cache {
+        hasher.update([0]);
+        hasher.update(context.current_dir.to_string_lossy().as_bytes());
+    }
+    let key = format!("custom/{}", encode_to_hex(&hasher.finalize()));
+
+    cached_output(context, &key, interval, || {
+        exec_command(cmd, context, config)
+    })
+}
+
 /// If the specified shell refers to `PowerShell`, adds the arguments "-Command -" to the
 /// given command.
 /// Returns `false` if the shell shell expects scripts as arguments, `true` if as `stdin`.
@@ -268,6 +401,7 @@ mod tests {
     use super::*;
 
     use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
     use nu_ansi_term::Color;
     use std::fs::File;
     use std::io;
@@ -680,4 +814,329 @@ mod tests {
 
         dir.close()
     }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn json_variables() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let actual = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                [custom.test]
+                format = "$ticket \\($points\\)( $assignee)"
+                command = r#"echo '{"ticket": "PROJ-42", "points": 3, "assignee": null}'"#
+                shell = ["/bin/sh"]
+                when = true
+                variables = ["ticket", "points", "assignee"]
+            })
+            .collect();
+        let expected = Some("PROJ-42 (3)".to_string());
+        assert_eq!(expected, actual);
+
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn json_variables_must_be_declared() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let actual = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                [custom.test]
+                format = "$ticket( $points)"
+                command = r#"echo '{"ticket": "PROJ-42", "points": 3}'"#
+                shell = ["/bin/sh"]
+                when = true
+                variables = ["ticket"]
+            })
+            .collect();
+        let expected = Some("PROJ-42".to_string());
+        assert_eq!(expected, actual);
+
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn json_variables_invalid_json() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let actual = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                [custom.test]
+                format = "[($ticket)]($style)"
+                command = "echo PROJ-42"
+                shell = ["/bin/sh"]
+                when = true
+                variables = ["ticket"]
+            })
+            .collect();
+        assert_eq!(None, actual);
+
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn refresh_interval_caches_output() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache_dir = tempfile::tempdir()?;
+
+        let render = |refresh_interval: &str| {
+            let refresh_interval = refresh_interval.to_owned();
+            ModuleRenderer::new("custom.test")
+                .path(dir.path())
+                .config(toml::toml! {
+                    [custom.test]
+                    format = "$output"
+                    command = "echo run >> runs; wc -l < runs"
+                    shell = ["/bin/sh"]
+                    when = true
+                    refresh_interval = refresh_interval
+                })
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .collect()
+        };
+
+        assert_eq!(Some("1".to_string()), render("1h"));
+        assert_eq!(Some("1".to_string()), render("1h"));
+        // The cache is older than the interval, so the command runs again
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        assert_eq!(Some("2".to_string()), render("1s"));
+
+        dir.close()?;
+        cache_dir.close()
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn refresh_interval_caches_output_per_directory() -> io::Result<()> {
+        let first = tempfile::tempdir()?;
+        let second = tempfile::tempdir()?;
+        let cache_dir = tempfile::tempdir()?;
+
+        let render = |dir: &Path, shared_cache: bool| {
+            ModuleRenderer::new("custom.test")
+                .path(dir)
+                .config(toml::toml! {
+                    [custom.test]
+                    format = "$output"
+                    command = "basename \"$PWD\""
+                    shell = ["/bin/sh"]
+                    when = true
+                    refresh_interval = "1h"
+                    shared_cache = shared_cache
+                })
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .collect()
+        };
+        let name = |dir: &Path| Some(dir.file_name().unwrap().to_string_lossy().to_string());
+
+        assert_eq!(render(first.path(), false), name(first.path()));
+        assert_eq!(render(second.path(), false), name(second.path()));
+        // A shared cache shows the output of the first directory it ran in
+        assert_eq!(render(first.path(), true), name(first.path()));
+        assert_eq!(render(second.path(), true), name(first.path()));
+
+        first.close()?;
+        second.close()?;
+        cache_dir.close()
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn refresh_interval_keeps_output_on_failure() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache_dir = tempfile::tempdir()?;
+
+        let render = || {
+            ModuleRenderer::new("custom.test")
+                .path(dir.path())
+                .config(toml::toml! {
+                    [custom.test]
+                    format = "$output"
+                    command = "test ! -e ran && touch ran && echo online"
+                    shell = ["/bin/sh"]
+                    when = true
+                    refresh_interval = "1s"
+                })
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .collect()
+        };
+
+        assert_eq!(Some("online".to_string()), render());
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        assert_eq!(Some("online".to_string()), render());
+
+        dir.close()?;
+        cache_dir.close()
+    }
+
+    #[test]
+    fn when_module_shown() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let render = || {
+            ModuleRenderer::new("custom.test")
+                .path(dir.path())
+                .config(toml::toml! {
+                    [custom.test]
+                    format = "test"
+                    when_module = "nodejs"
+                })
+                .collect()
+        };
+
+        assert_eq!(None, render());
+
+        File::create(dir.path().join("package.json"))?.sync_all()?;
+        assert_eq!(Some("test".to_string()), render());
+
+        dir.close()
+    }
+
+    #[test]
+    fn when_module_with_other_conditions() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("package.json"))?.sync_all()?;
+
+        let render = || {
+            ModuleRenderer::new("custom.test")
+                .path(dir.path())
+                .config(toml::toml! {
+                    [custom.test]
+                    format = "test"
+                    when_module = "nodejs"
+                    detect_files = ["yarn.lock"]
+                })
+                .collect()
+        };
+
+        assert_eq!(None, render());
+
+        File::create(dir.path().join("yarn.lock"))?.sync_all()?;
+        assert_eq!(Some("test".to_string()), render());
+
+        dir.close()
+    }
+
+    #[test]
+    fn when_module_unknown() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let actual = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                [custom.test]
+                format = "test"
+                when_module = "not_a_module"
+            })
+            .collect();
+        assert_eq!(None, actual);
+
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(feature = "git")]
+    fn require_repo() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let render = || {
+            ModuleRenderer::new("custom.test")
+                .path(dir.path())
+                .config(toml::toml! {
+                    [custom.test]
+                    format = "test"
+                    require_repo = true
+                })
+                .collect()
+        };
+
+        assert_eq!(None, render());
+
+        create_command("git")?
+            .args(["init", "--quiet"])
+            .current_dir(dir.path())
+            .output()?;
+        assert_eq!(Some("test".to_string()), render());
+
+        dir.close()
+    }
+
+    #[test]
+    fn exec_runs_without_shell() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let actual = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                [custom.test]
+                format = "$output"
+                exec = ["kubectl", "config", "current-context"]
+                when = true
+            })
+            .cmd(
+                "kubectl config current-context",
+                Some(CommandOutput {
+                    stdout: String::from("prod-cluster\n"),
+                    stderr: String::default(),
+                }),
+            )
+            .collect();
+        let expected = Some("prod-cluster".to_string());
+        assert_eq!(expected, actual);
+
+        dir.close()
+    }
+
+    #[test]
+    fn exec_takes_precedence_over_command() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let actual = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                [custom.test]
+                format = "$output"
+                command = "echo from shell"
+                exec = ["tool", "--arg with spaces"]
+                when = true
+            })
+            .cmd(
+                "tool --arg with spaces",
+                Some(CommandOutput {
+                    stdout: String::from("native"),
+                    stderr: String::default(),
+                }),
+            )
+            .collect();
+        let expected = Some("native".to_string());
+        assert_eq!(expected, actual);
+
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn exec_ignore_timeout() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let actual = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                [custom.test]
+                format = "$output"
+                exec = ["pwd"]
+                when = true
+                ignore_timeout = true
+            })
+            .collect();
+        let expected = Some(dir.path().to_string_lossy().to_string());
+        assert_eq!(expected, actual);
+
+        dir.close()
+    }
 }
@@ -16,6 +16,7 @@ use super::utils::directory::truncate;
 use crate::config::ModuleConfig;
 use crate::configs::directory::DirectoryConfig;
 use crate::formatter::StringFormatter;
+use crate::utils::file_url;
 
 /// Creates a module with the current logical or physical directory
 ///
@@ -138,6 +139,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     };
     let repo_root_style = config.repo_root_style.unwrap_or(config.style);
     let before_repo_root_style = config.before_repo_root_style.unwrap_or(config.style);
+    let url = file_url("", physical_dir);
 
     let parsed = StringFormatter::new(display_format).and_then(|formatter| {
         formatter
@@ -146,6 +148,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "read_only_style" => Some(Ok(config.read_only_style)),
                 "repo_root_style" => Some(Ok(repo_root_style)),
                 "before_repo_root_style" => Some(Ok(before_repo_root_style)),
+                "url" => Some(Ok(url.as_str())),
                 _ => None,
             })
             .map(|variable| match variable {
@@ -598,6 +601,30 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn directory_link() -> io::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let dir = tmp_dir.path().join("rocket controls");
+        fs::create_dir_all(&dir)?;
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                format = "[$path](link:$url)"
+                truncation_length = 1
+            })
+            .path(&dir)
+            .collect();
+        let url = format!("file://{}/rocket%20controls", tmp_dir.path().display());
+        let expected = Some(format!(
+            "\u{1b}]8;;{url}\u{7}rocket controls\u{1b}]8;;\u{7}"
+        ));
+
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
     #[test]
     fn home_directory_custom_home_symbol() {
         let actual = ModuleRenderer::new("directory")
@@ -1457,6 +1484,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "git")]
     fn truncation_symbol_not_truncated_in_repo() -> io::Result<()> {
         let (tmp_dir, _) = make_known_tempdir(Path::new("/tmp"))?;
         let repo_dir = tmp_dir.path().join("above").join("repo");
@@ -1655,6 +1683,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "git")]
     fn highlight_git_root_dir() -> io::Result<()> {
         let (tmp_dir, _) = make_known_tempdir(Path::new("/tmp"))?;
         let repo_dir = tmp_dir.path().join("above").join("repo");
@@ -1682,6 +1711,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "git")]
     fn highlight_git_root_dir_config_change() -> io::Result<()> {
         let (tmp_dir, _) = make_known_tempdir(Path::new("/tmp"))?;
         let repo_dir = tmp_dir.path().join("above").join("repo");
@@ -1712,6 +1742,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "git")]
     fn highlight_git_root_dir_zero_truncation_length() -> io::Result<()> {
         let (tmp_dir, _) = make_known_tempdir(Path::new("/tmp"))?;
         let repo_dir = tmp_dir.path().join("above").join("repo");
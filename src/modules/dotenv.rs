@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::dotenv::DotenvConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module with the number of variables that the `.env` files of the current
+/// directory define
+///
+/// The values of the variables are never shown, only compared to the environment to tell how
+/// many of them were exported into the shell.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("dotenv");
+    let config = DotenvConfig::try_load(module.config);
+
+    // As we default to disabled=true, we have to check here after loading our config module,
+    // before it was only checking against whatever is in the config starship.toml
+    if config.disabled {
+        return None;
+    }
+
+    // Later files override the variables of earlier ones, as `.env.local` does for `.env`
+    let mut files = Vec::new();
+    let mut variables = HashMap::new();
+    for file in &config.files {
+        if let Some(contents) = context.read_file_from_pwd(file) {
+            files.push(*file);
+            variables.extend(parse_dotenv(&contents));
+        }
+    }
+    if files.is_empty() {
+        return None;
+    }
+
+    let exported = variables
+        .iter()
+        .filter(|(name, value)| context.get_env(name).as_ref() == Some(*value))
+        .count();
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "count" => Some(Ok(variables.len().to_string())),
+                "exported" => (exported > 0).then(|| Ok(exported.to_string())),
+                "files" => Some(Ok(files.join(", "))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `dotenv`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// The variables of a `.env` file, with quotes and trailing comments removed from their values
+fn parse_dotenv(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, value) = line.split_once('=')?;
+            let name = name.trim();
+            let is_name = name
+                .chars()
+                .next()
+                .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            is_name.then(|| (name.to_string(), parse_value(value.trim())))
+        })
+        .collect()
+}
+
+fn parse_value(value: &str) -> String {
+    for quote in ['"', '\''] {
+        if let Some(quoted) = value.strip_prefix(quote) {
+            if let Some((quoted, _)) = quoted.split_once(quote) {
+                return quoted.to_string();
+            }
+        }
+    }
+    match value.split_once(" #") {
+        Some((value, _)) => value.trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+
+    #[test]
+    fn no_env_file() {
+        let actual = ModuleRenderer::new("dotenv")
+            .with_project_file("README.md", "")
+            .config(toml::toml! {
+                [dotenv]
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn counts_variables_of_all_files() {
+        let actual = ModuleRenderer::new("dotenv")
+            .with_project_file(
+                ".env",
+                "# comment\nDATABASE_URL=postgres://db\nAPI_KEY=secret\n",
+            )
+            .with_project_file(".env.local", "export API_KEY='local secret'\nDEBUG=1\n")
+            .config(toml::toml! {
+                [dotenv]
+                disabled = false
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Yellow.bold().paint("🔑 3")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn counts_exported_variables_without_showing_values() {
+        let actual = ModuleRenderer::new("dotenv")
+            .with_project_file(".env", "API_KEY=\"secret\" # comment\nDEBUG=1\nPORT=8080\n")
+            .config(toml::toml! {
+                [dotenv]
+                disabled = false
+                format = "$files: $count( \\($exported exported\\))"
+            })
+            .env("API_KEY", "secret")
+            .env("PORT", "3000")
+            .collect();
+        let expected = Some(".env: 3 (1 exported)".to_string());
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_dotenv_lines() {
+        let parsed = parse_dotenv(
+            "A=1\n  export B = 'two' \nC=\"three # four\"\nD=five # six\n1E=no\nnot a variable\n",
+        );
+        let expected = vec![
+            ("A".to_string(), "1".to_string()),
+            ("B".to_string(), "two".to_string()),
+            ("C".to_string(), "three # four".to_string()),
+            ("D".to_string(), "five".to_string()),
+        ];
+
+        assert_eq!(parsed, expected);
+    }
+}
@@ -27,8 +27,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_dotnet_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_extensions(&config.detect_extensions)
         .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     if !is_dotnet_project {
@@ -501,6 +504,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "git")]
     fn shows_pinned_in_deeply_nested_project_within_repository() -> io::Result<()> {
         let workspace = create_workspace(true)?;
         let global_json = make_pinned_sdk_json("1.2.3");
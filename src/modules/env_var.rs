@@ -1,20 +1,26 @@
 use super::{Context, Module};
 
 use crate::config::ModuleConfig;
-use crate::configs::env_var::EnvVarConfig;
+use crate::configs::env_var::{EnvVarConfig, EnvVarEntryConfig};
 use crate::formatter::StringFormatter;
 use crate::segment::Segment;
+use indexmap::IndexMap;
+use regex::Regex;
 
 /// Creates `env_var_module` displayer which displays all configured environmental variables
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let config_table = context.config.get_env_var_modules()?;
+    // `mapping` is the one option of the fallback module that is a table itself
     let mut env_modules = config_table
         .iter()
-        .filter(|(_, config)| config.is_table())
+        .filter(|(name, config)| config.is_table() && name.as_str() != "mapping")
         .filter_map(|(variable, _)| env_var_module(vec!["env_var", variable], context))
         .collect::<Vec<Module>>();
     // Old configuration is present in starship configuration
-    if config_table.iter().any(|(_, config)| !config.is_table()) {
+    if config_table
+        .iter()
+        .any(|(name, config)| !config.is_table() || name.as_str() == "mapping")
+    {
         if let Some(fallback_env_var_module) = env_var_module(vec!["env_var"], context) {
             env_modules.push(fallback_env_var_module);
         }
@@ -51,25 +57,46 @@ fn env_var_module<'a>(module_config_path: Vec<&str>, context: &'a Context) -> Op
         return None;
     };
 
-    let variable_name = get_variable_name(module_config_path, &config);
-
-    let env_value = get_env_value(context, variable_name?, config.default)?;
-    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
-        formatter
-            .map_meta(|var, _| match var {
-                "symbol" => Some(config.symbol),
-                _ => None,
-            })
-            .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
-                _ => None,
-            })
-            .map(|variable| match variable {
-                "env_value" => Some(Ok(&env_value)),
-                _ => None,
-            })
-            .parse(None, Some(context))
-    });
+    // With `variables`, all of them are shown together in `$env_value`
+    let parsed = if config.variables.is_empty() {
+        let variable_name = get_variable_name(module_config_path, &config);
+        let env_value = get_env_value(context, variable_name?, config.default)?;
+        let env_value = map_value(env_value, &config.mapping);
+        StringFormatter::new(config.format).and_then(|formatter| {
+            formatter
+                .map_meta(|var, _| match var {
+                    "symbol" => Some(config.symbol),
+                    _ => None,
+                })
+                .map_style(|variable| match variable {
+                    "style" => Some(Ok(config.style)),
+                    _ => None,
+                })
+                .map(|variable| match variable {
+                    "env_value" => Some(Ok(&env_value)),
+                    _ => None,
+                })
+                .parse(None, Some(context))
+        })
+    } else {
+        let values = join_variables(context, &config)?;
+        StringFormatter::new(config.format).and_then(|formatter| {
+            formatter
+                .map_meta(|var, _| match var {
+                    "symbol" => Some(config.symbol),
+                    _ => None,
+                })
+                .map_style(|variable| match variable {
+                    "style" => Some(Ok(config.style)),
+                    _ => None,
+                })
+                .map_variables_to_segments(|variable| match variable {
+                    "env_value" => Some(Ok(values.clone())),
+                    _ => None,
+                })
+                .parse(None, Some(context))
+        })
+    };
 
     module.set_segments(match parsed {
         Ok(segments) => segments,
@@ -95,6 +122,69 @@ fn get_variable_name<'a>(
     }
 }
 
+/// The segments of the `variables` that are set, each rendered with its own `format` and
+/// separated by `separator`, or `None` when none of them are set
+fn join_variables(context: &Context, config: &EnvVarConfig) -> Option<Vec<Segment>> {
+    let mut segments: Vec<Segment> = Vec::new();
+    for entry in &config.variables {
+        let Some(entry_segments) = render_variable(context, config, entry) else {
+            continue;
+        };
+        if !segments.is_empty() {
+            segments.extend(Segment::from_text(None, config.separator));
+        }
+        segments.extend(entry_segments);
+    }
+    (!segments.is_empty()).then_some(segments)
+}
+
+fn render_variable(
+    context: &Context,
+    config: &EnvVarConfig,
+    entry: &EnvVarEntryConfig,
+) -> Option<Vec<Segment>> {
+    let env_value = get_env_value(context, entry.variable, entry.default)?;
+    let env_value = map_value(env_value, &entry.mapping);
+    let parsed = StringFormatter::new(entry.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(entry.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(entry.style.unwrap_or(config.style))),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "env_value" => Some(Ok(&env_value)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+    match parsed {
+        Ok(segments) => Some(segments),
+        Err(error) => {
+            log::warn!("Error in `env_var` for `{}`:\n{}", entry.variable, error);
+            None
+        }
+    }
+}
+
+/// Replace the part of the value that the first matching pattern of `mapping` matches with its
+/// label, which can refer to the groups of the pattern, as in `$1`
+fn map_value(value: String, mapping: &IndexMap<String, &str>) -> String {
+    for (pattern, label) in mapping {
+        match Regex::new(pattern) {
+            Ok(regex) if regex.is_match(&value) => {
+                return regex.replace(&value, *label).into_owned();
+            }
+            Ok(_) => {}
+            Err(error) => log::warn!("Invalid pattern in `env_var` mapping:\n{error}"),
+        }
+    }
+    value
+}
+
 fn get_env_value(context: &Context, name: &str, default: Option<&str>) -> Option<String> {
     match context.get_env(name) {
         Some(value) => Some(value),
@@ -253,6 +343,78 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn multiple_variables_in_one_module() {
+        let actual = ModuleRenderer::new("env_var")
+            .config(toml::toml! {
+                [env_var.cloud]
+                format = "on $env_value "
+                separator = "|"
+                [[env_var.cloud.variables]]
+                variable = "AWS_PROFILE"
+                symbol = "☁ "
+                style = "yellow"
+                [[env_var.cloud.variables]]
+                variable = "UNSET_VAR"
+                [[env_var.cloud.variables]]
+                variable = "KUBE_NAMESPACE"
+                default = "default"
+            })
+            .env("AWS_PROFILE", "dev")
+            .collect();
+        let expected = Some(format!(
+            "on {}|{} ",
+            Color::Yellow.paint("☁ dev"),
+            style().paint("default")
+        ));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn multiple_variables_none_set() {
+        let actual = ModuleRenderer::new("env_var")
+            .config(toml::toml! {
+                [env_var.cloud]
+                [[env_var.cloud.variables]]
+                variable = "UNSET_VAR"
+            })
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn values_are_mapped() {
+        let actual = ModuleRenderer::new("env_var")
+            .config(toml::toml! {
+                [env_var]
+                variable = "AWS_PROFILE"
+                [env_var.mapping]
+                "^production-(.*)$" = "prod:$1"
+                "^prod" = "unused"
+                "(" = "invalid"
+            })
+            .env("AWS_PROFILE", "production-eu")
+            .collect();
+        let expected = Some(format!("with {} ", style().paint("prod:eu")));
+
+        assert_eq!(expected, actual);
+
+        let actual = ModuleRenderer::new("env_var")
+            .config(toml::toml! {
+                [env_var.cloud]
+                [[env_var.cloud.variables]]
+                variable = "AWS_PROFILE"
+                mapping = { "^staging$" = "stg" }
+            })
+            .env("AWS_PROFILE", "development")
+            .collect();
+        let expected = Some(format!("with {} ", style().paint("development")));
+
+        assert_eq!(expected, actual);
+    }
+
     fn style() -> Style {
         // default style
         Color::Black.bold().dimmed()
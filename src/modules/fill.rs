@@ -16,7 +16,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     let style = parse_style_string(config.style, Some(context));
 
-    module.set_segments(vec![Segment::fill(style, config.symbol)]);
+    module.set_segments(vec![Segment::weighted_fill(
+        style,
+        config.symbol,
+        config.weights,
+    )]);
 
     Some(module)
 }
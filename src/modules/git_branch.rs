@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use unicode_segmentation::UnicodeSegmentation;
 
 use super::{Context, Module, ModuleConfig};
@@ -77,7 +78,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" => Some(Ok(Cow::from(config.style))),
+                "url" => {
+                    let branch = repo
+                        .remote
+                        .as_ref()
+                        .and_then(|remote| remote.branch.as_deref())
+                        .unwrap_or(branch_name);
+                    Some(Ok(Cow::from(format!("{}/tree/{branch}", repo.web_url()?))))
+                }
                 _ => None,
             })
             .map(|variable| match variable {
@@ -237,6 +246,33 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_format_link_to_forge() -> io::Result<()> {
+        let repo_dir = fixture_repo(FixtureProvider::Git)?;
+        create_command("git")?
+            .args([
+                "remote",
+                "set-url",
+                "origin",
+                "git@github.com:starship/starship.git",
+            ])
+            .current_dir(repo_dir.path())
+            .output()?;
+
+        let actual = ModuleRenderer::new("git_branch")
+            .config(toml::toml! {
+                [git_branch]
+                format = "[$branch](link:$url)"
+            })
+            .path(repo_dir.path())
+            .collect();
+        let expected =
+            "\u{1b}]8;;https://github.com/starship/starship/tree/master\u{7}master\u{1b}]8;;\u{7}";
+
+        assert_eq!(Some(expected.to_string()), actual);
+        repo_dir.close()
+    }
+
     #[test]
     fn test_format_mixed_colors() -> io::Result<()> {
         test_format(
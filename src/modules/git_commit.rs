@@ -1,5 +1,6 @@
 use super::{Context, Module, ModuleConfig};
 use git_repository::commit::describe::SelectRef::AllTags;
+use std::borrow::Cow;
 
 use crate::configs::git_commit::GitCommitConfig;
 use crate::context::Repo;
@@ -24,7 +25,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" => Some(Ok(Cow::from(config.style))),
+                "url" => {
+                    let repo = context.get_repo().ok()?;
+                    let hash = repo.open().head_id().ok()?.to_hex().to_string();
+                    Some(Ok(Cow::from(format!("{}/commit/{hash}", repo.web_url()?))))
+                }
                 _ => None,
             })
             .map(|variable| match variable {
@@ -158,38 +164,35 @@ mod tests {
     }
 
     #[test]
-    fn test_render_commit_hash_only_detached_on_branch() -> io::Result<()> {
-        let repo_dir = fixture_repo(FixtureProvider::Git)?;
-
+    fn test_render_commit_hash_only_detached_on_branch() {
         let actual = ModuleRenderer::new("git_commit")
-            .path(repo_dir.path())
+            .with_git_repo(|_| Ok(()))
             .collect();
 
         let expected = None;
 
         assert_eq!(expected, actual);
-        repo_dir.close()
     }
 
     #[test]
-    fn test_render_commit_hash_only_detached_on_detached() -> io::Result<()> {
-        let repo_dir = fixture_repo(FixtureProvider::Git)?;
-
-        create_command("git")?
-            .args(["checkout", "@~1"])
-            .current_dir(repo_dir.path())
-            .output()?;
-
-        let mut git_output = create_command("git")?
-            .args(["rev-parse", "HEAD"])
-            .current_dir(repo_dir.path())
-            .output()?
-            .stdout;
-        git_output.truncate(7);
-        let expected_hash = str::from_utf8(&git_output).unwrap();
+    fn test_render_commit_hash_only_detached_on_detached() {
+        let mut expected_hash = String::new();
 
         let actual = ModuleRenderer::new("git_commit")
-            .path(repo_dir.path())
+            .with_git_repo(|repo_dir| {
+                create_command("git")?
+                    .args(["checkout", "@~1"])
+                    .current_dir(repo_dir)
+                    .output()?;
+
+                let git_output = create_command("git")?
+                    .args(["rev-parse", "--short=7", "HEAD"])
+                    .current_dir(repo_dir)
+                    .output()?
+                    .stdout;
+                expected_hash = String::from_utf8_lossy(&git_output).trim().to_string();
+                Ok(())
+            })
             .collect();
 
         let expected = Some(format!(
@@ -198,7 +201,6 @@ mod tests {
         ));
 
         assert_eq!(expected, actual);
-        repo_dir.close()
     }
 
     #[test]
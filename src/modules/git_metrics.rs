@@ -23,22 +23,28 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let repo = context.get_repo().ok()?;
     let repo_root = repo.workdir.as_ref()?;
 
-    let diff = context
-        .exec_cmd(
-            "git",
-            &[
-                OsStr::new("--git-dir"),
-                repo.path.as_os_str(),
-                OsStr::new("--work-tree"),
-                repo_root.as_os_str(),
-                OsStr::new("--no-optional-locks"),
-                OsStr::new("diff"),
-                OsStr::new("--shortstat"),
-            ],
-        )?
-        .stdout;
+    // Diffing the worktree is too slow in large repositories, so it is skipped there
+    let skipped = repo.exceeds_size(config.max_index_entries);
+    let diff = if skipped {
+        String::new()
+    } else {
+        context
+            .exec_cmd(
+                "git",
+                &[
+                    OsStr::new("--git-dir"),
+                    repo.path.as_os_str(),
+                    OsStr::new("--work-tree"),
+                    repo_root.as_os_str(),
+                    OsStr::new("--no-optional-locks"),
+                    OsStr::new("diff"),
+                    OsStr::new("--shortstat"),
+                ],
+            )?
+            .stdout
+    };
 
-    let stats = GitDiff::parse(&diff);
+    let stats = (!skipped).then(|| GitDiff::parse(&diff));
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -48,8 +54,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "added" => GitDiff::get_variable(config.only_nonzero_diffs, stats.added),
-                "deleted" => GitDiff::get_variable(config.only_nonzero_diffs, stats.deleted),
+                "added" => stats.as_ref().and_then(|stats| {
+                    GitDiff::get_variable(config.only_nonzero_diffs, stats.added)
+                }),
+                "deleted" => stats.as_ref().and_then(|stats| {
+                    GitDiff::get_variable(config.only_nonzero_diffs, stats.deleted)
+                }),
+                "skipped" => skipped.then_some(Ok(config.skipped)),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -228,6 +239,38 @@ mod tests {
         repo_dir.close()
     }
 
+    #[test]
+    fn shows_skipped_in_large_repo() -> io::Result<()> {
+        let repo_dir = create_repo_with_commit()?;
+        let path = repo_dir.path();
+
+        write_file(path.join("the_file"), "Changed\n")?;
+        write_file(path.join("other_file"), "Added\n")?;
+        run_git_cmd(["add", "other_file"], Some(path), true)?;
+
+        let render = || {
+            ModuleRenderer::new("git_metrics")
+                .config(toml::toml! {
+                    [git_metrics]
+                    disabled = false
+                    max_index_entries = 1
+                })
+                .path(path)
+                .collect()
+        };
+
+        assert_eq!(Some("… ".to_string()), render());
+
+        write_file(path.join(".git").join("starship-allow"), "")?;
+        let expected = Some(format!(
+            "{} {} ",
+            Color::Green.bold().paint("+1"),
+            Color::Red.bold().paint("-3")
+        ));
+        assert_eq!(expected, render());
+        repo_dir.close()
+    }
+
     fn render_metrics(path: &Path) -> Option<String> {
         ModuleRenderer::new("git_metrics")
             .config(toml::toml! {
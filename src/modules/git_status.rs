@@ -6,10 +6,12 @@ use super::{Context, Module, ModuleConfig};
 use crate::configs::git_status::GitStatusConfig;
 use crate::formatter::StringFormatter;
 use crate::segment::Segment;
-use std::ffi::OsStr;
+use crate::utils::create_command;
+use std::ffi::{OsStr, OsString};
 use std::sync::Arc;
 
-const ALL_STATUS_FORMAT: &str = "$conflicted$stashed$deleted$renamed$modified$staged$untracked";
+const ALL_STATUS_FORMAT: &str =
+    "$conflicted$stashed$deleted$renamed$modified$staged$untracked$skipped";
 
 /// Creates a module with the Git branch in the current directory
 ///
@@ -33,7 +35,9 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let info = Arc::new(GitStatusInfo::load(context, config.clone()));
 
     //Return None if not in git repository
-    context.get_repo().ok()?;
+    let repo = context.get_repo().ok()?;
+    // Scanning the worktree is too slow in large repositories, so only the stashes are shown
+    let skipped = repo.exceeds_size(config.max_index_entries);
 
     if let Some(git_status) = git_status_wsl(context, &config) {
         if git_status.is_empty() {
@@ -56,9 +60,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             .map_variables_to_segments(|variable: &str| {
                 let info = Arc::clone(&info);
                 let segments = match variable {
+                    "skipped" => skipped
+                        .then(|| format_symbol(config.skipped, "git_status.skipped", context))
+                        .flatten(),
                     "stashed" => info.get_stashed().and_then(|count| {
                         format_count(config.stashed, "git_status.stashed", context, count)
                     }),
+                    _ if skipped => None,
                     "ahead_behind" => info.get_ahead_behind().and_then(|(ahead, behind)| {
                         let (ahead, behind) = (ahead?, behind?);
                         if ahead > 0 && behind > 0 {
@@ -199,9 +207,12 @@ fn get_repo_status(context: &Context, config: &GitStatusConfig) -> Option<RepoSt
         OsStr::new("-C"),
         context.current_dir.as_os_str(),
         OsStr::new("--no-optional-locks"),
-        OsStr::new("status"),
-        OsStr::new("--porcelain=2"),
     ];
+    let fsmonitor = fsmonitor_setting(context, config);
+    if let Some(fsmonitor) = &fsmonitor {
+        args.extend([OsStr::new("-c"), fsmonitor.as_os_str()]);
+    }
+    args.extend([OsStr::new("status"), OsStr::new("--porcelain=2")]);
 
     // for performance reasons, only pass flags if necessary...
     let has_ahead_behind = !config.ahead.is_empty() || !config.behind.is_empty();
@@ -235,6 +246,39 @@ fn get_repo_status(context: &Context, config: &GitStatusConfig) -> Option<RepoSt
     Some(repo_status)
 }
 
+/// The `core.fsmonitor` setting that lets `git status` skip scanning the worktree, when the
+/// monitor asked for in `fsmonitor` is available
+///
+/// Without one, git uses the `core.fsmonitor` of the repository, if any.
+fn fsmonitor_setting(context: &Context, config: &GitStatusConfig) -> Option<OsString> {
+    match config.fsmonitor {
+        "" => None,
+        // git falls back to scanning the worktree where its daemon isn't supported
+        "builtin" => Some("core.fsmonitor=true".into()),
+        "watchman" => {
+            let hook = context
+                .get_repo()
+                .ok()?
+                .path
+                .join("hooks")
+                .join("fsmonitor-watchman");
+            if !hook.is_file() || create_command("watchman").is_err() {
+                log::debug!("watchman or the hook at {hook:?} is missing, scanning the worktree");
+                return None;
+            }
+            let mut setting = OsString::from("core.fsmonitor=");
+            setting.push(hook);
+            Some(setting)
+        }
+        other => {
+            log::warn!(
+                "Unknown fsmonitor `{other}` for git_status, expected `builtin` or `watchman`"
+            );
+            None
+        }
+    }
+}
+
 fn get_stashed_count(context: &Context) -> Option<usize> {
     let stash_output = context.exec_cmd(
         "git",
@@ -364,7 +408,6 @@ fn format_symbol(format_str: &str, config_path: &str, context: &Context) -> Opti
 
 #[cfg(target_os = "linux")]
 fn git_status_wsl(context: &Context, conf: &GitStatusConfig) -> Option<String> {
-    use crate::utils::create_command;
     use nix::sys::utsname::uname;
     use std::env;
     use std::io::ErrorKind;
@@ -670,6 +713,52 @@ mod tests {
         repo_dir.close()
     }
 
+    #[test]
+    fn shows_untracked_file_with_fsmonitor() -> io::Result<()> {
+        for fsmonitor in ["builtin", "watchman"] {
+            let repo_dir = fixture_repo(FixtureProvider::Git)?;
+
+            create_untracked(repo_dir.path())?;
+
+            let actual = ModuleRenderer::new("git_status")
+                .config(toml::toml! {
+                    [git_status]
+                    fsmonitor = fsmonitor
+                })
+                .path(repo_dir.path())
+                .collect();
+            let expected = format_output("?");
+
+            assert_eq!(expected, actual);
+            repo_dir.close()?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn shows_skipped_in_large_repo() -> io::Result<()> {
+        let repo_dir = fixture_repo(FixtureProvider::Git)?;
+
+        create_stash(repo_dir.path())?;
+        create_untracked(repo_dir.path())?;
+
+        let render = || {
+            ModuleRenderer::new("git_status")
+                .config(toml::toml! {
+                    [git_status]
+                    max_index_entries = 1
+                })
+                .path(repo_dir.path())
+                .collect()
+        };
+
+        assert_eq!(format_output("$…"), render());
+
+        File::create(repo_dir.path().join(".git").join("starship-allow"))?;
+        assert_eq!(format_output("$?"), render());
+        repo_dir.close()
+    }
+
     #[test]
     fn shows_untracked_file_with_count() -> io::Result<()> {
         let repo_dir = fixture_repo(FixtureProvider::Git)?;
@@ -3,6 +3,9 @@ use super::{Context, Module, ModuleConfig};
 use crate::configs::go::GoConfig;
 use crate::formatter::StringFormatter;
 use crate::formatter::VersionFormatter;
+use crate::utils::{parse_pinned_version, satisfies_pinned_version};
+
+use once_cell::sync::Lazy;
 
 /// Creates a module with the current Go version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -11,14 +14,19 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_go_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_extensions(&config.detect_extensions)
         .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     if !is_go_project {
         return None;
     }
 
+    let installed_version = Lazy::new(|| get_go_version(context));
+    let pinned_version = Lazy::new(|| get_pinned_go_version(context));
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -26,23 +34,39 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
-                _ => None,
-            })
-            .map(|variable| match variable {
-                "version" => {
-                    let golang_version =
-                        parse_go_version(&context.exec_cmd("go", &["version"])?.stdout)?;
-
-                    VersionFormatter::format_module_version(
-                        module.get_name(),
-                        &golang_version,
-                        config.version_format,
-                    )
-                    .map(Ok)
+                "style" if config.mismatch_style.is_empty() => Some(Ok(config.style)),
+                "style" => {
+                    let is_mismatch = pinned_version.as_deref().map_or(false, |pinned| {
+                        installed_version.as_deref().map_or(false, |installed| {
+                            !satisfies_pinned_version(installed, pinned)
+                        })
+                    });
+                    Some(Ok(if is_mismatch {
+                        config.mismatch_style
+                    } else {
+                        config.style
+                    }))
                 }
                 _ => None,
             })
+            .map(|variable| {
+                let version = match variable {
+                    "workspace" => return get_go_workspace(context).map(Ok),
+                    "version" if config.use_pinned_version => {
+                        pinned_version.as_ref().or(installed_version.as_ref())
+                    }
+                    "version" | "installed" => installed_version.as_ref(),
+                    "pinned" => pinned_version.as_ref(),
+                    _ => return None,
+                };
+
+                VersionFormatter::format_module_version(
+                    module.get_name(),
+                    version?,
+                    config.version_format,
+                )
+                .map(Ok)
+            })
             .parse(None, Some(context))
     });
 
@@ -57,6 +81,43 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+fn get_go_version(context: &Context) -> Option<String> {
+    parse_go_version(&context.exec_cmd("go", &["version"])?.stdout)
+}
+
+/// Reads the version of the `toolchain` directive of `go.mod`, without running `go`
+fn get_pinned_go_version(context: &Context) -> Option<String> {
+    let go_mod = context.read_file_from_pwd("go.mod")?;
+    go_mod
+        .lines()
+        .find_map(|line| {
+            line.trim()
+                .strip_prefix("toolchain")?
+                .trim()
+                .strip_prefix("go")
+        })
+        .and_then(parse_pinned_version)
+        .map(str::to_owned)
+}
+
+/// Returns the name of the directory of the active `go.work`, found like `go env GOWORK` does
+fn get_go_workspace(context: &Context) -> Option<String> {
+    let work_file = match context
+        .get_env("GOWORK")
+        .filter(|gowork| !gowork.is_empty())
+    {
+        Some(gowork) if gowork == "off" => return None,
+        Some(gowork) => context.current_dir.join(gowork),
+        None => context
+            .current_dir
+            .ancestors()
+            .map(|dir| dir.join("go.work"))
+            .find(|work_file| work_file.is_file())?,
+    };
+    let workspace_dir = work_file.parent()?.file_name()?;
+    Some(workspace_dir.to_string_lossy().to_string())
+}
+
 fn parse_go_version(go_stdout: &str) -> Option<String> {
     // go version output looks like this:
     // go version go1.13.3 linux/amd64
@@ -78,7 +139,7 @@ fn parse_go_version(go_stdout: &str) -> Option<String> {
 mod tests {
     use super::*;
     use crate::test::ModuleRenderer;
-    use nu_ansi_term::Color;
+    use nu_ansi_term::{Color, Style};
     use std::fs::{self, File};
     use std::io;
 
@@ -203,4 +264,75 @@ mod tests {
         let input = "go version go1.12 darwin/amd64";
         assert_eq!(parse_go_version(input), Some("1.12".to_string()));
     }
+
+    #[test]
+    fn folder_with_go_mod_toolchain() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("go.mod"),
+            "module example.com/app\n\ngo 1.21\n\ntoolchain go1.21.3\n",
+        )?;
+
+        let render = |use_pinned_version: bool| {
+            ModuleRenderer::new("golang")
+                .path(dir.path())
+                .config(toml::toml! {
+                    [golang]
+                    format = "$version"
+                    use_pinned_version = use_pinned_version
+                })
+                .collect()
+        };
+        assert_eq!(Some("v1.21.3".to_string()), render(true));
+        assert_eq!(Some("v1.12.1".to_string()), render(false));
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_go_mod_toolchain_mismatch() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let render = |toolchain: &str| -> io::Result<Option<String>> {
+            fs::write(
+                dir.path().join("go.mod"),
+                format!("module example.com/app\n\ntoolchain go{}\n", toolchain),
+            )?;
+            Ok(ModuleRenderer::new("golang")
+                .path(dir.path())
+                .config(toml::toml! {
+                    [golang]
+                    format = "[$version]($style)"
+                    mismatch_style = "red"
+                })
+                .collect())
+        };
+
+        let expected = |style: Style| Some(format!("{}", style.paint("v1.12.1")));
+        assert_eq!(expected(Color::Red.normal()), render("1.21.3")?);
+        assert_eq!(expected(Color::Cyan.bold()), render("1.12")?);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_in_go_workspace() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let workspace_dir = dir.path().join("monorepo");
+        let module_dir = workspace_dir.join("service");
+        fs::create_dir_all(&module_dir)?;
+        fs::write(workspace_dir.join("go.work"), "go 1.21\n\nuse ./service\n")?;
+        fs::write(module_dir.join("go.mod"), "module example.com/service\n")?;
+
+        let render = |gowork: &str| {
+            ModuleRenderer::new("golang")
+                .path(&module_dir)
+                .env("GOWORK", gowork)
+                .config(toml::toml! {
+                    [golang]
+                    format = "$version( \\($workspace\\))"
+                })
+                .collect()
+        };
+        assert_eq!(Some("v1.12.1 (monorepo)".to_string()), render(""));
+        assert_eq!(Some("v1.12.1".to_string()), render("off"));
+        dir.close()
+    }
 }
@@ -0,0 +1,190 @@
+use systemstat::data::ByteSize;
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::gpu::GpuConfig;
+use crate::formatter::StringFormatter;
+use crate::utils::{cached_output, parse_refresh_interval};
+
+const NVIDIA_SMI_ARGS: [&str; 2] = [
+    "--query-gpu=name,utilization.gpu,memory.used,memory.total",
+    "--format=csv,noheader,nounits",
+];
+
+/// The status of a GPU, as reported by NVML or `nvidia-smi`
+#[derive(Debug, PartialEq)]
+struct Gpu {
+    name: String,
+    utilization: u64,
+    /// Used memory in MiB
+    memory_used: u64,
+    /// Total memory in MiB
+    memory_total: u64,
+}
+
+/// Creates a module with the utilization and memory usage of the NVIDIA GPUs
+///
+/// When there are several GPUs, their utilization is averaged and their memory is summed up.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("gpu");
+    let config = GpuConfig::try_load(module.config);
+
+    // As we default to disabled=true, we have to check here after loading our config module,
+    // before it was only checking against whatever is in the config starship.toml
+    if config.disabled {
+        return None;
+    }
+
+    let gpus = parse_nvidia_smi(&query_gpus_cached(context, &config)?);
+    if gpus.is_empty() {
+        return None;
+    }
+
+    let utilization = gpus.iter().map(|gpu| gpu.utilization).sum::<u64>() / gpus.len() as u64;
+    if (utilization as i64) < config.threshold {
+        return None;
+    }
+    let memory_used = gpus.iter().map(|gpu| gpu.memory_used).sum::<u64>();
+    let memory_total = gpus.iter().map(|gpu| gpu.memory_total).sum::<u64>();
+    let memory_percent = (100 * memory_used).checked_div(memory_total).unwrap_or(0);
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "name" => Some(Ok(gpus[0].name.clone())),
+                "count" => Some(Ok(gpus.len().to_string())),
+                "utilization" => Some(Ok(utilization.to_string())),
+                "memory_used" => Some(Ok(display_mib(memory_used))),
+                "memory_total" => Some(Ok(display_mib(memory_total))),
+                "memory_percent" => Some(Ok(memory_percent.to_string())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `gpu`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Queries the GPUs, reusing the result for `refresh_interval`
+///
+/// With the `nvml` feature, NVML is used directly, and `nvidia-smi`, which takes a while,
+/// is only run when NVML can't be loaded. Both give the same CSV lines.
+fn query_gpus_cached(context: &Context, config: &GpuConfig) -> Option<String> {
+    let refresh_interval = parse_refresh_interval(config.refresh_interval).unwrap_or_default();
+    cached_output(context, "gpu", refresh_interval, || {
+        // Tests mock `nvidia-smi`, so they shouldn't see the GPUs of the machine
+        #[cfg(feature = "nvml")]
+        if !cfg!(test) {
+            if let Some(output) = super::utils::nvml::query() {
+                return Some(output);
+            }
+        }
+        Some(context.exec_cmd("nvidia-smi", &NVIDIA_SMI_ARGS)?.stdout)
+    })
+}
+
+/// Parses the CSV lines of `nvidia-smi`, one per GPU, e.g. `NVIDIA A100, 87, 30210, 40960`
+fn parse_nvidia_smi(output: &str) -> Vec<Gpu> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(',').map(str::trim);
+            let name = fields.next()?.to_string();
+            let mut number = || fields.next()?.parse::<u64>().ok();
+            Some(Gpu {
+                name,
+                utilization: number()?,
+                memory_used: number()?,
+                memory_total: number()?,
+            })
+        })
+        .collect()
+}
+
+fn display_mib(mib: u64) -> String {
+    let mut display = ByteSize::mib(mib).to_string_as(true);
+    display.retain(|c| c != ' ');
+    display
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
+    use std::io;
+
+    const NVIDIA_SMI: &str = "nvidia-smi --query-gpu=name,utilization.gpu,memory.used,memory.total --format=csv,noheader,nounits";
+
+    fn nvidia_smi_output(stdout: &str) -> Option<CommandOutput> {
+        Some(CommandOutput {
+            stdout: stdout.to_string(),
+            stderr: String::default(),
+        })
+    }
+
+    #[test]
+    fn test_parse_nvidia_smi() {
+        let output =
+            "NVIDIA A100-SXM4-40GB, 87, 30210, 40960\nNVIDIA A100-SXM4-40GB, [N/A], 0, 40960\n";
+        assert_eq!(
+            parse_nvidia_smi(output),
+            vec![Gpu {
+                name: "NVIDIA A100-SXM4-40GB".to_string(),
+                utilization: 87,
+                memory_used: 30210,
+                memory_total: 40960,
+            }]
+        );
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("gpu")
+            .cmd(
+                NVIDIA_SMI,
+                nvidia_smi_output("NVIDIA T4, 50, 1024, 15360\n"),
+            )
+            .collect();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn two_gpus() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let render = |threshold: i64| {
+            ModuleRenderer::new("gpu")
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .cmd(
+                    NVIDIA_SMI,
+                    nvidia_smi_output("NVIDIA T4, 90, 2048, 16384\nNVIDIA T4, 10, 6144, 16384\n"),
+                )
+                .config(toml::toml! {
+                    [gpu]
+                    disabled = false
+                    threshold = threshold
+                    format = "$count $utilization% $memory_used/$memory_total $memory_percent%"
+                })
+                .collect()
+        };
+        assert_eq!(Some("2 50% 8.0GiB/32.0GiB 25%".to_string()), render(0));
+        assert_eq!(None, render(75));
+        cache_dir.close()
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use super::{Context, Module, ModuleConfig};
 
 use crate::configs::guix_shell::GuixShellConfig;
@@ -6,16 +8,12 @@ use crate::formatter::StringFormatter;
 /// Creates a module showing if inside a guix-shell
 ///
 /// The module will use the `$GUIX_ENVIRONMENT` environment variable to determine if it's
-/// inside a guix-shell and the name of it.
+/// inside a guix-shell (or the older guix environment) and the name of its profile.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("guix_shell");
     let config: GuixShellConfig = GuixShellConfig::try_load(module.config);
 
-    let is_guix_shell = context.get_env("GUIX_ENVIRONMENT").is_some();
-
-    if !is_guix_shell {
-        return None;
-    };
+    let guix_environment = context.get_env("GUIX_ENVIRONMENT")?;
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -27,6 +25,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "style" => Some(Ok(config.style)),
                 _ => None,
             })
+            .map(|variable| match variable {
+                "name" => get_profile_name(&guix_environment).map(Ok),
+                _ => None,
+            })
             .parse(None, Some(context))
     });
 
@@ -41,6 +43,21 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Gets the name of the profile of the environment
+///
+/// Profiles given with `--profile` keep their own name, while the profiles guix builds from
+/// packages or manifests live in the store as `<hash>-profile` and have no name.
+fn get_profile_name(guix_environment: &str) -> Option<String> {
+    let file_name = Path::new(guix_environment).file_name()?.to_str()?;
+    let name = match file_name.split_once('-') {
+        Some((hash, name)) if hash.len() == 32 && guix_environment.starts_with("/gnu/store/") => {
+            name
+        }
+        _ => file_name,
+    };
+    (name != "profile").then(|| name.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
@@ -66,4 +83,33 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn env_variables_with_named_profile() {
+        let actual = ModuleRenderer::new("guix_shell")
+            .env(
+                "GUIX_ENVIRONMENT",
+                "/home/user/.guix-extra-profiles/emacs/emacs",
+            )
+            .collect();
+        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐃 (emacs)")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn env_variables_with_store_profile() {
+        let actual = ModuleRenderer::new("guix_shell")
+            .env(
+                "GUIX_ENVIRONMENT",
+                "/gnu/store/7vmfs4khf4fllsh83kqkxssbw3437qsh-dev-tools",
+            )
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐃 (dev-tools)")
+        ));
+
+        assert_eq!(expected, actual);
+    }
 }
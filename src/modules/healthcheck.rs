@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+use rayon::prelude::*;
+use sha1::{Digest, Sha1};
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::healthcheck::HealthcheckConfig;
+use crate::formatter::StringFormatter;
+use crate::utils::{cached_output, encode_to_hex, parse_refresh_interval};
+
+#[cfg(windows)]
+const NULL_DEVICE: &str = "NUL";
+#[cfg(not(windows))]
+const NULL_DEVICE: &str = "/dev/null";
+
+/// Creates a module with whether each of the configured HTTP services is up
+///
+/// The services are requested with `curl` in parallel, and their status is cached in the
+/// starship cache directory for `refresh_interval`, so most prompts don't make any request.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("healthcheck");
+    let config = HealthcheckConfig::try_load(module.config);
+
+    if config.disabled || config.urls.is_empty() {
+        return None;
+    }
+
+    let refresh_interval = parse_refresh_interval(config.refresh_interval).unwrap_or_default();
+    let statuses = config
+        .urls
+        .par_iter()
+        .map(|url| is_up_cached(context, url, config.timeout, refresh_interval))
+        .collect::<Vec<_>>();
+    let up = statuses.iter().filter(|is_up| **is_up).count();
+    let down = statuses.len() - up;
+    let status = statuses
+        .iter()
+        .map(|is_up| {
+            if *is_up {
+                config.up_symbol
+            } else {
+                config.down_symbol
+            }
+        })
+        .collect::<String>();
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" if down > 0 => Some(Ok(config.down_style)),
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "status" => Some(Ok(status.clone())),
+                "up" => Some(Ok(up.to_string())),
+                "down" => Some(Ok(down.to_string())),
+                "total" => Some(Ok(statuses.len().to_string())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `healthcheck`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Whether `url` is up, reusing the result of the last check for `refresh_interval`
+fn is_up_cached(context: &Context, url: &str, timeout: u64, refresh_interval: Duration) -> bool {
+    let mut hasher = Sha1::new();
+    hasher.update(url.as_bytes());
+    let key = format!("healthcheck/{}", encode_to_hex(&hasher.finalize()));
+
+    let status = cached_output(context, &key, refresh_interval, || {
+        Some(
+            if is_up(context, url, timeout) {
+                "up"
+            } else {
+                "down"
+            }
+            .to_string(),
+        )
+    });
+    status.as_deref() == Some("up")
+}
+
+/// Requests `url`, which is up when it answers with a status below 400 within `timeout` ms
+fn is_up(context: &Context, url: &str, timeout: u64) -> bool {
+    let max_time = format!("{:.3}", timeout as f64 / 1000.0);
+    context
+        .exec_cmd_with_timeout(
+            "curl",
+            &[
+                "--silent",
+                "--fail",
+                "--output",
+                NULL_DEVICE,
+                "--max-time",
+                &max_time,
+                // Even a URL starting with `-` isn't taken as an option
+                "--url",
+                url,
+            ],
+            // Leave curl some time to report the timeout itself
+            Duration::from_millis(timeout + 100),
+        )
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
+    use nu_ansi_term::Color;
+    use std::io;
+
+    fn ok() -> CommandOutput {
+        CommandOutput {
+            stdout: String::default(),
+            stderr: String::default(),
+        }
+    }
+
+    fn curl(url: &str) -> String {
+        format!("curl --silent --fail --output {NULL_DEVICE} --max-time 1.000 --url {url}")
+    }
+
+    #[test]
+    fn no_urls() {
+        let actual = ModuleRenderer::new("healthcheck")
+            .config(toml::toml! {
+                [healthcheck]
+                disabled = false
+            })
+            .collect();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn some_services_down() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let up = curl("https://api.example.com/health");
+        let down = curl("https://auth.example.com/health");
+
+        let render = |down_output: Option<CommandOutput>| {
+            ModuleRenderer::new("healthcheck")
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .cmd(&up, Some(ok()))
+                .cmd(&down, down_output)
+                .config(toml::toml! {
+                    [healthcheck]
+                    disabled = false
+                    urls = ["https://api.example.com/health", "https://auth.example.com/health"]
+                })
+                .collect()
+        };
+
+        let expected = Some(format!("{} ", Color::Red.bold().paint("♥ ●○")));
+        assert_eq!(expected, render(None));
+        // The status is cached, so the service stays down until the next check
+        assert_eq!(expected, render(Some(ok())));
+        cache_dir.close()
+    }
+
+    #[test]
+    fn all_services_up() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let actual = ModuleRenderer::new("healthcheck")
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .cmd(&curl("http://localhost:8080"), Some(ok()))
+            .config(toml::toml! {
+                [healthcheck]
+                disabled = false
+                format = "$up/$total"
+                urls = ["http://localhost:8080"]
+                refresh_interval = "0s"
+            })
+            .collect();
+        assert_eq!(Some("1/1".to_string()), actual);
+        cache_dir.close()
+    }
+}
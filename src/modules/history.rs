@@ -0,0 +1,113 @@
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::history::HistoryConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module with the shell history number of the next command
+///
+/// The history number and the number of commands run in the session are
+/// provided by the init scripts in `STARSHIP_HISTORY_INDEX` and `STARSHIP_CMD_COUNT`.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("history");
+    let config = HistoryConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let read_number = |var| {
+        context
+            .get_env(var)
+            .filter(|value| value.trim().parse::<u64>().is_ok())
+    };
+    let index = read_number("STARSHIP_HISTORY_INDEX");
+    let count = read_number("STARSHIP_CMD_COUNT");
+
+    if index.is_none() && count.is_none() {
+        return None;
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "index" => index.as_deref().map(str::trim).map(Ok),
+                "count" => count.as_deref().map(str::trim).map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `history`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("history")
+            .env("STARSHIP_HISTORY_INDEX", "512")
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn history_index() {
+        let actual = ModuleRenderer::new("history")
+            .config(toml::toml! {
+                [history]
+                disabled = false
+            })
+            .env("STARSHIP_HISTORY_INDEX", "512")
+            .collect();
+        let expected = Some(format!("{} ", Color::White.bold().dimmed().paint("!512")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn command_count() {
+        let actual = ModuleRenderer::new("history")
+            .config(toml::toml! {
+                [history]
+                disabled = false
+                format = "$count cmds"
+            })
+            .env("STARSHIP_CMD_COUNT", "7")
+            .collect();
+
+        assert_eq!(Some("7 cmds".to_string()), actual);
+    }
+
+    #[test]
+    fn not_provided_by_shell() {
+        let actual = ModuleRenderer::new("history")
+            .config(toml::toml! {
+                [history]
+                disabled = false
+            })
+            .env("STARSHIP_HISTORY_INDEX", "")
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+}
@@ -1,5 +1,6 @@
 use super::{Context, Module};
 use std::ffi::OsString;
+use std::net::IpAddr;
 
 use crate::config::ModuleConfig;
 use crate::configs::hostname::HostnameConfig;
@@ -19,6 +20,16 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
+    let ssh_info = ssh_connection
+        .as_deref()
+        .and_then(SshInfo::from_connection)
+        .or_else(|| {
+            context
+                .get_env("SSH_CLIENT")
+                .as_deref()
+                .and_then(SshInfo::from_client)
+        });
+
     let os_hostname: OsString = gethostname::gethostname();
 
     let host = match os_hostname.into_string() {
@@ -51,6 +62,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                         None
                     }
                 }
+                "ssh_loopback_symbol" => match &ssh_info {
+                    Some(info) if info.is_loopback() => Some(config.ssh_loopback_symbol),
+                    _ => None,
+                },
                 _ => None,
             })
             .map_style(|variable| match variable {
@@ -59,6 +74,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "hostname" => Some(Ok(host)),
+                "ssh_client" => ssh_info.as_ref().map(|info| Ok(info.client_ip.as_str())),
+                "ssh_port" => ssh_info.as_ref().map(|info| Ok(info.port.as_str())),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -75,6 +92,47 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Details about the current SSH session, as reported by `sshd`
+struct SshInfo {
+    client_ip: String,
+    port: String,
+}
+
+impl SshInfo {
+    /// Parses `$SSH_CONNECTION`: `<client ip> <client port> <server ip> <server port>`
+    fn from_connection(value: &str) -> Option<Self> {
+        let mut parts = value.split_whitespace();
+        let client_ip = parts.next()?;
+        let port = parts.nth(2)?;
+        Some(Self::new(client_ip, port))
+    }
+
+    /// Parses `$SSH_CLIENT`: `<client ip> <client port> <server port>`
+    fn from_client(value: &str) -> Option<Self> {
+        let mut parts = value.split_whitespace();
+        let client_ip = parts.next()?;
+        let port = parts.nth(1)?;
+        Some(Self::new(client_ip, port))
+    }
+
+    fn new(client_ip: &str, port: &str) -> Self {
+        Self {
+            client_ip: client_ip.to_string(),
+            port: port.to_string(),
+        }
+    }
+
+    /// Whether the session was opened from the machine itself, e.g. through a port forward
+    ///
+    /// Sessions opened through a jump host come from the address of the jump host instead.
+    fn is_loopback(&self) -> bool {
+        self.client_ip
+            .parse::<IpAddr>()
+            .map(|ip| ip.is_loopback())
+            .unwrap_or(false)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
@@ -189,6 +247,65 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn ssh_port_and_client() {
+        let hostname = get_hostname!();
+        let actual = ModuleRenderer::new("hostname")
+            .config(toml::toml! {
+                [hostname]
+                trim_at = ""
+                format = "$hostname:$ssh_port from $ssh_client"
+            })
+            .env("SSH_CONNECTION", "192.168.1.10 51234 192.168.1.2 2222")
+            .collect();
+        let expected = Some(format!("{hostname}:2222 from 192.168.1.10"));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn ssh_port_from_ssh_client() {
+        let actual = ModuleRenderer::new("hostname")
+            .config(toml::toml! {
+                [hostname]
+                ssh_only = false
+                format = "$ssh_port"
+            })
+            .env("SSH_CLIENT", "10.0.0.1 51234 22")
+            .collect();
+        let expected = Some("22".to_string());
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn ssh_not_loopback() {
+        let actual = ModuleRenderer::new("hostname")
+            .config(toml::toml! {
+                [hostname]
+                format = "${ssh_loopback_symbol}host"
+            })
+            .env("SSH_CONNECTION", "192.168.1.10 51234 192.168.1.2 22")
+            .collect();
+        let expected = Some("host".to_string());
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn ssh_loopback() {
+        let actual = ModuleRenderer::new("hostname")
+            .config(toml::toml! {
+                [hostname]
+                format = "${ssh_loopback_symbol}host"
+            })
+            .env("SSH_CONNECTION", "::1 51234 ::1 22")
+            .collect();
+        let expected = Some("⇢ host".to_string());
+
+        assert_eq!(expected, actual);
+    }
+
     fn style() -> Style {
         Color::Green.bold().dimmed()
     }
@@ -2,6 +2,7 @@ use super::{Context, Module, ModuleConfig};
 use crate::configs::java::JavaConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
 use crate::utils::get_command_string_output;
+use once_cell::sync::Lazy;
 use std::path::PathBuf;
 
 use regex::Regex;
@@ -15,14 +16,18 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_java_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_extensions(&config.detect_extensions)
         .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     if !is_java_project {
         return None;
     }
 
+    let jdk = Lazy::new(|| get_jdk(context));
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -35,14 +40,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "version" => {
-                    let java_version = get_java_version(context)?;
+                    let java_version = &jdk.as_ref()?.version;
                     VersionFormatter::format_module_version(
                         module.get_name(),
-                        &java_version,
+                        java_version,
                         config.version_format,
                     )
                     .map(Ok)
                 }
+                "vendor" => jdk.as_ref()?.vendor.clone().map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -59,6 +65,105 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// The JDK selected for the current directory
+#[derive(Debug, PartialEq)]
+struct Jdk {
+    version: String,
+    vendor: Option<String>,
+}
+
+/// Resolves the JDK like the toolchain managers do, in order of precedence: the `java` candidate
+/// of SDKMAN's `.sdkmanrc`, jenv's `.java-version`, the `release` file of `$JAVA_HOME`, and only
+/// then the output of `java`
+fn get_jdk(context: &Context) -> Option<Jdk> {
+    let from_sdkmanrc = || {
+        let sdkmanrc = context.read_file_from_pwd(".sdkmanrc")?;
+        sdkmanrc.lines().find_map(|line| {
+            let (candidate, identifier) = line.split_once('=')?;
+            (candidate.trim() == "java").then(|| parse_sdkman_identifier(identifier.trim()))?
+        })
+    };
+    let from_java_version = || parse_jenv_version(&context.read_file_from_pwd(".java-version")?);
+    let from_java_home = || {
+        let java_home = PathBuf::from(context.get_env("JAVA_HOME")?);
+        parse_release_file(&std::fs::read_to_string(java_home.join("release")).ok()?)
+    };
+
+    from_sdkmanrc()
+        .or_else(from_java_version)
+        .or_else(from_java_home)
+        .or_else(|| {
+            get_java_version(context).map(|version| Jdk {
+                version,
+                vendor: None,
+            })
+        })
+}
+
+/// Parses the identifiers of SDKMAN, e.g. `17.0.8-tem`
+fn parse_sdkman_identifier(identifier: &str) -> Option<Jdk> {
+    let (version, vendor) = identifier.rsplit_once('-')?;
+    let vendor = match vendor {
+        "amzn" => "Corretto",
+        "graal" | "graalce" => "GraalVM",
+        "librca" => "Liberica",
+        "ms" => "Microsoft",
+        "open" => "OpenJDK",
+        "oracle" => "Oracle",
+        "sapmchn" => "SapMachine",
+        "sem" => "Semeru",
+        "tem" => "Temurin",
+        "zulu" => "Zulu",
+        vendor => vendor,
+    };
+    Some(Jdk {
+        version: parse_numeric_version(version)?,
+        vendor: Some(vendor.to_string()),
+    })
+}
+
+/// Parses the versions jenv writes, e.g. `17`, `17.0` or `temurin64-17.0.8`
+fn parse_jenv_version(contents: &str) -> Option<Jdk> {
+    let name = contents.lines().next()?.trim();
+    match name.rsplit_once('-') {
+        Some((vendor, version)) if vendor.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+            Some(Jdk {
+                version: parse_numeric_version(version)?,
+                vendor: Some(
+                    vendor
+                        .trim_end_matches(|c: char| c.is_ascii_digit())
+                        .to_string(),
+                )
+                .filter(|vendor| !vendor.is_empty()),
+            })
+        }
+        _ => Some(Jdk {
+            version: parse_numeric_version(name)?,
+            vendor: None,
+        }),
+    }
+}
+
+/// Parses the `release` file shipped at the root of JDKs
+fn parse_release_file(contents: &str) -> Option<Jdk> {
+    let value = |key: &str| {
+        contents.lines().find_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            (name.trim() == key).then(|| value.trim().trim_matches('"').to_string())
+        })
+    };
+    Some(Jdk {
+        version: parse_numeric_version(&value("JAVA_VERSION")?)?,
+        vendor: value("IMPLEMENTOR").filter(|vendor| !vendor.is_empty()),
+    })
+}
+
+/// Keeps the leading `major.minor.patch` of a version, e.g. `1.8.0` of `1.8.0_292`
+fn parse_numeric_version(version: &str) -> Option<String> {
+    let re = Regex::new(r"^\d+(?:\.\d+){0,2}").ok()?;
+    Some(re.find(version)?.as_str().to_string())
+}
+
 fn get_java_version(context: &Context) -> Option<String> {
     let java_command = context
         .get_env("JAVA_HOME")
@@ -91,7 +196,7 @@ mod tests {
     use super::*;
     use crate::{test::ModuleRenderer, utils::CommandOutput};
     use nu_ansi_term::Color;
-    use std::fs::File;
+    use std::fs::{self, File};
     use std::io;
 
     #[test]
@@ -295,4 +400,75 @@ mod tests {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn test_parse_toolchain_manager_versions() {
+        let jdk = |version: &str, vendor: Option<&str>| {
+            Some(Jdk {
+                version: version.to_string(),
+                vendor: vendor.map(str::to_string),
+            })
+        };
+        assert_eq!(
+            parse_sdkman_identifier("17.0.8-tem"),
+            jdk("17.0.8", Some("Temurin"))
+        );
+        assert_eq!(
+            parse_sdkman_identifier("21-custom"),
+            jdk("21", Some("custom"))
+        );
+        assert_eq!(parse_jenv_version("17\n"), jdk("17", None));
+        assert_eq!(
+            parse_jenv_version("oracle64-1.8.0.292"),
+            jdk("1.8.0", Some("oracle"))
+        );
+        assert_eq!(parse_jenv_version("system"), None);
+        assert_eq!(
+            parse_release_file("IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"21.0.1\"\n"),
+            jdk("21.0.1", Some("Eclipse Adoptium"))
+        );
+    }
+
+    #[test]
+    fn folder_with_sdkmanrc_and_java_version_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join(".java-version"), "zulu64-11.0.12\n")?;
+        let render = || {
+            ModuleRenderer::new("java")
+                .path(dir.path())
+                .config(toml::toml! {
+                    [java]
+                    format = "$version $vendor"
+                })
+                .collect()
+        };
+        assert_eq!(render(), Some("v11.0.12 zulu".to_string()));
+
+        fs::write(dir.path().join(".sdkmanrc"), "# sdk env\njava=17.0.8-tem\n")?;
+        assert_eq!(render(), Some("v17.0.8 Temurin".to_string()));
+        dir.close()
+    }
+
+    #[test]
+    fn test_java_home_release_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("Main.java"))?.sync_all()?;
+        let java_home = tempfile::tempdir()?;
+        fs::write(
+            java_home.path().join("release"),
+            "IMPLEMENTOR=\"Amazon.com Inc.\"\nJAVA_VERSION=\"21.0.2\"\n",
+        )?;
+
+        let actual = ModuleRenderer::new("java")
+            .env("JAVA_HOME", java_home.path().to_str().unwrap())
+            .path(dir.path())
+            .config(toml::toml! {
+                [java]
+                format = "$version $vendor"
+            })
+            .collect();
+        assert_eq!(actual, Some("v21.0.2 Amazon.com Inc.".to_string()));
+        java_home.close()?;
+        dir.close()
+    }
 }
@@ -34,6 +34,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     let props = &context.properties;
     let num_of_jobs = props.jobs;
+    let num_of_stopped = props.stopped_jobs.clamp(0, num_of_jobs.max(0));
+    let num_of_running = num_of_jobs - num_of_stopped;
 
     if num_of_jobs == 0
         && config.threshold > 0
@@ -81,6 +83,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "number" => Some(Ok(module_number.clone())),
+                "running" => (num_of_running > 0).then(|| Ok(num_of_running.to_string())),
+                "stopped" => (num_of_stopped > 0).then(|| Ok(num_of_stopped.to_string())),
+                "last_job" => props
+                    .last_job
+                    .as_deref()
+                    .filter(|job| !job.is_empty())
+                    .map(|job| Ok(job.to_string())),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -330,4 +339,79 @@ mod test {
         let expected = Some(format!("{} ", Color::Blue.bold().paint("✦1")));
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn running_and_stopped() {
+        let actual = ModuleRenderer::new("jobs")
+            .config(toml::toml! {
+                [jobs]
+                format = "$symbol( $running running)( $stopped stopped)"
+            })
+            .jobs(3)
+            .stopped_jobs(1)
+            .collect();
+
+        let expected = Some("✦ 2 running 1 stopped".to_string());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn only_stopped() {
+        let actual = ModuleRenderer::new("jobs")
+            .config(toml::toml! {
+                [jobs]
+                format = "$symbol( $running running)( $stopped stopped)"
+            })
+            .jobs(1)
+            .stopped_jobs(1)
+            .collect();
+
+        let expected = Some("✦ 1 stopped".to_string());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn stopped_more_than_jobs() {
+        let actual = ModuleRenderer::new("jobs")
+            .config(toml::toml! {
+                [jobs]
+                format = "$symbol( $running running)( $stopped stopped)"
+            })
+            .jobs(1)
+            .stopped_jobs(5)
+            .collect();
+
+        let expected = Some("✦ 1 stopped".to_string());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn last_job() {
+        let actual = ModuleRenderer::new("jobs")
+            .config(toml::toml! {
+                [jobs]
+                format = "$symbol( \\($last_job\\))"
+            })
+            .jobs(1)
+            .last_job("vim README.md")
+            .collect();
+
+        let expected = Some("✦ (vim README.md)".to_string());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn last_job_empty() {
+        let actual = ModuleRenderer::new("jobs")
+            .config(toml::toml! {
+                [jobs]
+                format = "$symbol( \\($last_job\\))"
+            })
+            .jobs(1)
+            .last_job("")
+            .collect();
+
+        let expected = Some("✦".to_string());
+        assert_eq!(expected, actual);
+    }
 }
@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::jupyter::JupyterConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module showing that the shell runs inside Jupyter
+///
+/// Jupyter sets `$JPY_PARENT_PID` for its terminals and kernels, and the shells that kernels
+/// start (e.g. for `!ls` in a notebook) also get `$JPY_SESSION_NAME` with the notebook path.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let get_env = |var: &str| {
+        context
+            .get_env(var)
+            .filter(|value| !value.trim().is_empty())
+    };
+
+    get_env("JPY_PARENT_PID")?;
+    let session = get_env("JPY_SESSION_NAME");
+    let kind = if session.is_some() {
+        "kernel"
+    } else {
+        "terminal"
+    };
+
+    let mut module = context.new_module("jupyter");
+    let config = JupyterConfig::try_load(module.config);
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "kind" => Some(Ok(kind.to_string())),
+                "session" => session.as_deref().and_then(file_name).map(Ok),
+                "env" => get_env("VIRTUAL_ENV")
+                    .as_deref()
+                    .and_then(file_name)
+                    .or_else(|| get_env("CONDA_DEFAULT_ENV"))
+                    .map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `jupyter`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn file_name(path: &str) -> Option<String> {
+    Some(Path::new(path).file_name()?.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+
+    #[test]
+    fn outside_jupyter() {
+        let actual = ModuleRenderer::new("jupyter")
+            .env("VIRTUAL_ENV", "/home/user/.venvs/analysis")
+            .collect();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn jupyter_terminal() {
+        let actual = ModuleRenderer::new("jupyter")
+            .env("JPY_PARENT_PID", "4242")
+            .env("VIRTUAL_ENV", "/home/user/.venvs/analysis")
+            .collect();
+        let expected = Some(format!(
+            "in {} ",
+            Color::Fixed(208).bold().paint("🪐 terminal (analysis)")
+        ));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn jupyter_kernel_subshell() {
+        let actual = ModuleRenderer::new("jupyter")
+            .env("JPY_PARENT_PID", "4242")
+            .env("JPY_SESSION_NAME", "/home/user/notebooks/report.ipynb")
+            .env("CONDA_DEFAULT_ENV", "ml")
+            .config(toml::toml! {
+                [jupyter]
+                format = "$kind $session $env"
+            })
+            .collect();
+        assert_eq!(Some("kernel report.ipynb ml".to_string()), actual);
+    }
+}
@@ -0,0 +1,335 @@
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::license::LicenseConfig;
+use crate::formatter::StringFormatter;
+
+use serde_json as json;
+
+/// The files that name the license of a project in its metadata, and mark a project that
+/// can be missing one
+const METADATA_FILES: &[&str] = &["Cargo.toml", "package.json", "pyproject.toml"];
+
+/// Creates a module with the license of the project in the current directory
+///
+/// The license is read from the package metadata, or recognized from the text of the license
+/// files. Projects without a license, or with a denied one, are shown in `warning_style`.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("license");
+    let config = LicenseConfig::try_load(module.config);
+
+    // As we default to disabled=true, we have to check here after loading our config module,
+    // before it was only checking against whatever is in the config starship.toml
+    if config.disabled {
+        return None;
+    }
+
+    let (license, style) = match get_license(context) {
+        Some(license) if is_denied(&license, &config.denied) => (license, config.warning_style),
+        Some(license) => (license, config.style),
+        None if !config.missing.is_empty() && is_project(context) => {
+            (config.missing.to_string(), config.warning_style)
+        }
+        None => return None,
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "license" => Some(Ok(&license)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `license`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn get_license(context: &Context) -> Option<String> {
+    get_cargo_license(context)
+        .or_else(|| get_node_license(context))
+        .or_else(|| get_pyproject_license(context))
+        .or_else(|| get_license_files(context))
+}
+
+fn get_cargo_license(context: &Context) -> Option<String> {
+    let cargo_toml: toml::Value =
+        toml::from_str(&context.read_file_from_pwd("Cargo.toml")?).ok()?;
+    let license = cargo_toml.get("package")?.get("license")?;
+    // The root package of a workspace can inherit it with `license.workspace = true`
+    let license = license.as_str().or_else(|| {
+        cargo_toml
+            .get("workspace")?
+            .get("package")?
+            .get("license")?
+            .as_str()
+    })?;
+    Some(license.to_string())
+}
+
+fn get_node_license(context: &Context) -> Option<String> {
+    let package_json: json::Value =
+        json::from_str(&context.read_file_from_pwd("package.json")?).ok()?;
+    let license = package_json.get("license")?;
+    // The deprecated `{ "type": "MIT" }` form is still found in older packages
+    let license = license.as_str().or_else(|| license.get("type")?.as_str())?;
+    Some(license.to_string())
+}
+
+fn get_pyproject_license(context: &Context) -> Option<String> {
+    let pyproject: toml::Value =
+        toml::from_str(&context.read_file_from_pwd("pyproject.toml")?).ok()?;
+    let license = pyproject
+        .get("project")
+        .and_then(|project| project.get("license"))
+        .or_else(|| pyproject.get("tool")?.get("poetry")?.get("license"))?;
+    // PEP 621 also allows `{ text = "MIT" }`, or `{ file = "LICENSE" }` which is left to the
+    // license files
+    let license = license.as_str().or_else(|| license.get("text")?.as_str())?;
+    Some(license.to_string())
+}
+
+/// The licenses of files like `LICENSE` or `COPYING`, joined as alternatives when a project
+/// has several, like `LICENSE-MIT` and `LICENSE-APACHE`
+fn get_license_files(context: &Context) -> Option<String> {
+    let mut files: Vec<_> = context
+        .dir_contents()
+        .ok()?
+        .files()
+        .filter_map(|file| file.to_str())
+        .filter(|file| {
+            let name = file.to_uppercase();
+            ["LICENSE", "LICENCE", "COPYING", "UNLICENSE"]
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        })
+        .collect();
+    files.sort_unstable();
+
+    let mut licenses = Vec::new();
+    for file in files {
+        let license = context
+            .read_file_from_pwd(file)
+            .and_then(|text| identify_license(&text))
+            .unwrap_or("unknown");
+        if !licenses.contains(&license) {
+            licenses.push(license);
+        }
+    }
+    (!licenses.is_empty()).then(|| licenses.join(" OR "))
+}
+
+/// The SPDX identifier of a license text, recognized by phrases only it contains
+fn identify_license(text: &str) -> Option<&'static str> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let license = if text.contains("GNU AFFERO GENERAL PUBLIC LICENSE") {
+        "AGPL-3.0"
+    } else if text.contains("GNU LESSER GENERAL PUBLIC LICENSE") {
+        if text.contains("Version 2.1") {
+            "LGPL-2.1"
+        } else {
+            "LGPL-3.0"
+        }
+    } else if text.contains("GNU GENERAL PUBLIC LICENSE") {
+        if text.contains("Version 2,") {
+            "GPL-2.0"
+        } else {
+            "GPL-3.0"
+        }
+    } else if text.contains("Apache License") && text.contains("Version 2.0") {
+        "Apache-2.0"
+    } else if text.contains("Mozilla Public License Version 2.0") {
+        "MPL-2.0"
+    } else if text.contains("Boost Software License") {
+        "BSL-1.0"
+    } else if text.contains("free and unencumbered software released into the public domain") {
+        "Unlicense"
+    } else if text.contains("Permission is hereby granted, free of charge") {
+        "MIT"
+    } else if text.contains("Permission to use, copy, modify, and/or distribute") {
+        "ISC"
+    } else if text.contains("Redistribution and use in source and binary forms") {
+        if text.contains("Neither the name") {
+            "BSD-3-Clause"
+        } else {
+            "BSD-2-Clause"
+        }
+    } else {
+        return None;
+    };
+    Some(license)
+}
+
+/// Whether one of the licenses of an SPDX expression starts with a denied identifier, so that
+/// `AGPL` denies `AGPL-3.0-only` as well as `MIT OR AGPL-3.0`
+fn is_denied(license: &str, denied: &[&str]) -> bool {
+    license
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == '/')
+        .filter(|id| !id.is_empty() && !matches!(*id, "AND" | "OR" | "WITH"))
+        .any(|id| {
+            let id = id.to_lowercase();
+            denied
+                .iter()
+                .any(|denied| id.starts_with(&denied.to_lowercase()))
+        })
+}
+
+fn is_project(context: &Context) -> bool {
+    context.try_begin_scan().map_or(false, |scan| {
+        scan.set_files(METADATA_FILES)
+            .set_folders(&[".git"])
+            .is_match()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+
+    const MIT: &str = "MIT License\n\nPermission is hereby granted, free of charge, to any\n\
+                       person obtaining a copy of this software";
+
+    #[test]
+    fn folder_without_license() {
+        let actual = ModuleRenderer::new("license")
+            .with_project_file("README.md", "")
+            .config(toml::toml! {
+                [license]
+                disabled = false
+                missing = "no license"
+            })
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn license_from_cargo_toml() {
+        let actual = ModuleRenderer::new("license")
+            .with_project_file(
+                "Cargo.toml",
+                "[package]\nname = \"starship\"\nlicense = \"ISC\"\n",
+            )
+            .with_project_file("LICENSE", MIT)
+            .config(toml::toml! {
+                [license]
+                disabled = false
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Blue.bold().paint("⚖️ ISC")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn license_from_package_json() {
+        let actual = ModuleRenderer::new("license")
+            .with_project_file("package.json", r#"{ "license": { "type": "MIT" } }"#)
+            .config(toml::toml! {
+                [license]
+                disabled = false
+                format = "$license"
+            })
+            .collect();
+
+        assert_eq!(Some("MIT".to_string()), actual);
+    }
+
+    #[test]
+    fn license_from_pyproject() {
+        let actual = ModuleRenderer::new("license")
+            .with_project_file(
+                "pyproject.toml",
+                "[project]\nlicense = { text = \"BSD-3-Clause\" }\n",
+            )
+            .config(toml::toml! {
+                [license]
+                disabled = false
+                format = "$license"
+            })
+            .collect();
+
+        assert_eq!(Some("BSD-3-Clause".to_string()), actual);
+    }
+
+    #[test]
+    fn licenses_from_several_files() {
+        let actual = ModuleRenderer::new("license")
+            .with_project_file("LICENSE-MIT", MIT)
+            .with_project_file(
+                "LICENSE-APACHE",
+                "                 Apache License\n           Version 2.0, January 2004",
+            )
+            .config(toml::toml! {
+                [license]
+                disabled = false
+                format = "$license"
+            })
+            .collect();
+
+        assert_eq!(Some("Apache-2.0 OR MIT".to_string()), actual);
+    }
+
+    #[test]
+    fn denied_license() {
+        let actual = ModuleRenderer::new("license")
+            .with_project_file("package.json", r#"{ "license": "AGPL-3.0-only" }"#)
+            .config(toml::toml! {
+                [license]
+                disabled = false
+                denied = ["agpl"]
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Red.bold().paint("⚖️ AGPL-3.0-only")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn missing_license() {
+        let actual = ModuleRenderer::new("license")
+            .with_project_file("package.json", r#"{ "name": "starship" }"#)
+            .config(toml::toml! {
+                [license]
+                disabled = false
+                missing = "no license"
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Red.bold().paint("⚖️ no license")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn spdx_expressions_are_denied_by_prefix() {
+        assert!(is_denied("MIT OR AGPL-3.0", &["AGPL"]));
+        assert!(is_denied(
+            "(GPL-2.0-only WITH Classpath-exception-2.0)",
+            &["GPL"]
+        ));
+        assert!(!is_denied("LGPL-2.1", &["GPL"]));
+        assert!(!is_denied("MIT/Apache-2.0", &[]));
+        assert_eq!(
+            identify_license("GNU GENERAL PUBLIC LICENSE\nVersion 2, June 1991"),
+            Some("GPL-2.0")
+        );
+        assert_eq!(identify_license("All rights reserved."), None);
+    }
+}
@@ -4,8 +4,10 @@ use crate::config::ModuleConfig;
 use crate::configs::localip::LocalipConfig;
 use crate::formatter::StringFormatter;
 
+use regex::Regex;
 use std::io::Error;
-use std::net::UdpSocket;
+use std::net::{IpAddr, UdpSocket};
+use systemstat::{data::IpAddr as NetworkAddr, Platform, System};
 
 fn get_local_ipv4() -> Result<String, Error> {
     let socket = UdpSocket::bind("0.0.0.0:0")?;
@@ -16,13 +18,111 @@ fn get_local_ipv4() -> Result<String, Error> {
     Ok(addr.ip().to_string())
 }
 
-/// Creates a module with the ipv4 address of the local machine.
+fn get_local_ipv6() -> Result<String, Error> {
+    let socket = UdpSocket::bind("[::]:0")?;
+    socket.connect("[2001:db8::]:80")?;
+
+    let addr = socket.local_addr()?;
+
+    Ok(addr.ip().to_string())
+}
+
+/// Gets the address of the default route, trying the preferred address family first
+fn get_default_route_ip(prefer_ipv6: bool) -> Result<String, Error> {
+    if prefer_ipv6 {
+        get_local_ipv6().or_else(|_| get_local_ipv4())
+    } else {
+        get_local_ipv4().or_else(|e| get_local_ipv6().map_err(|_| e))
+    }
+}
+
+/// Lists the addresses of the interfaces matching `interface`, or of all interfaces but
+/// loopback when it is empty
 ///
-/// The IP address is gathered from the local endpoint of an UDP socket
-/// connected to a reserved IPv4 remote address, which is an accurate and fast
+/// Link-local IPv6 addresses are skipped, and the preferred address family comes first.
+fn get_interface_ips(interface: &str, prefer_ipv6: bool) -> Vec<(String, IpAddr)> {
+    let pattern = if interface.is_empty() {
+        None
+    } else {
+        match Regex::new(&format!("^(?:{interface})$")) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                log::warn!("Invalid interface regex in module `localip`: {e}");
+                return Vec::new();
+            }
+        }
+    };
+
+    let networks = match System::new().networks() {
+        Ok(networks) => networks,
+        Err(e) => {
+            log::warn!("unable to list network interfaces: {e}");
+            return Vec::new();
+        }
+    };
+
+    let mut ips: Vec<(String, IpAddr)> = networks
+        .values()
+        .filter(|network| pattern.as_ref().map_or(true, |p| p.is_match(&network.name)))
+        .flat_map(|network| {
+            network.addrs.iter().filter_map(|addrs| {
+                let ip = match addrs.addr {
+                    NetworkAddr::V4(ip) => IpAddr::V4(ip),
+                    NetworkAddr::V6(ip) => IpAddr::V6(ip),
+                    _ => return None,
+                };
+                Some((network.name.clone(), ip))
+            })
+        })
+        .filter(|(_, ip)| pattern.is_some() || !ip.is_loopback())
+        .filter(|(_, ip)| !is_ipv6_link_local(ip))
+        .collect();
+    ips.sort_by_key(|(_, ip)| ip.is_ipv6() != prefer_ipv6);
+    ips
+}
+
+fn is_ipv6_link_local(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V6(ip) => ip.segments()[0] & 0xffc0 == 0xfe80,
+        IpAddr::V4(_) => false,
+    }
+}
+
+/// Finds the interface an address belongs to
+fn get_interface_name(ip: &str) -> Option<String> {
+    let ip: IpAddr = ip.parse().ok()?;
+    get_interface_ips("", false)
+        .into_iter()
+        .find_map(|(name, interface_ip)| (interface_ip == ip).then_some(name))
+}
+
+/// Joins up to `max_addresses` addresses, where 0 means no limit
+fn join_ips<'a>(
+    ips: impl Iterator<Item = &'a IpAddr>,
+    max_addresses: usize,
+    separator: &str,
+) -> String {
+    let limit = if max_addresses == 0 {
+        usize::MAX
+    } else {
+        max_addresses
+    };
+    ips.take(limit)
+        .map(IpAddr::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Creates a module with the ip address of the local machine.
+///
+/// By default, the IP address is gathered from the local endpoint of an UDP socket
+/// connected to a reserved remote address, which is an accurate and fast
 /// way, especially if there are multiple IP addresses available.
 /// There should be no actual packets send over the wire.
 ///
+/// When an `interface` is configured or several addresses are requested, the addresses are
+/// listed from the network interfaces instead.
+///
 /// Will display the ip if all of the following criteria are met:
 ///     - `localip.disabled` is false
 ///     - `localip.ssh_only` is false OR the user is currently connected as an SSH session (`$SSH_CONNECTION`)
@@ -41,17 +141,45 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    let localip = match get_local_ipv4() {
-        Ok(ip) => ip,
-        Err(e) => {
-            // ErrorKind::NetworkUnreachable is unstable
-            if cfg!(target_os = "linux") && e.raw_os_error() == Some(101) {
-                "NetworkUnreachable".to_string()
-            } else {
-                log::warn!("unable to determine local ipv4 address: {e}");
-                return None;
+    let use_default_route = config.interface.is_empty() && config.max_addresses == 1;
+    let (localip, interface_ips) = if use_default_route {
+        let localip = match get_default_route_ip(config.prefer_ipv6) {
+            Ok(ip) => ip,
+            Err(e) => {
+                // ErrorKind::NetworkUnreachable is unstable
+                if cfg!(target_os = "linux") && e.raw_os_error() == Some(101) {
+                    "NetworkUnreachable".to_string()
+                } else {
+                    log::warn!("unable to determine local ip address: {e}");
+                    return None;
+                }
             }
+        };
+        (localip, None)
+    } else {
+        let interface_ips = get_interface_ips(config.interface, config.prefer_ipv6);
+        if interface_ips.is_empty() {
+            return None;
         }
+        let localip = join_ips(
+            interface_ips.iter().map(|(_, ip)| ip),
+            config.max_addresses,
+            config.separator,
+        );
+        (localip, Some(interface_ips))
+    };
+
+    let ips_of_family = |ipv6: bool| match &interface_ips {
+        Some(interface_ips) => join_ips(
+            interface_ips
+                .iter()
+                .map(|(_, ip)| ip)
+                .filter(|ip| ip.is_ipv6() == ipv6),
+            config.max_addresses,
+            config.separator,
+        ),
+        None if localip.contains(':') == ipv6 => localip.clone(),
+        None => String::new(),
     };
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
@@ -61,7 +189,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "localipv4" => Some(Ok(&localip)),
+                "localip" => Some(Ok(localip.clone())),
+                "localipv4" => Some(Ok(ips_of_family(false))),
+                "localipv6" => Some(Ok(ips_of_family(true))),
+                "interface" => match &interface_ips {
+                    Some(interface_ips) => interface_ips.first().map(|(name, _)| Ok(name.clone())),
+                    None => get_interface_name(&localip).map(Ok),
+                },
                 _ => None,
             })
             .parse(None, Some(context))
@@ -80,7 +214,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
 #[cfg(test)]
 mod tests {
-    use crate::modules::localip::get_local_ipv4;
+    use crate::modules::localip::{get_local_ipv4, is_ipv6_link_local, join_ips};
     use crate::test::ModuleRenderer;
     use nu_ansi_term::{Color, Style};
 
@@ -163,6 +297,46 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_join_ips() {
+        let ips = ["10.0.0.2".parse().unwrap(), "fd00::2".parse().unwrap()];
+        assert_eq!(join_ips(ips.iter(), 1, " "), "10.0.0.2");
+        assert_eq!(join_ips(ips.iter(), 0, ", "), "10.0.0.2, fd00::2");
+        assert!(is_ipv6_link_local(&"fe80::1".parse().unwrap()));
+        assert!(!is_ipv6_link_local(&"fd00::2".parse().unwrap()));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn loopback_interface() {
+        let actual = ModuleRenderer::new("localip")
+            .config(toml::toml! {
+                [localip]
+                ssh_only = false
+                disabled = false
+                interface = "lo"
+                format = "[$interface $localipv4]($style)"
+            })
+            .collect();
+        let expected = Some(format!("{}", style().paint("lo 127.0.0.1")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn unknown_interface() {
+        let actual = ModuleRenderer::new("localip")
+            .config(toml::toml! {
+                [localip]
+                ssh_only = false
+                disabled = false
+                interface = "starship[0-9]+"
+            })
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
     fn style() -> Style {
         Color::Yellow.bold()
     }
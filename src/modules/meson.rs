@@ -1,28 +1,52 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
 use super::{Context, Module, ModuleConfig};
 
 use super::utils::truncate::truncate_text;
 use crate::configs::meson::MesonConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VersionFormatter};
+use regex::Regex;
+use serde_json as json;
 
-/// Creates a module with the current Meson dev environment
+/// Creates a module with the current Meson dev environment or Meson project
 ///
-/// Will display the Meson environment if `$MESON_DEVENV` and `MESON_PROJECT_NAME` are set.
+/// Will display the Meson environment if `$MESON_DEVENV` and `MESON_PROJECT_NAME` are set, or the
+/// Meson project of the current directory, along with the Meson version and the `buildtype` of the
+/// configured build directory.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
-    let meson_env = context.get_env("MESON_DEVENV")?;
-    let project_env = context.get_env("MESON_PROJECT_NAME")?;
-    if meson_env != "1" || project_env.trim().is_empty() {
-        return None;
-    }
-
     let mut module = context.new_module("meson");
     let config: MesonConfig = MesonConfig::try_load(module.config);
 
+    let project = match get_devenv_project(context) {
+        Some(project) => project,
+        None => {
+            let is_meson_project = context
+                .try_begin_scan()?
+                .set_files(&config.detect_files)
+                .set_files_not(&config.detect_files_not)
+                .set_extensions(&config.detect_extensions)
+                .set_folders(&config.detect_folders)
+                .set_content(&config.detect_content)
+                .set_scan_ancestors(config.scan_ancestors)
+                .is_match();
+
+            if !is_meson_project {
+                return None;
+            }
+
+            get_project_name(context)?
+        }
+    };
+
     let truncated_text = truncate_text(
-        &project_env,
+        &project,
         config.truncation_length as usize,
         config.truncation_symbol,
     );
 
+    let meson_info_dir = find_meson_info_dir(context, &config.build_dirs);
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
@@ -34,7 +58,26 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "project" => Some(Ok(&truncated_text)),
+                "project" => Some(Ok(truncated_text.clone())),
+                "version" => {
+                    let meson_version = meson_info_dir
+                        .as_deref()
+                        .and_then(read_meson_info_version)
+                        .or_else(|| {
+                            let output = context.exec_cmd("meson", &["--version"])?.stdout;
+                            Some(output.trim().to_string())
+                        })?;
+                    VersionFormatter::format_module_version(
+                        module.get_name(),
+                        &meson_version,
+                        config.version_format,
+                    )
+                    .map(Ok)
+                }
+                "buildtype" => meson_info_dir
+                    .as_deref()
+                    .and_then(read_meson_info_buildtype)
+                    .map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -51,10 +94,62 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+fn get_devenv_project(context: &Context) -> Option<String> {
+    let meson_env = context.get_env("MESON_DEVENV")?;
+    let project_env = context.get_env("MESON_PROJECT_NAME")?;
+    if meson_env != "1" || project_env.trim().is_empty() {
+        return None;
+    }
+    Some(project_env)
+}
+
+/// Reads the name from the `project()` call in `meson.build`, falling back to the directory name
+fn get_project_name(context: &Context) -> Option<String> {
+    let re = Regex::new(r#"project\s*\(\s*['"]([^'"]+)['"]"#).unwrap();
+    let from_meson_build = context
+        .read_file_from_pwd("meson.build")
+        .and_then(|contents| Some(re.captures(&contents)?.get(1)?.as_str().to_string()));
+
+    from_meson_build.or_else(|| {
+        let dir_name = context.current_dir.file_name()?;
+        Some(dir_name.to_string_lossy().to_string())
+    })
+}
+
+/// Finds the `meson-info` directory of the current directory when it is a build directory itself,
+/// or of the first configured build directory
+fn find_meson_info_dir(context: &Context, build_dirs: &[&str]) -> Option<PathBuf> {
+    std::iter::once(context.current_dir.clone())
+        .chain(build_dirs.iter().map(|dir| context.current_dir.join(dir)))
+        .map(|dir| dir.join("meson-info"))
+        .find(|dir| dir.is_dir())
+}
+
+fn read_meson_info_version(meson_info_dir: &Path) -> Option<String> {
+    let contents = fs::read_to_string(meson_info_dir.join("meson-info.json")).ok()?;
+    let meson_info: json::Value = json::from_str(&contents).ok()?;
+    let version = meson_info.get("meson_version")?.get("full")?.as_str()?;
+    Some(version.to_string())
+}
+
+fn read_meson_info_buildtype(meson_info_dir: &Path) -> Option<String> {
+    let contents = fs::read_to_string(meson_info_dir.join("intro-buildoptions.json")).ok()?;
+    let build_options: json::Value = json::from_str(&contents).ok()?;
+    build_options
+        .as_array()?
+        .iter()
+        .find(|option| option.get("name").and_then(json::Value::as_str) == Some("buildtype"))?
+        .get("value")?
+        .as_str()
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
     use nu_ansi_term::Color;
+    use std::fs::{self, File};
+    use std::io;
 
     #[test]
     fn not_in_env() {
@@ -72,7 +167,10 @@ mod tests {
             .env("MESON_PROJECT_NAME", "starship")
             .collect();
 
-        let expected = Some(format!("via {} ", Color::Blue.bold().paint("⬢ starship")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("⬢ starship v1.2.3")
+        ));
 
         assert_eq!(expected, actual);
     }
@@ -95,4 +193,54 @@ mod tests {
         let expected = None;
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn folder_without_meson_build() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("CMakeLists.txt"))?.sync_all()?;
+        let actual = ModuleRenderer::new("meson").path(dir.path()).collect();
+        assert_eq!(None, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_meson_build() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("meson.build"),
+            "project('starship', 'c',\n  version : '1.0.0')\n",
+        )?;
+        let actual = ModuleRenderer::new("meson").path(dir.path()).collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("⬢ starship v1.2.3")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_configured_build_dir() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("meson.build"), "project('starship', 'c')\n")?;
+        let meson_info = dir.path().join("builddir").join("meson-info");
+        fs::create_dir_all(&meson_info)?;
+        fs::write(
+            meson_info.join("meson-info.json"),
+            r#"{"meson_version": {"full": "1.3.0", "major": 1, "minor": 3, "patch": 0}}"#,
+        )?;
+        fs::write(
+            meson_info.join("intro-buildoptions.json"),
+            r#"[{"name": "backend", "value": "ninja"}, {"name": "buildtype", "value": "debugoptimized"}]"#,
+        )?;
+        let actual = ModuleRenderer::new("meson").path(dir.path()).collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue
+                .bold()
+                .paint("⬢ starship v1.3.0 (debugoptimized)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
 }
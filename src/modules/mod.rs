@@ -1,4 +1,5 @@
 // While adding out new module add out module to src/module.rs ALL_MODULES const array also.
+mod arch;
 mod aws;
 mod azure;
 mod buf;
@@ -10,13 +11,18 @@ mod cmd_duration;
 mod cobol;
 mod conda;
 mod container;
+mod context;
+mod cpp_package_manager;
 mod crystal;
 pub(crate) mod custom;
+mod cuda;
 mod daml;
 mod dart;
+mod database;
 mod deno;
 mod directory;
 mod docker_context;
+mod dotenv;
 mod dotnet;
 mod elixir;
 mod elm;
@@ -24,29 +30,41 @@ mod env_var;
 mod erlang;
 mod fill;
 mod gcloud;
+#[cfg(feature = "git")]
 mod git_branch;
+#[cfg(feature = "git")]
 mod git_commit;
+#[cfg(feature = "git")]
 mod git_metrics;
+#[cfg(feature = "git")]
 mod git_state;
+#[cfg(feature = "git")]
 mod git_status;
 mod golang;
+mod gpu;
 mod guix_shell;
 mod haskell;
 mod haxe;
+#[cfg(feature = "network")]
+mod healthcheck;
 mod helm;
 mod hg_branch;
+mod history;
 mod hostname;
 mod java;
 mod jobs;
 mod julia;
+mod jupyter;
 mod kotlin;
 mod kubernetes;
+mod license;
 mod line_break;
 mod localip;
 mod lua;
 mod memory_usage;
 mod meson;
 mod nim;
+mod nix_generation;
 mod nix_shell;
 mod nodejs;
 mod ocaml;
@@ -56,10 +74,12 @@ mod os;
 mod package;
 mod perl;
 mod php;
+pub(crate) mod plugin;
 mod pulumi;
 mod purescript;
 mod python;
 mod raku;
+mod reboot_required;
 mod red;
 mod rlang;
 mod ruby;
@@ -72,8 +92,11 @@ mod spack;
 mod status;
 mod sudo;
 mod swift;
+mod systemd;
 mod terraform;
 mod time;
+mod timer;
+mod updates;
 mod username;
 mod utils;
 mod vagrant;
@@ -98,6 +121,7 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
         match module {
             // Keep these ordered alphabetically.
             // Default ordering is handled in configs/starship_root.rs
+            "arch" => arch::module(context),
             "aws" => aws::module(context),
             "azure" => azure::module(context),
             #[cfg(feature = "battery")]
@@ -111,11 +135,15 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "cobol" => cobol::module(context),
             "conda" => conda::module(context),
             "container" => container::module(context),
+            "context" => context::module(context),
+            "cuda" => cuda::module(context),
             "daml" => daml::module(context),
             "dart" => dart::module(context),
+            "database" => database::module(context),
             "deno" => deno::module(context),
             "directory" => directory::module(context),
             "docker_context" => docker_context::module(context),
+            "dotenv" => dotenv::module(context),
             "dotnet" => dotnet::module(context),
             "elixir" => elixir::module(context),
             "elm" => elm::module(context),
@@ -123,29 +151,41 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "env_var" => env_var::module(context),
             "fill" => fill::module(context),
             "gcloud" => gcloud::module(context),
+            #[cfg(feature = "git")]
             "git_branch" => git_branch::module(context),
+            #[cfg(feature = "git")]
             "git_commit" => git_commit::module(context),
+            #[cfg(feature = "git")]
             "git_metrics" => git_metrics::module(context),
+            #[cfg(feature = "git")]
             "git_state" => git_state::module(context),
+            #[cfg(feature = "git")]
             "git_status" => git_status::module(context),
             "golang" => golang::module(context),
+            "gpu" => gpu::module(context),
             "guix_shell" => guix_shell::module(context),
             "haskell" => haskell::module(context),
             "haxe" => haxe::module(context),
+            #[cfg(feature = "network")]
+            "healthcheck" => healthcheck::module(context),
             "helm" => helm::module(context),
             "hg_branch" => hg_branch::module(context),
+            "history" => history::module(context),
             "hostname" => hostname::module(context),
             "java" => java::module(context),
             "jobs" => jobs::module(context),
             "julia" => julia::module(context),
+            "jupyter" => jupyter::module(context),
             "kotlin" => kotlin::module(context),
             "kubernetes" => kubernetes::module(context),
+            "license" => license::module(context),
             "line_break" => line_break::module(context),
             "localip" => localip::module(context),
             "lua" => lua::module(context),
             "memory_usage" => memory_usage::module(context),
             "meson" => meson::module(context),
             "nim" => nim::module(context),
+            "nix_generation" => nix_generation::module(context),
             "nix_shell" => nix_shell::module(context),
             "nodejs" => nodejs::module(context),
             "ocaml" => ocaml::module(context),
@@ -160,6 +200,7 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "python" => python::module(context),
             "raku" => raku::module(context),
             "rlang" => rlang::module(context),
+            "reboot_required" => reboot_required::module(context),
             "red" => red::module(context),
             "ruby" => ruby::module(context),
             "rust" => rust::module(context),
@@ -171,9 +212,13 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "swift" => swift::module(context),
             "status" => status::module(context),
             "sudo" => sudo::module(context),
+            "systemd" => systemd::module(context),
             "terraform" => terraform::module(context),
             "time" => time::module(context),
+            "cpp_package_manager" => cpp_package_manager::module(context),
             "crystal" => crystal::module(context),
+            "timer" => timer::module(context),
+            "updates" => updates::module(context),
             "username" => username::module(context),
             "vlang" => vlang::module(context),
             "vagrant" => vagrant::module(context),
@@ -185,6 +230,11 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
                 // SAFETY: We just checked that the module starts with "custom."
                 custom::module(custom.strip_prefix("custom.").unwrap(), context)
             }
+            #[cfg(test)]
+            plugin if plugin.starts_with("plugin.") => {
+                // SAFETY: We just checked that the module starts with "plugin."
+                plugin::module(plugin.strip_prefix("plugin.").unwrap(), context)
+            }
             _ => {
                 eprintln!("Error: Unknown module {module}. Use starship module --list to list out all supported modules.");
                 None
@@ -206,6 +256,7 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
 
 pub fn description(module: &str) -> &'static str {
     match module {
+        "arch" => "The CPU architecture, highlighted when it is emulated",
         "aws" => "The current AWS region and profile",
         "azure" => "The current Azure subscription",
         "battery" => "The current charge of the device's battery and its current charging status",
@@ -220,12 +271,17 @@ pub fn description(module: &str) -> &'static str {
         "cobol" => "The currently installed version of COBOL/GNUCOBOL",
         "conda" => "The current conda environment, if $CONDA_DEFAULT_ENV is set",
         "container" => "The container indicator, if inside a container.",
+        "context" => "A compact summary of SSH, container and root/sudo status",
+        "cpp_package_manager" => "The Conan profile or vcpkg triplet of the current C++ project",
         "crystal" => "The currently installed version of Crystal",
+        "cuda" => "The installed version of the CUDA or ROCm toolkit",
         "daml" => "The Daml SDK version of your project",
         "dart" => "The currently installed version of Dart",
+        "database" => "The database your SQL clients connect to",
         "deno" => "The currently installed version of Deno",
         "directory" => "The current working directory",
         "docker_context" => "The current docker context",
+        "dotenv" => "The number of variables the .env files of the current directory define",
         "dotnet" => "The relevant version of the .NET Core SDK for the current directory",
         "elixir" => "The currently installed versions of Elixir and OTP",
         "elm" => "The currently installed version of Elm",
@@ -239,17 +295,22 @@ pub fn description(module: &str) -> &'static str {
         "git_state" => "The current git operation, and it's progress",
         "git_status" => "Symbol representing the state of the repo",
         "golang" => "The currently installed version of Golang",
+        "gpu" => "The utilization and memory usage of the NVIDIA GPUs",
         "guix_shell" => "The guix-shell environment",
         "haskell" => "The selected version of the Haskell toolchain",
         "haxe" => "The currently installed version of Haxe",
+        "healthcheck" => "Whether the configured HTTP services are up",
         "helm" => "The currently installed version of Helm",
         "hg_branch" => "The active branch of the repo in your current directory",
+        "history" => "The shell history number of the next command",
         "hostname" => "The system hostname",
         "java" => "The currently installed version of Java",
         "jobs" => "The current number of jobs running",
         "julia" => "The currently installed version of Julia",
+        "jupyter" => "Whether the shell runs in a Jupyter terminal or kernel",
         "kotlin" => "The currently installed version of Kotlin",
         "kubernetes" => "The current Kubernetes context name and, if set, the namespace",
+        "license" => "The license of the current project",
         "line_break" => "Separates the prompt into two lines",
         "localip" => "The currently assigned ipv4 address",
         "lua" => "The currently installed version of Lua",
@@ -258,6 +319,7 @@ pub fn description(module: &str) -> &'static str {
             "The current Meson environment, if $MESON_DEVENV and $MESON_PROJECT_NAME are set"
         }
         "nim" => "The currently installed version of Nim",
+        "nix_generation" => "The current Home Manager or NixOS generation",
         "nix_shell" => "The nix-shell environment",
         "nodejs" => "The currently installed version of NodeJS",
         "ocaml" => "The currently installed version of OCaml",
@@ -271,6 +333,7 @@ pub fn description(module: &str) -> &'static str {
         "purescript" => "The currently installed version of PureScript",
         "python" => "The currently installed version of Python",
         "raku" => "The currently installed version of Raku",
+        "reboot_required" => "Whether the system needs to be rebooted to apply updates",
         "red" => "The currently installed version of Red",
         "rlang" => "The currently installed version of R",
         "ruby" => "The currently installed version of Ruby",
@@ -283,8 +346,11 @@ pub fn description(module: &str) -> &'static str {
         "status" => "The status of the last command",
         "sudo" => "The sudo credentials are currently cached",
         "swift" => "The currently installed version of Swift",
+        "systemd" => "The number of failed systemd units",
         "terraform" => "The currently selected terraform workspace and version",
         "time" => "The current local time",
+        "timer" => "A countdown of the focus timer started with `starship timer start`",
+        "updates" => "The number of pending package updates",
         "username" => "The active user's username",
         "vagrant" => "The currently installed version of Vagrant",
         "vcsh" => "The currently active VCSH repository",
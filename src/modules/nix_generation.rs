@@ -0,0 +1,242 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::nix_generation::NixGenerationConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module with the current Home Manager or `NixOS` generation
+///
+/// The generation is read from the link the profile points to, e.g. `home-manager-42-link`.
+/// `$dirty` is shown when the git repository holding the configuration has uncommitted changes.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("nix_generation");
+    let config = NixGenerationConfig::try_load(module.config);
+
+    let (profile, generation) = config
+        .profiles
+        .iter()
+        .find_map(|profile| Some((*profile, get_generation(context, profile)?)))?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "profile" => Some(Ok(profile.to_string())),
+                "generation" => Some(Ok(generation.to_string())),
+                "dirty" => {
+                    let config_path = get_config_path(context, profile, config.config_path)?;
+                    is_dirty(context, &config_path).then(|| Ok(config.dirty_symbol.to_string()))
+                }
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `nix_generation`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn get_generation(context: &Context, profile: &str) -> Option<u32> {
+    get_profile_links(context, profile)
+        .iter()
+        .find_map(|link| parse_generation(&fs::read_link(link).ok()?))
+}
+
+/// Returns the locations of the profile link, newest layout first
+fn get_profile_links(context: &Context, profile: &str) -> Vec<PathBuf> {
+    match profile {
+        "home-manager" => {
+            let mut links = Vec::new();
+            let state_home = context
+                .get_env("XDG_STATE_HOME")
+                .map(PathBuf::from)
+                .or_else(|| Some(context.get_home()?.join(".local").join("state")));
+            if let Some(state_home) = state_home {
+                links.push(state_home.join("nix/profiles/home-manager"));
+            }
+            if let Some(user) = context.get_env("USER") {
+                links.push(
+                    Path::new("/nix/var/nix/profiles/per-user")
+                        .join(user)
+                        .join("home-manager"),
+                );
+            }
+            links
+        }
+        "system" => vec![PathBuf::from("/nix/var/nix/profiles/system")],
+        _ => {
+            log::warn!("Unknown profile in module `nix_generation`: {profile}");
+            Vec::new()
+        }
+    }
+}
+
+/// Parses the generation from a profile link target, e.g. `home-manager-42-link`
+fn parse_generation(target: &Path) -> Option<u32> {
+    let file_name = target.file_name()?.to_str()?;
+    let (_, generation) = file_name.strip_suffix("-link")?.rsplit_once('-')?;
+    generation.parse().ok()
+}
+
+fn get_config_path(context: &Context, profile: &str, config_path: &str) -> Option<PathBuf> {
+    if !config_path.is_empty() {
+        return Some(Context::expand_tilde(PathBuf::from(config_path)));
+    }
+
+    let candidates = match profile {
+        "home-manager" => {
+            let config_home = context
+                .get_env("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| Some(context.get_home()?.join(".config")))?;
+            vec![
+                config_home.join("home-manager"),
+                config_home.join("nixpkgs"),
+            ]
+        }
+        _ => vec![PathBuf::from("/etc/nixos")],
+    };
+    candidates.into_iter().find(|path| path.is_dir())
+}
+
+fn is_dirty(context: &Context, config_path: &Path) -> bool {
+    let config_path = config_path.to_string_lossy();
+    context
+        .exec_cmd("git", &["-C", &config_path, "status", "--porcelain"])
+        .map_or(false, |output| !output.stdout.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
+    use nu_ansi_term::Color;
+    use std::io;
+
+    #[test]
+    fn test_parse_generation() {
+        assert_eq!(
+            parse_generation(Path::new("home-manager-42-link")),
+            Some(42)
+        );
+        assert_eq!(
+            parse_generation(Path::new("/nix/var/nix/profiles/system-123-link")),
+            Some(123)
+        );
+        assert_eq!(
+            parse_generation(Path::new("/nix/store/abc-home-manager-generation")),
+            None
+        );
+    }
+
+    #[test]
+    fn no_profile() -> io::Result<()> {
+        let home = tempfile::tempdir()?;
+        let actual = ModuleRenderer::new("nix_generation")
+            .env("HOME", home.path().to_string_lossy())
+            .config(toml::toml! {
+                [nix_generation]
+                disabled = false
+                profiles = ["home-manager"]
+            })
+            .collect();
+        assert_eq!(None, actual);
+        home.close()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn home_manager_generation() -> io::Result<()> {
+        let home = tempfile::tempdir()?;
+        let profiles = home.path().join(".local/state/nix/profiles");
+        fs::create_dir_all(&profiles)?;
+        std::os::unix::fs::symlink("home-manager-42-link", profiles.join("home-manager"))?;
+        let config_path = home.path().join("dotfiles");
+        fs::create_dir_all(&config_path)?;
+
+        let actual = ModuleRenderer::new("nix_generation")
+            .env("HOME", home.path().to_string_lossy())
+            .cmd(
+                &format!(
+                    "git -C {} status --porcelain",
+                    config_path.to_string_lossy()
+                ),
+                Some(CommandOutput {
+                    stdout: " M home.nix\n".to_string(),
+                    stderr: String::default(),
+                }),
+            )
+            .config(
+                toml::from_str(&format!(
+                    "
+                    [nix_generation]
+                        disabled = false
+                        profiles = ['home-manager']
+                        config_path = '{}'
+                ",
+                    config_path.to_string_lossy()
+                ))
+                .unwrap(),
+            )
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Blue.bold().paint("❄️  home-manager gen 42 ✎")
+        ));
+        assert_eq!(expected, actual);
+        home.close()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn home_manager_generation_clean_config() -> io::Result<()> {
+        let home = tempfile::tempdir()?;
+        let profiles = home.path().join(".local/state/nix/profiles");
+        fs::create_dir_all(&profiles)?;
+        std::os::unix::fs::symlink("home-manager-7-link", profiles.join("home-manager"))?;
+        let config_path = home.path().join(".config/home-manager");
+        fs::create_dir_all(&config_path)?;
+
+        let actual = ModuleRenderer::new("nix_generation")
+            .env("HOME", home.path().to_string_lossy())
+            .cmd(
+                &format!(
+                    "git -C {} status --porcelain",
+                    config_path.to_string_lossy()
+                ),
+                Some(CommandOutput {
+                    stdout: String::default(),
+                    stderr: String::default(),
+                }),
+            )
+            .config(toml::toml! {
+                [nix_generation]
+                disabled = false
+                profiles = ["home-manager"]
+            })
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Blue.bold().paint("❄️  home-manager gen 7")
+        ));
+        assert_eq!(expected, actual);
+        home.close()
+    }
+}
@@ -2,6 +2,7 @@ use super::{Context, Module, ModuleConfig};
 
 use crate::configs::nodejs::NodejsConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils::{parse_pinned_version, satisfies_pinned_version};
 
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -17,8 +18,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_js_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_extensions(&config.detect_extensions)
         .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     let is_esy_project = context
@@ -30,11 +34,17 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    let nodejs_version = Lazy::new(|| {
+    let installed_version = Lazy::new(|| {
         context
             .exec_cmd("node", &["--version"])
-            .map(|cmd| cmd.stdout)
+            .map(|cmd| cmd.stdout.trim().trim_start_matches('v').to_string())
     });
+    let pinned_version = Lazy::new(|| get_pinned_version(context));
+    let nodejs_version = Lazy::new(|| match pinned_version.deref() {
+        Some(pinned) if config.use_pinned_version => Some(pinned.clone()),
+        _ => installed_version.deref().clone(),
+    });
+    let package_json = Lazy::new(|| get_package_json(context));
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -42,10 +52,21 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
+                "style"
+                    if !config.mismatch_style.is_empty()
+                        && pinned_version.as_deref().map_or(false, |pinned| {
+                            installed_version.as_deref().map_or(false, |installed| {
+                                !satisfies_pinned_version(installed, pinned)
+                            })
+                        }) =>
+                {
+                    Some(Ok(config.mismatch_style))
+                }
                 "style" => {
-                    let engines_version = get_engines_version(context);
+                    let engines_version = get_engines_version(package_json.as_ref(), "node");
                     let in_engines_range =
-                        check_engines_version(nodejs_version.deref().as_ref()?, engines_version);
+                        check_engines_version(nodejs_version.deref().as_ref()?, engines_version)
+                            && check_package_manager_engines(context, package_json.as_ref());
                     if in_engines_range {
                         Some(Ok(config.style))
                     } else {
@@ -54,22 +75,23 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 }
                 _ => None,
             })
-            .map(|variable| match variable {
-                "version" => {
-                    let version = nodejs_version
-                        .deref()
-                        .as_ref()?
-                        .trim_start_matches('v')
-                        .trim();
-
-                    VersionFormatter::format_module_version(
-                        module.get_name(),
-                        version,
-                        config.version_format,
-                    )
-                    .map(Ok)
-                }
-                _ => None,
+            .map(|variable| {
+                let version = match variable {
+                    "engines_range" => {
+                        return get_engines_version(package_json.as_ref(), "node").map(Ok)
+                    }
+                    "version" => nodejs_version.deref(),
+                    "installed" => installed_version.deref(),
+                    "pinned" => pinned_version.deref(),
+                    _ => return None,
+                };
+
+                VersionFormatter::format_module_version(
+                    module.get_name(),
+                    version.as_ref()?,
+                    config.version_format,
+                )
+                .map(Ok)
             })
             .parse(None, Some(context))
     });
@@ -85,13 +107,51 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
-fn get_engines_version(context: &Context) -> Option<String> {
+/// Reads the version of `.nvmrc` or `.node-version`, without running `node`
+fn get_pinned_version(context: &Context) -> Option<String> {
+    [".nvmrc", ".node-version"].iter().find_map(|file| {
+        let contents = context.read_file_from_pwd(file)?;
+        parse_pinned_version(&contents).map(str::to_owned)
+    })
+}
+
+fn get_package_json(context: &Context) -> Option<json::Value> {
     let json_str = context.read_file_from_pwd("package.json")?;
-    let package_json: json::Value = json::from_str(&json_str).ok()?;
-    let raw_version = package_json.get("engines")?.get("node")?.as_str()?;
+    json::from_str(&json_str).ok()
+}
+
+fn get_engines_version(package_json: Option<&json::Value>, engine: &str) -> Option<String> {
+    let raw_version = package_json?.get("engines")?.get(engine)?.as_str()?;
     Some(raw_version.to_string())
 }
 
+/// Checks the `yarn` and `pnpm` engines of the package manager the project uses, if any
+fn check_package_manager_engines(context: &Context, package_json: Option<&json::Value>) -> bool {
+    let package_manager = package_json
+        .and_then(|package_json| package_json.get("packageManager")?.as_str())
+        .and_then(|package_manager| package_manager.split('@').next());
+    let has_file = |name: &str| {
+        context
+            .dir_contents()
+            .map_or(false, |dir_contents| dir_contents.has_file(name))
+    };
+
+    [("yarn", "yarn.lock"), ("pnpm", "pnpm-lock.yaml")]
+        .iter()
+        .filter(|(manager, lock_file)| package_manager == Some(manager) || has_file(lock_file))
+        .all(|(manager, _)| {
+            let engines_version = match get_engines_version(package_json, manager) {
+                Some(engines_version) => engines_version,
+                None => return true,
+            };
+            context
+                .exec_cmd(manager, &["--version"])
+                .map_or(true, |output| {
+                    check_engines_version(output.stdout.trim(), Some(engines_version))
+                })
+        })
+}
+
 fn check_engines_version(nodejs_version: &str, engines_version: Option<String>) -> bool {
     if engines_version.is_none() {
         return true;
@@ -101,12 +161,11 @@ fn check_engines_version(nodejs_version: &str, engines_version: Option<String>)
         Err(_e) => return true,
     };
     let re = Regex::new(r"\d+\.\d+\.\d+").unwrap();
-    let version = re
-        .captures(nodejs_version)
-        .unwrap()
-        .get(0)
-        .unwrap()
-        .as_str();
+    // Pinned versions can be partial, e.g. `18`
+    let version = match re.find(nodejs_version) {
+        Some(version) => version.as_str(),
+        None => return true,
+    };
     let v = match Version::parse(version) {
         Ok(v) => v,
         Err(_e) => return true,
@@ -117,6 +176,7 @@ fn check_engines_version(nodejs_version: &str, engines_version: Option<String>)
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
     use nu_ansi_term::Color;
     use std::fs::{self, File};
     use std::io;
@@ -142,6 +202,45 @@ mod tests {
         dir.close()
     }
 
+    #[test]
+    #[cfg(feature = "git")]
+    fn repo_root_with_package_json_and_scan_ancestors() -> io::Result<()> {
+        let repo_dir = tempfile::tempdir()?;
+        crate::utils::create_command("git")?
+            .args(["init", "--quiet"])
+            .current_dir(repo_dir.path())
+            .output()?;
+        File::create(repo_dir.path().join("package.json"))?.sync_all()?;
+        let dir = repo_dir.path().join("src/components");
+        fs::create_dir_all(&dir)?;
+
+        let actual = ModuleRenderer::new("nodejs").path(&dir).collect();
+        assert_eq!(None, actual);
+
+        let actual = ModuleRenderer::new("nodejs")
+            .path(&dir)
+            .config(toml::toml! {
+                [nodejs]
+                scan_ancestors = true
+            })
+            .collect();
+        let expected = Some(format!("via {}", Color::Green.bold().paint(" v12.0.0 ")));
+        assert_eq!(expected, actual);
+
+        // The closest directory decides, and nothing above the repository is scanned
+        File::create(dir.join("package.json"))?.sync_all()?;
+        let actual = ModuleRenderer::new("nodejs")
+            .path(&dir)
+            .config(toml::toml! {
+                [nodejs]
+                scan_ancestors = true
+                detect_files = ["!package.json"]
+            })
+            .collect();
+        assert_eq!(None, actual);
+        repo_dir.close()
+    }
+
     #[test]
     fn folder_with_package_json_and_esy_lock() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -270,4 +369,65 @@ mod tests {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn engines_range_and_package_manager() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"engines": {"node": ">=12.0.0", "pnpm": ">=8"}}"#,
+        )?;
+        File::create(dir.path().join("pnpm-lock.yaml"))?.sync_all()?;
+
+        let render = |pnpm_version: &str| {
+            ModuleRenderer::new("nodejs")
+                .path(dir.path())
+                .cmd(
+                    "pnpm --version",
+                    Some(CommandOutput {
+                        stdout: format!("{}\n", pnpm_version),
+                        stderr: String::default(),
+                    }),
+                )
+                .config(toml::toml! {
+                    [nodejs]
+                    format = "[$engines_range]($style)"
+                })
+                .collect()
+        };
+
+        let expected = |color: Color| Some(format!("{}", color.bold().paint(">=12.0.0")));
+        assert_eq!(expected(Color::Green), render("8.6.0"));
+        assert_eq!(expected(Color::Red), render("7.33.1"));
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_nvmrc_and_pinned_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join(".nvmrc"), "v18.16.0\n")?;
+
+        let actual = ModuleRenderer::new("nodejs")
+            .path(dir.path())
+            .config(toml::toml! {
+                [nodejs]
+                format = "$version $pinned $installed"
+                use_pinned_version = true
+            })
+            .collect();
+        assert_eq!(Some("v18.16.0 v18.16.0 v12.0.0".to_string()), actual);
+
+        // Aliases aren't versions, so the installed version is shown
+        fs::write(dir.path().join(".nvmrc"), "lts/*\n")?;
+        let actual = ModuleRenderer::new("nodejs")
+            .path(dir.path())
+            .config(toml::toml! {
+                [nodejs]
+                format = "$version"
+                use_pinned_version = true
+            })
+            .collect();
+        assert_eq!(Some("v12.0.0".to_string()), actual);
+        dir.close()
+    }
 }
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use super::{Context, Module, ModuleConfig};
 
 use crate::configs::os::OSConfig;
 use crate::formatter::StringFormatter;
+use crate::utils::{context_path, read_file};
 
 /// Creates a module with the current operating system
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -12,16 +15,25 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
+    // The operating system is detected on this machine
+    if !context.is_local() {
+        return None;
+    }
+
     #[cfg(not(test))]
     let os = os_info::get();
 
     #[cfg(test)]
     let os = os_info::Info::default();
 
+    let os_release = get_os_release(context);
+    let wsl_distro = context.get_env("WSL_DISTRO_NAME");
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
                 "symbol" => get_symbol(&config, &os.os_type()),
+                "wsl_symbol" => wsl_distro.as_ref().map(|_| config.wsl_symbol),
                 _ => None,
             })
             .map_style(|variable| match variable {
@@ -29,11 +41,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "codename" => get_codename(&os).map(Ok),
+                "build" => get_build(&os, &os_release).map(Ok),
+                "codename" => get_codename(&os, &os_release).map(Ok),
                 "edition" => get_edition(&os).map(Ok),
                 "name" => get_name(&os).map(Ok),
                 "type" => get_type(&os).map(Ok),
-                "version" => get_version(&os).map(Ok),
+                "version" => get_version(&os, &os_release).map(Ok),
+                "wsl_distro" => wsl_distro.clone().map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -57,8 +71,44 @@ fn get_symbol<'a>(config: &'a OSConfig, os_type: &os_info::Type) -> Option<&'a s
         .or_else(|| OSConfig::default().get_symbol(os_type))
 }
 
-fn get_codename(os: &os_info::Info) -> Option<String> {
-    os.codename().map(String::from)
+/// Reads the `KEY=value` pairs of `/etc/os-release`, which fills in what `os_info` doesn't detect
+fn get_os_release(context: &Context) -> HashMap<String, String> {
+    ["/etc/os-release", "/usr/lib/os-release"]
+        .iter()
+        .find_map(|path| read_file(context_path(context, path)).ok())
+        .map(|contents| parse_os_release(&contents))
+        .unwrap_or_default()
+}
+
+fn parse_os_release(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once('=')?;
+            let value = value.trim_matches(|c| c == '"' || c == '\'');
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn get_build(os: &os_info::Info, os_release: &HashMap<String, String>) -> Option<String> {
+    if let Some(build_id) = os_release.get("BUILD_ID") {
+        return Some(build_id.clone());
+    }
+
+    // The build number shown by `winver`, e.g. 22631 for Windows 10.0.22631
+    match (os.os_type(), os.version()) {
+        (os_info::Type::Windows, os_info::Version::Semantic(_, _, build)) => {
+            Some(build.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn get_codename(os: &os_info::Info, os_release: &HashMap<String, String>) -> Option<String> {
+    os.codename()
+        .map(String::from)
+        .or_else(|| os_release.get("VERSION_CODENAME").cloned())
 }
 
 fn get_edition(os: &os_info::Info) -> Option<String> {
@@ -74,10 +124,11 @@ fn get_type(os: &os_info::Info) -> Option<String> {
     Some(format!("{:?}", os.os_type()))
 }
 
-fn get_version(os: &os_info::Info) -> Option<String> {
+fn get_version(os: &os_info::Info, os_release: &HashMap<String, String>) -> Option<String> {
     Some(os.version())
         .filter(|&x| x != &os_info::Version::Unknown)
         .map(|x| x.to_string())
+        .or_else(|| os_release.get("VERSION_ID").cloned())
 }
 
 #[cfg(test)]
@@ -126,6 +177,48 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn os_release_segments() -> std::io::Result<()> {
+        let renderer = ModuleRenderer::new("os").config(toml::toml! {
+            [os]
+            disabled = false
+            format = "[$symbol($version )($codename )($build )]($style)"
+        });
+        let os_release = renderer.root_path().join("etc/os-release");
+        std::fs::create_dir_all(os_release.parent().unwrap())?;
+        crate::utils::write_file(
+            &os_release,
+            "NAME=\"Ubuntu\"\nVERSION_ID=\"22.04\"\nVERSION_CODENAME=jammy\nBUILD_ID=20231205\n",
+        )?;
+
+        let expected = Some(format!(
+            "{}",
+            Color::White.bold().paint("❓ 22.04 jammy 20231205 ")
+        ));
+
+        assert_eq!(renderer.collect(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn wsl_distro() {
+        let actual = ModuleRenderer::new("os")
+            .env("WSL_DISTRO_NAME", "Ubuntu-22.04")
+            .config(toml::toml! {
+                [os]
+                disabled = false
+                format = "[$symbol($wsl_symbol$wsl_distro )]($style)"
+            })
+            .collect();
+
+        let expected = Some(format!(
+            "{}",
+            Color::White.bold().paint("❓ 🪟 Ubuntu-22.04 ")
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn get_symbol_default() {
         let config = OSConfig::try_load(None);
@@ -1,5 +1,6 @@
 use super::{Context, Module, ModuleConfig};
 use crate::configs::package::PackageConfig;
+use crate::context::DirContents;
 use crate::formatter::{StringFormatter, VersionFormatter};
 
 use ini::Ini;
@@ -8,13 +9,12 @@ use quick_xml::Reader as QXReader;
 use regex::Regex;
 use serde_json as json;
 use std::fs;
-use std::io::Read;
 
 /// Creates a module with the current package version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("package");
     let config: PackageConfig = PackageConfig::try_load(module.config);
-    let module_version = get_version(context, &config)?;
+    let package = get_package(context, &config)?;
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -27,7 +27,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => Some(Ok(&module_version)),
+                "version" => Some(Ok(package.version.as_str())),
+                "name" => package.name.as_deref().map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -44,8 +45,27 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
-fn get_node_package_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("package.json")?;
+/// The name and formatted version a package file declares
+struct Package {
+    name: Option<String>,
+    version: String,
+}
+
+impl Package {
+    fn new(name: Option<&str>, version: &str, config: &PackageConfig) -> Option<Self> {
+        Some(Self {
+            name: name.map(ToString::to_string),
+            version: format_version(version, config.version_format)?,
+        })
+    }
+}
+
+fn get_node_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("package.json")?;
     let package_json: json::Value = json::from_str(&file_contents).ok()?;
 
     if !config.display_private
@@ -54,18 +74,18 @@ fn get_node_package_version(context: &Context, config: &PackageConfig) -> Option
         return None;
     }
 
+    let name = package_json.get("name").and_then(json::Value::as_str);
     let raw_version = package_json.get("version")?.as_str()?;
     if raw_version == "null" {
         return None;
     };
 
-    let formatted_version = format_version(raw_version, config.version_format)?;
-    if formatted_version == "v0.0.0-development" || formatted_version.starts_with("v0.0.0-semantic")
-    {
-        return Some("semantic".to_string());
+    let mut package = Package::new(name, raw_version, config)?;
+    if package.version == "v0.0.0-development" || package.version.starts_with("v0.0.0-semantic") {
+        package.version = "semantic".to_string();
     };
 
-    Some(formatted_version)
+    Some(package)
 }
 
 fn get_poetry_version(pyproject: &toml::Value) -> Option<&str> {
@@ -80,100 +100,228 @@ fn get_pep621_version(pyproject: &toml::Value) -> Option<&str> {
     pyproject.get("project")?.get("version")?.as_str()
 }
 
-fn get_pyproject_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("pyproject.toml")?;
+fn get_pyproject_name(pyproject: &toml::Value) -> Option<&str> {
+    pyproject
+        .get("project")
+        .and_then(|project| project.get("name"))
+        .or_else(|| pyproject.get("tool")?.get("poetry")?.get("name"))?
+        .as_str()
+}
+
+/// The version of a project that lists it in `project.dynamic`, from the file setuptools or
+/// hatch take it from
+fn get_pep621_dynamic_version(dir: &DirContents, pyproject: &toml::Value) -> Option<String> {
+    let dynamic = pyproject.get("project")?.get("dynamic")?.as_array()?;
+    if !dynamic
+        .iter()
+        .any(|field| field.as_str() == Some("version"))
+    {
+        return None;
+    }
+
+    let tool = pyproject.get("tool")?;
+    let setuptools = tool
+        .get("setuptools")
+        .and_then(|setuptools| setuptools.get("dynamic")?.get("version"));
+    let file = setuptools.and_then(|version| {
+        let file = version.get("file")?;
+        file.as_str().or_else(|| file.as_array()?.first()?.as_str())
+    });
+    if let Some(file) = file {
+        return Some(dir.read_file(file)?.trim().to_string());
+    }
+
+    // `attr = "package.module.__version__"` is found in `package/module.py`, or in
+    // `package/module/__init__.py`, either at the root or in `src`
+    let sources = match setuptools.and_then(|version| version.get("attr")?.as_str()) {
+        Some(attr) => {
+            let module = attr.rsplit_once('.')?.0.replace('.', "/");
+            ["", "src/"]
+                .iter()
+                .flat_map(|root| {
+                    [
+                        format!("{root}{module}.py"),
+                        format!("{root}{module}/__init__.py"),
+                    ]
+                })
+                .collect()
+        }
+        None => vec![tool
+            .get("hatch")?
+            .get("version")?
+            .get("path")?
+            .as_str()?
+            .to_string()],
+    };
+    let re =
+        Regex::new(r#"(?m)^(__version__|VERSION)\s*(:\s*str\s*)?=\s*['"](?P<version>[^'"]+)['"]"#)
+            .unwrap();
+    sources.iter().find_map(|source| {
+        let contents = dir.read_file(source)?;
+        Some(re.captures(&contents)?["version"].to_string())
+    })
+}
+
+fn get_pyproject_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("pyproject.toml")?;
     let pyproject_toml: toml::Value = toml::from_str(&file_contents).ok()?;
 
-    get_pep621_version(&pyproject_toml)
+    let raw_version = get_pep621_version(&pyproject_toml)
         .or_else(|| get_poetry_version(&pyproject_toml))
-        .and_then(|raw_version| format_version(raw_version, config.version_format))
+        .map(ToString::to_string)
+        .or_else(|| get_pep621_dynamic_version(dir, &pyproject_toml))?;
+    Package::new(get_pyproject_name(&pyproject_toml), &raw_version, config)
 }
 
-fn get_setup_cfg_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("setup.cfg")?;
+fn get_setup_cfg_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("setup.cfg")?;
     let ini = Ini::load_from_str(&file_contents).ok()?;
     let raw_version = ini.get_from(Some("metadata"), "version")?;
 
     if raw_version.starts_with("attr:") || raw_version.starts_with("file:") {
         None
     } else {
-        format_version(raw_version, config.version_format)
+        Package::new(ini.get_from(Some("metadata"), "name"), raw_version, config)
     }
 }
 
-fn get_gradle_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    context
-        .read_file_from_pwd("gradle.properties")
+fn get_gradle_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let version = dir
+        .read_file("gradle.properties")
         .and_then(|contents| {
             let re = Regex::new(r"version=(?P<version>.*)").unwrap();
             let caps = re.captures(&contents)?;
-            format_version(&caps["version"], config.version_format)
+            Some(caps["version"].to_string())
         }).or_else(|| {
-            let build_file_contents = context.read_file_from_pwd("build.gradle")?;
+            let build_file_contents = dir.read_file("build.gradle")?;
             let re = Regex::new(r#"(?m)^version ['"](?P<version>[^'"]+)['"]$"#).unwrap(); /*dark magic*/
             let caps = re.captures(&build_file_contents)?;
-            format_version(&caps["version"], config.version_format)
-
+            Some(caps["version"].to_string())
+        }).or_else(|| {
+            let build_file_contents = dir.read_file("build.gradle.kts")?;
+            let re = Regex::new(r#"(?m)^version\s*=\s*"(?P<version>[^"]+)"\s*$"#).unwrap();
+            let caps = re.captures(&build_file_contents)?;
+            Some(caps["version"].to_string())
+        })?;
+
+    // The name of a Gradle project is the one of its directory, unless its settings set it
+    let name = ["settings.gradle", "settings.gradle.kts"]
+        .iter()
+        .find_map(|file| {
+            let contents = dir.read_file(file)?;
+            let re = Regex::new(r#"rootProject\.name\s*=\s*['"](?P<name>[^'"]+)['"]"#).unwrap();
+            Some(re.captures(&contents)?["name"].to_string())
         })
+        .or_else(|| Some(dir.path().file_name()?.to_string_lossy().to_string()));
+    Package::new(name.as_deref(), &version, config)
 }
 
-fn get_composer_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("composer.json")?;
+fn get_composer_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("composer.json")?;
     let composer_json: json::Value = json::from_str(&file_contents).ok()?;
     let raw_version = composer_json.get("version")?.as_str()?;
+    let name = composer_json.get("name").and_then(json::Value::as_str);
 
-    format_version(raw_version, config.version_format)
+    Package::new(name, raw_version, config)
 }
 
-fn get_julia_project_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("Project.toml")?;
+fn get_julia_project_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("Project.toml")?;
     let project_toml: toml::Value = toml::from_str(&file_contents).ok()?;
     let raw_version = project_toml.get("version")?.as_str()?;
+    let name = project_toml.get("name").and_then(toml::Value::as_str);
 
-    format_version(raw_version, config.version_format)
+    Package::new(name, raw_version, config)
 }
 
-fn get_helm_package_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("Chart.yaml")?;
+fn get_helm_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("Chart.yaml")?;
     let yaml = yaml_rust::YamlLoader::load_from_str(&file_contents).ok()?;
-    let version = yaml.first()?["version"].as_str()?;
+    let chart = yaml.first()?;
+    let version = chart["version"].as_str()?;
 
-    format_version(version, config.version_format)
+    Package::new(chart["name"].as_str(), version, config)
 }
 
-fn get_mix_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("mix.exs")?;
+fn get_mix_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("mix.exs")?;
     let re = Regex::new(r#"(?m)version: "(?P<version>[^"]+)""#).unwrap();
     let caps = re.captures(&file_contents)?;
+    let name_re = Regex::new(r"(?m)app: :(?P<name>\w+)").unwrap();
+    let name = name_re
+        .captures(&file_contents)
+        .map(|caps| caps["name"].to_string());
 
-    format_version(&caps["version"], config.version_format)
+    Package::new(name.as_deref(), &caps["version"], config)
 }
 
-fn get_maven_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("pom.xml")?;
+fn get_maven_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("pom.xml")?;
     let mut reader = QXReader::from_str(&file_contents);
     reader.trim_text(true);
 
     let mut buf = vec![];
-    let mut in_ver = false;
+    let mut in_tag = None;
     let mut depth = 0;
+    let mut name = None;
+    let mut version = None;
     loop {
         match reader.read_event_into(&mut buf) {
             Ok(QXEvent::Start(ref e)) => {
-                in_ver = depth == 1 && e.name().as_ref() == b"version";
+                in_tag = match e.name().as_ref() {
+                    b"version" if depth == 1 => Some(b"version".as_slice()),
+                    b"artifactId" if depth == 1 => Some(b"artifactId".as_slice()),
+                    _ => None,
+                };
                 depth += 1;
             }
             Ok(QXEvent::End(_)) => {
-                in_ver = false;
+                in_tag = None;
                 depth -= 1;
             }
-            Ok(QXEvent::Text(t)) if in_ver => {
-                let ver = t.unescape().ok().map(std::borrow::Cow::into_owned);
-                return match ver {
+            Ok(QXEvent::Text(t)) if in_tag.is_some() => {
+                let text = t.unescape().ok().map(std::borrow::Cow::into_owned);
+                if in_tag == Some(b"artifactId") {
+                    name = text;
+                } else {
                     // Ignore version which is just a property reference
-                    Some(ref v) if !v.starts_with('$') => format_version(v, config.version_format),
-                    _ => None,
-                };
+                    match text {
+                        Some(v) if !v.starts_with('$') => version = Some(v),
+                        _ => return None,
+                    }
+                }
             }
             Ok(QXEvent::Eof) => break,
             Ok(_) => (),
@@ -185,51 +333,86 @@ fn get_maven_version(context: &Context, config: &PackageConfig) -> Option<String
         }
     }
 
-    None
+    Package::new(name.as_deref(), &version?, config)
 }
 
-fn get_meson_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context
-        .read_file_from_pwd("meson.build")?
+fn get_meson_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir
+        .read_file("meson.build")?
         .split_ascii_whitespace()
         .collect::<String>();
 
-    let re = Regex::new(r#"project\([^())]*,version:'(?P<version>[^']+)'[^())]*\)"#).unwrap();
+    let re =
+        Regex::new(r#"project\('(?P<name>[^']+)'[^())]*,version:'(?P<version>[^']+)'[^())]*\)"#)
+            .unwrap();
     let caps = re.captures(&file_contents)?;
 
-    format_version(&caps["version"], config.version_format)
+    Package::new(Some(&caps["name"]), &caps["version"], config)
 }
 
-fn get_vmod_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("v.mod")?;
+fn get_vmod_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("v.mod")?;
     let re = Regex::new(r"(?m)^\s*version\s*:\s*'(?P<version>[^']+)'").unwrap();
     let caps = re.captures(&file_contents)?;
-    format_version(&caps["version"], config.version_format)
+    let name_re = Regex::new(r"(?m)^\s*name\s*:\s*'(?P<name>[^']+)'").unwrap();
+    let name = name_re
+        .captures(&file_contents)
+        .map(|caps| caps["name"].to_string());
+    Package::new(name.as_deref(), &caps["version"], config)
 }
 
-fn get_vpkg_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("vpkg.json")?;
+fn get_vpkg_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("vpkg.json")?;
     let vpkg_json: json::Value = json::from_str(&file_contents).ok()?;
     let raw_version = vpkg_json.get("version")?.as_str()?;
+    let name = vpkg_json.get("name").and_then(json::Value::as_str);
 
-    format_version(raw_version, config.version_format)
+    Package::new(name, raw_version, config)
 }
 
-fn get_sbt_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("build.sbt")?;
+fn get_sbt_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("build.sbt")?;
     let re = Regex::new(r"(?m)^(.*/)*\s*version\s*:=\s*.(?P<version>[\d\.]+)").unwrap();
     let caps = re.captures(&file_contents)?;
-    format_version(&caps["version"], config.version_format)
+    let name_re = Regex::new(r#"(?m)^\s*name\s*:=\s*"(?P<name>[^"]+)""#).unwrap();
+    let name = name_re
+        .captures(&file_contents)
+        .map(|caps| caps["name"].to_string());
+    Package::new(name.as_deref(), &caps["version"], config)
 }
 
-fn get_cargo_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let mut file_contents = context.read_file_from_pwd("Cargo.toml")?;
-
-    let mut cargo_toml: toml::Value = toml::from_str(&file_contents).ok()?;
+fn get_cargo_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    let file_contents = dir.read_file("Cargo.toml")?;
+
+    let cargo_toml: toml::Value = toml::from_str(&file_contents).ok()?;
+    let name = cargo_toml
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(toml::Value::as_str);
     let cargo_version = cargo_toml.get("package").and_then(|p| p.get("version"));
     let raw_version = if let Some(v) = cargo_version.and_then(toml::Value::as_str) {
         // regular version string
-        v
+        v.to_string()
     } else if cargo_version
         .and_then(|v| v.get("workspace"))
         .and_then(toml::Value::as_bool)
@@ -239,17 +422,17 @@ fn get_cargo_version(context: &Context, config: &PackageConfig) -> Option<String
         // need to read the Cargo.toml file from the workspace root
         let mut version = None;
         // disover the workspace root
-        for path in context.current_dir.ancestors().skip(1) {
+        for path in dir.path().ancestors().skip(1) {
             // Assume the workspace root is the first ancestor that contains a Cargo.toml file
-            if let Ok(mut file) = fs::File::open(path.join("Cargo.toml")) {
-                file.read_to_string(&mut file_contents).ok()?;
-                cargo_toml = toml::from_str(&file_contents).ok()?;
+            if let Ok(ws_contents) = fs::read_to_string(path.join("Cargo.toml")) {
+                let ws_toml: toml::Value = toml::from_str(&ws_contents).ok()?;
                 // Read workspace.package.version
-                version = cargo_toml
+                version = ws_toml
                     .get("workspace")?
                     .get("package")?
                     .get("version")?
-                    .as_str();
+                    .as_str()
+                    .map(ToString::to_string);
                 break;
             }
         }
@@ -261,17 +444,20 @@ fn get_cargo_version(context: &Context, config: &PackageConfig) -> Option<String
             .get("package")?
             .get("version")?
             .as_str()?
+            .to_string()
     };
 
-    format_version(raw_version, config.version_format)
+    Package::new(name, &raw_version, config)
 }
 
-fn get_nimble_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    if !context
-        .try_begin_scan()?
-        .set_extensions(&["nimble"])
-        .is_match()
-    {
+fn get_nimble_package(
+    context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    // `nimble dump` runs in the current directory, so it can't tell the version of a package
+    // above it
+    if !dir.has_extension("nimble") || dir.path() != context.current_dir {
         return None;
     };
 
@@ -279,60 +465,81 @@ fn get_nimble_version(context: &Context, config: &PackageConfig) -> Option<Strin
     let nimble_json: json::Value = json::from_str(&cmd_output.stdout).ok()?;
 
     let raw_version = nimble_json.get("version")?.as_str()?;
+    let name = nimble_json.get("name").and_then(json::Value::as_str);
 
-    format_version(raw_version, config.version_format)
+    Package::new(name, raw_version, config)
 }
 
-fn get_shard_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("shard.yml")?;
+/// The package of the YAML files that have its `name` and `version` at the top level
+fn get_yaml_package(dir: &DirContents, file: &str, config: &PackageConfig) -> Option<Package> {
+    let file_contents = dir.read_file(file)?;
 
     let data = yaml_rust::YamlLoader::load_from_str(&file_contents).ok()?;
-    let raw_version = data.first()?["version"].as_str()?;
+    let data = data.first()?;
+    let raw_version = data["version"].as_str()?;
 
-    format_version(raw_version, config.version_format)
+    Package::new(data["name"].as_str(), raw_version, config)
 }
 
-fn get_daml_project_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("daml.yaml")?;
-
-    let daml_yaml = yaml_rust::YamlLoader::load_from_str(&file_contents).ok()?;
-    let raw_version = daml_yaml.first()?["version"].as_str()?;
-
-    format_version(raw_version, config.version_format)
+fn get_shard_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    get_yaml_package(dir, "shard.yml", config)
 }
 
-fn get_dart_pub_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let file_contents = context.read_file_from_pwd("pubspec.yaml")?;
-
-    let data = yaml_rust::YamlLoader::load_from_str(&file_contents).ok()?;
-    let raw_version = data.first()?["version"].as_str()?;
+fn get_daml_project_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    get_yaml_package(dir, "daml.yaml", config)
+}
 
-    format_version(raw_version, config.version_format)
+fn get_dart_pub_package(
+    _context: &Context,
+    dir: &DirContents,
+    config: &PackageConfig,
+) -> Option<Package> {
+    get_yaml_package(dir, "pubspec.yaml", config)
 }
 
-fn get_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let package_version_fn: Vec<fn(&Context, &PackageConfig) -> Option<String>> = vec![
-        get_cargo_version,
-        get_nimble_version,
-        get_node_package_version,
-        get_pyproject_version,
-        get_setup_cfg_version,
-        get_composer_version,
-        get_gradle_version,
-        get_julia_project_version,
-        get_mix_version,
-        get_helm_package_version,
-        get_maven_version,
-        get_meson_version,
-        get_shard_version,
-        get_vmod_version,
-        get_vpkg_version,
-        get_sbt_version,
-        get_daml_project_version,
-        get_dart_pub_version,
+/// Reads the package of one kind from the files of a directory
+type PackageFn = fn(&Context, &DirContents, &PackageConfig) -> Option<Package>;
+
+/// The package of the current directory, or with `scan_ancestors` of the closest directory
+/// above it with one, so that the packages of a monorepo show in all of their folders
+fn get_package(context: &Context, config: &PackageConfig) -> Option<Package> {
+    let package_fn: Vec<PackageFn> = vec![
+        get_cargo_package,
+        get_nimble_package,
+        get_node_package,
+        get_pyproject_package,
+        get_setup_cfg_package,
+        get_composer_package,
+        get_gradle_package,
+        get_julia_project_package,
+        get_mix_package,
+        get_helm_package,
+        get_maven_package,
+        get_meson_package,
+        get_shard_package,
+        get_vmod_package,
+        get_vpkg_package,
+        get_sbt_package,
+        get_daml_project_package,
+        get_dart_pub_package,
     ];
 
-    package_version_fn.iter().find_map(|f| f(context, config))
+    let ancestors = if config.scan_ancestors {
+        context.ancestor_dir_contents()
+    } else {
+        &[]
+    };
+    std::iter::once(context.dir_contents().ok()?)
+        .chain(ancestors)
+        .find_map(|dir| package_fn.iter().find_map(|f| f(context, dir, config)))
 }
 
 fn format_version(version: &str, version_format: &str) -> Option<String> {
@@ -841,6 +1048,76 @@ license = "MIT"
         project_dir.close()
     }
 
+    #[test]
+    fn test_extract_pep621_dynamic_version_from_file() -> io::Result<()> {
+        let config_name = "pyproject.toml";
+        let config_content = toml::toml! {
+            [project]
+            name = "starship"
+            dynamic = ["version"]
+
+            [tool.setuptools.dynamic]
+            version = {file = "VERSION"}
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        fill_config(&project_dir, "VERSION", Some("0.1.0\n"))?;
+        expect_output(&project_dir, Some("v0.1.0"), None);
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_pep621_dynamic_version_from_attr() -> io::Result<()> {
+        let config_name = "pyproject.toml";
+        let config_content = toml::toml! {
+            [project]
+            name = "starship"
+            dynamic = ["version"]
+
+            [tool.setuptools.dynamic]
+            version = {attr = "starship.__version__"}
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        fs::create_dir_all(project_dir.path().join("src/starship"))?;
+        fill_config(
+            &project_dir,
+            "src/starship/__init__.py",
+            Some("\"\"\"A prompt\"\"\"\n\n__version__ = \"0.2.0\"\n"),
+        )?;
+        expect_output(&project_dir, Some("v0.2.0"), None);
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_pep621_dynamic_version_from_hatch() -> io::Result<()> {
+        let config_name = "pyproject.toml";
+        let config_content = toml::toml! {
+            [project]
+            name = "starship"
+            dynamic = ["version"]
+
+            [tool.hatch.version]
+            path = "starship/about.py"
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        fs::create_dir_all(project_dir.path().join("starship"))?;
+        fill_config(
+            &project_dir,
+            "starship/about.py",
+            Some("VERSION = '0.3.0'\n"),
+        )?;
+        expect_output(&project_dir, Some("v0.3.0"), None);
+        project_dir.close()
+    }
+
     #[test]
     fn test_extract_setup_cfg_version() -> io::Result<()> {
         let config_name = "setup.cfg";
@@ -968,6 +1245,37 @@ java {
         expect_output(&project_dir, None, None);
         project_dir.close()
     }
+    #[test]
+    fn test_extract_gradle_kts_version() -> io::Result<()> {
+        let config_name = "build.gradle.kts";
+        let config_content = "plugins {
+    kotlin(\"jvm\") version \"1.9.0\"
+}
+
+group = \"com.example\"
+version = \"0.1.0\"
+";
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(config_content))?;
+        fill_config(
+            &project_dir,
+            "settings.gradle.kts",
+            Some("rootProject.name = \"starship\"\n"),
+        )?;
+        expect_output(&project_dir, Some("v0.1.0"), None);
+
+        let actual = ModuleRenderer::new("package")
+            .path(project_dir.path())
+            .config(toml::toml! {
+                [package]
+                format = "$name"
+            })
+            .collect();
+        assert_eq!(actual, Some("starship".to_string()));
+        project_dir.close()
+    }
+
     #[test]
     fn test_extract_grade_version_from_properties() -> io::Result<()> {
         let config_name = "gradle.properties";
@@ -1390,6 +1698,81 @@ environment:
         project_dir.close()
     }
 
+    #[test]
+    fn test_extract_package_name() -> io::Result<()> {
+        let project_dir = create_project_dir()?;
+        let pom = "<project>
+    <parent>
+        <artifactId>parent</artifactId>
+    </parent>
+    <artifactId>starship</artifactId>
+    <version>0.1.0</version>
+</project>";
+        fill_config(&project_dir, "pom.xml", Some(pom))?;
+
+        let actual = ModuleRenderer::new("package")
+            .path(project_dir.path())
+            .config(toml::toml! {
+                [package]
+                format = "$name@$version"
+            })
+            .collect();
+        assert_eq!(actual, Some("starship@v0.1.0".to_string()));
+
+        // Packages without a name still show their version
+        fill_config(
+            &project_dir,
+            "Cargo.toml",
+            Some("[workspace.package]\nversion = \"0.2.0\"\n"),
+        )?;
+        let actual = ModuleRenderer::new("package")
+            .path(project_dir.path())
+            .config(toml::toml! {
+                [package]
+                format = "($name@)$version"
+            })
+            .collect();
+        assert_eq!(actual, Some("v0.2.0".to_string()));
+        project_dir.close()
+    }
+
+    #[test]
+    #[cfg(feature = "git")]
+    fn test_extract_monorepo_package_version() -> io::Result<()> {
+        let repo_dir = create_project_dir()?;
+        crate::utils::create_command("git")?
+            .args(["init", "--quiet"])
+            .current_dir(repo_dir.path())
+            .output()?;
+        fill_config(
+            &repo_dir,
+            "package.json",
+            Some(r#"{ "name": "monorepo", "version": "1.0.0" }"#),
+        )?;
+        fs::create_dir_all(repo_dir.path().join("packages/app/src"))?;
+        fill_config(
+            &repo_dir,
+            "packages/app/package.json",
+            Some(r#"{ "name": "app", "version": "0.1.0" }"#),
+        )?;
+        let dir = repo_dir.path().join("packages/app/src");
+
+        let actual = ModuleRenderer::new("package").path(&dir).collect();
+        assert_eq!(actual, None);
+
+        // The closest package decides
+        let actual = ModuleRenderer::new("package")
+            .path(&dir)
+            .config(toml::toml! {
+                [package]
+                format = "$name $version"
+                scan_ancestors = true
+            })
+            .collect();
+        assert_eq!(actual, Some("app v0.1.0".to_string()));
+        repo_dir.close()
+    }
+
     fn create_project_dir() -> io::Result<TempDir> {
         tempfile::tempdir()
     }
@@ -3,6 +3,9 @@ use super::{Context, Module, ModuleConfig};
 use crate::configs::php::PhpConfig;
 use crate::formatter::StringFormatter;
 use crate::formatter::VersionFormatter;
+use once_cell::sync::Lazy;
+use semver::{Version, VersionReq};
+use serde_json as json;
 
 /// Creates a module with the current PHP version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -11,14 +14,29 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_php_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_folders(&config.detect_folders)
         .set_extensions(&config.detect_extensions)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     if !is_php_project {
         return None;
     }
 
+    let php_version = Lazy::new(|| {
+        context
+            .exec_cmd(
+                "php",
+                &[
+                    "-nr",
+                    "echo PHP_MAJOR_VERSION.\".\".PHP_MINOR_VERSION.\".\".PHP_RELEASE_VERSION;",
+                ],
+            )
+            .map(|output| output.stdout)
+    });
+    let php_requirement = Lazy::new(|| get_php_requirement(context));
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
@@ -26,20 +44,30 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" if config.mismatch_style.is_empty() => Some(Ok(config.style)),
+                "style" => {
+                    let is_mismatch = php_version.as_deref().map_or(false, |installed| {
+                        php_requirement.as_deref().map_or(false, |requirement| {
+                            !satisfies_composer_constraint(installed, requirement)
+                        })
+                    });
+                    Some(Ok(if is_mismatch {
+                        config.mismatch_style
+                    } else {
+                        config.style
+                    }))
+                }
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => {
-                    let php_version = context.exec_cmd(
-                        "php",
-                        &[
-                            "-nr",
-                            "echo PHP_MAJOR_VERSION.\".\".PHP_MINOR_VERSION.\".\".PHP_RELEASE_VERSION;",
-                        ],
-                    )?.stdout;
-                    VersionFormatter::format_module_version(module.get_name(), &php_version, config.version_format).map(Ok)
-                }
+                "version" => VersionFormatter::format_module_version(
+                    module.get_name(),
+                    php_version.as_deref()?,
+                    config.version_format,
+                )
+                .map(Ok),
+                "requirement" => php_requirement.clone().map(Ok),
+                "composer_version" => get_composer_version(context).map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -56,11 +84,59 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Reads the PHP version the project requires in `composer.json`
+fn get_php_requirement(context: &Context) -> Option<String> {
+    let composer_json: json::Value =
+        json::from_str(&context.read_file_from_pwd("composer.json")?).ok()?;
+    let requirement = composer_json.get("require")?.get("php")?.as_str()?;
+    Some(requirement.trim().to_string())
+}
+
+fn get_composer_version(context: &Context) -> Option<String> {
+    // The output looks like `Composer version 2.6.5 2023-10-06 10:11:52`
+    let output = context.exec_cmd("composer", &["--version", "--no-ansi"])?;
+    let version = output
+        .stdout
+        .split_whitespace()
+        .find(|part| part.starts_with(|c: char| c.is_ascii_digit()))?;
+    Some(version.to_string())
+}
+
+/// Whether the `installed` version satisfies a Composer constraint, e.g. `^7.4 || ^8.0` or
+/// `>=8.1 <8.3`. Constraints that can't be parsed are assumed to be satisfied.
+fn satisfies_composer_constraint(installed: &str, constraint: &str) -> bool {
+    let installed = match Version::parse(installed.trim()) {
+        Ok(installed) => installed,
+        Err(_) => return true,
+    };
+
+    constraint
+        .split('|')
+        .filter(|alternative| !alternative.is_empty())
+        .any(|alternative| {
+            // Composer separates the ranges that must all match with spaces or commas
+            let mut ranges: Vec<String> = Vec::new();
+            for part in alternative
+                .split([' ', ','])
+                .filter(|part| !part.is_empty())
+            {
+                match ranges.last_mut() {
+                    // Join dangling operators like `>= 7.4`
+                    Some(last) if last.chars().all(|c| "<>=!~^".contains(c)) => last.push_str(part),
+                    _ => ranges.push(part.trim_start_matches('v').to_string()),
+                }
+            }
+            VersionReq::parse(&ranges.join(", ")).map_or(true, |req| req.matches(&installed))
+        })
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::test::ModuleRenderer;
-    use nu_ansi_term::Color;
-    use std::fs::File;
+    use crate::utils::CommandOutput;
+    use nu_ansi_term::{Color, Style};
+    use std::fs::{self, File};
     use std::io;
 
     #[test]
@@ -118,4 +194,54 @@ mod tests {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn test_satisfies_composer_constraint() {
+        assert!(satisfies_composer_constraint("8.2.1", "^8.1"));
+        assert!(satisfies_composer_constraint("7.4.33", "^7.4 || ^8.0"));
+        assert!(satisfies_composer_constraint("8.1.0", ">= 8.0 <8.2"));
+        assert!(satisfies_composer_constraint("8.1.0", "8.1.*"));
+        assert!(!satisfies_composer_constraint("7.3.8", "^7.4|^8.0"));
+        assert!(!satisfies_composer_constraint("8.2.0", ">=8.0,<8.2"));
+        assert!(satisfies_composer_constraint("8.2.0", "not a constraint"));
+    }
+
+    #[test]
+    fn folder_with_composer_requirement() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let render = |requirement: &str| -> io::Result<Option<String>> {
+            fs::write(
+                dir.path().join("composer.json"),
+                format!(r#"{{"require": {{"php": "{}"}}}}"#, requirement),
+            )?;
+            Ok(ModuleRenderer::new("php")
+                .path(dir.path())
+                .cmd(
+                    "composer --version --no-ansi",
+                    Some(CommandOutput {
+                        stdout: "Composer version 2.6.5 2023-10-06 10:11:52\n".to_owned(),
+                        stderr: String::default(),
+                    }),
+                )
+                .config(toml::toml! {
+                    [php]
+                    format = "[$version $requirement]($style) $composer_version"
+                    mismatch_style = "red"
+                })
+                .collect())
+        };
+
+        let expected = |style: Style, requirement: &str| {
+            Some(format!(
+                "{} 2.6.5",
+                style.paint(format!("v7.3.8 {}", requirement))
+            ))
+        };
+        assert_eq!(expected(Color::Red.normal(), "^8.1"), render("^8.1")?);
+        assert_eq!(
+            expected(Color::Fixed(147).bold(), ">=7.3"),
+            render(">=7.3")?
+        );
+        dir.close()
+    }
 }
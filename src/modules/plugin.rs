@@ -0,0 +1,400 @@
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use process_control::{ChildExt, Control};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{Context, Module};
+
+use crate::config::parse_style_string;
+use crate::configs::plugin::PluginConfig;
+use crate::formatter::string_formatter::shell_prompt_escape;
+use crate::segment::Segment;
+use crate::serde_utils::ValueDeserializer;
+use crate::utils::create_command;
+
+/// Prefix of the executables on `PATH` that are run as plugins
+pub const PLUGIN_PREFIX: &str = "starship-module-";
+
+/// Version of the JSON protocol sent to plugins on stdin
+const PROTOCOL_VERSION: u32 = 1;
+
+/// What a plugin prints on stdout
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PluginOutput {
+    segments: Vec<PluginSegment>,
+    description: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PluginSegment {
+    text: String,
+    style: Option<String>,
+}
+
+/// Creates a module from the plugin executable `starship-module-<name>` found on `PATH`
+///
+/// The plugin receives the prompt context as JSON on stdin, and prints the segments
+/// to render as JSON on stdout. A plugin that fails or prints nothing is hidden.
+pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
+    let start = Instant::now();
+    let Some(path) = find_plugin(context, name) else {
+        log::debug!("No executable named \"{PLUGIN_PREFIX}{name}\" found in PATH");
+        return None;
+    };
+    let toml_config = context.config.get_plugin_module_config(name);
+    // Every option is forwarded to the plugin, so the ones starship doesn't know are expected
+    let config = toml_config
+        .and_then(|toml_config| {
+            PluginConfig::deserialize(ValueDeserializer::new(toml_config).with_allow_unknown_keys())
+                .map_err(|err| log::warn!("Failed to load config value: {}", err))
+                .ok()
+        })
+        .unwrap_or_default();
+
+    if config.disabled {
+        return None;
+    }
+
+    let input = json!({
+        "version": PROTOCOL_VERSION,
+        "name": name,
+        "shell": context.get_env("STARSHIP_SHELL"),
+        "cwd": context.current_dir,
+        "logical_cwd": context.logical_dir,
+        "status": context.properties.status_code,
+        "pipestatus": context.properties.pipestatus,
+        "cmd_duration": context.properties.cmd_duration,
+        "jobs": context.properties.jobs,
+        "keymap": context.properties.keymap,
+        "terminal_width": context.width,
+        "config": toml_config,
+    });
+
+    let stdout = exec_plugin(&path, &input.to_string(), context, &config)?;
+    let output = match serde_json::from_str::<PluginOutput>(&stdout) {
+        Ok(output) => output,
+        Err(error) => {
+            log::warn!("Error in plugin `{name}`: invalid output:\n{error}");
+            return None;
+        }
+    };
+
+    let description = match (config.description, output.description) {
+        (Some(description), _) => description.to_string(),
+        (None, Some(description)) => description,
+        (None, None) => format!("<plugin {}>", path.display()),
+    };
+    let mut module = Module::new(name, &description, toml_config);
+    module.set_segments(
+        output
+            .segments
+            .into_iter()
+            .flat_map(|segment| {
+                let style = segment
+                    .style
+                    .and_then(|style| parse_style_string(&style, Some(context)));
+                // The text is the plugin's, so the shell must not expand anything in it
+                Segment::from_text(style, shell_prompt_escape(segment.text, context.shell))
+            })
+            .collect(),
+    );
+    module.duration = start.elapsed();
+    Some(module)
+}
+
+/// Finds the executable of a plugin on `PATH`
+///
+/// Like the shell, the first directory of `PATH` that contains it wins. Only the file of the
+/// plugin is looked up in each directory, as listing all of them is slow on some systems.
+fn find_plugin(context: &Context, name: &str) -> Option<PathBuf> {
+    if name.is_empty() || name.contains(['/', '\\']) {
+        return None;
+    }
+    let file_name = if cfg!(windows) {
+        format!("{PLUGIN_PREFIX}{name}.exe")
+    } else {
+        format!("{PLUGIN_PREFIX}{name}")
+    };
+    env::split_paths(&context.get_env("PATH")?)
+        .map(|dir| dir.join(&file_name))
+        .find(|path| is_executable(path))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path).map_or(false, |meta| {
+        meta.is_file() && meta.permissions().mode() & 0o111 != 0
+    })
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+fn exec_plugin(
+    path: &Path,
+    input: &str,
+    context: &Context,
+    config: &PluginConfig,
+) -> Option<String> {
+    log::trace!("Running plugin {path:?}");
+    let mut child = create_command(path)
+        .ok()?
+        .current_dir(&context.current_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| log::debug!("Failed to run plugin {path:?}: {error}"))
+        .ok()?;
+
+    // Close stdin once the context is written, so the plugin sees the end of the input.
+    // Plugins that don't need the context may exit without reading it, which isn't an error.
+    if let Err(error) = child.stdin.take()?.write_all(input.as_bytes()) {
+        log::trace!("Unable to write the context to plugin {path:?}: {error}");
+    }
+
+    let mut output = child.controlled_with_output();
+    if !config.ignore_timeout {
+        output = output
+            .time_limit(Duration::from_millis(context.root_config.command_timeout))
+            .terminate_for_timeout();
+    }
+
+    let Some(output) = output.wait().ok()? else {
+        log::warn!("Executing plugin {path:?} timed out.");
+        log::warn!("You can set command_timeout in your config to a higher value or set ignore_timeout to true for this plugin to allow longer-running plugins to keep executing.");
+        return None;
+    };
+
+    if !output.status.success() {
+        log::trace!("Non-zero exit code '{:?}'", output.status.code());
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = stdout.trim();
+    (!stdout.is_empty()).then(|| stdout.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::context::Shell;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::io;
+
+    #[cfg(unix)]
+    fn write_plugin(dir: &Path, name: &str, script: &str) -> io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join(format!("{PLUGIN_PREFIX}{name}"));
+        fs::write(&path, format!("#!/bin/sh\n{script}\n"))?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+    }
+
+    #[test]
+    fn plugin_output_parsing() {
+        let output: PluginOutput = serde_json::from_str(
+            r#"{"segments": [{"text": "a", "style": "red"}, {"text": "b"}], "extra": 1}"#,
+        )
+        .unwrap();
+
+        assert_eq!(output.segments.len(), 2);
+        assert_eq!(output.segments[0].style.as_deref(), Some("red"));
+        assert_eq!(output.segments[1].style, None);
+        assert_eq!(output.description, None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn renders_plugin_segments() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_plugin(
+            dir.path(),
+            "hello",
+            r#"echo '{"segments": [{"text": "hi", "style": "bold red"}, {"text": " there"}]}'"#,
+        )?;
+
+        let actual = ModuleRenderer::new("plugin.hello")
+            .path(dir.path())
+            .env("PATH", dir.path().to_string_lossy())
+            .collect();
+        let expected = Some(format!("{} there", Color::Red.bold().paint("hi")));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn plugin_output_is_escaped() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_plugin(
+            dir.path(),
+            "evil",
+            r#"echo '{"segments": [{"text": "$(echo pwned) `id` 100%"}]}'"#,
+        )?;
+
+        let render = |shell| {
+            ModuleRenderer::new("plugin.evil")
+                .path(dir.path())
+                .env("PATH", dir.path().to_string_lossy())
+                .shell(shell)
+                .collect()
+        };
+
+        assert_eq!(
+            render(Shell::Bash),
+            Some(r"\$(echo pwned) \`id\` 100%".to_string())
+        );
+        assert_eq!(
+            render(Shell::Zsh),
+            Some("$(echo pwned) `id` 100%%".to_string())
+        );
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn plugin_receives_context_and_config() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_plugin(
+            dir.path(),
+            "echo",
+            r#"input=$(cat)
+echo "$input" | grep -q '"greeting":"howdy"' || exit 1
+echo "$input" | grep -q '"status":"1"' || exit 1
+echo '{"segments": [{"text": "ok"}]}'"#,
+        )?;
+
+        let actual = ModuleRenderer::new("plugin.echo")
+            .config(toml::toml! {
+                [plugin.echo]
+                greeting = "howdy"
+            })
+            .path(dir.path())
+            .env("PATH", dir.path().to_string_lossy())
+            .status(1)
+            .collect();
+
+        assert_eq!(Some("ok".to_string()), actual);
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn failing_plugin_is_hidden() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_plugin(
+            dir.path(),
+            "fail",
+            r#"echo '{"segments": [{"text": "no"}]}'; exit 1"#,
+        )?;
+        write_plugin(dir.path(), "garbage", "echo not json")?;
+
+        for name in ["plugin.fail", "plugin.garbage", "plugin.missing"] {
+            let actual = ModuleRenderer::new(name)
+                .path(dir.path())
+                .env("PATH", dir.path().to_string_lossy())
+                .collect();
+            assert_eq!(None, actual);
+        }
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn disabled_plugin() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_plugin(
+            dir.path(),
+            "hello",
+            r#"echo '{"segments": [{"text": "hi"}]}'"#,
+        )?;
+
+        let actual = ModuleRenderer::new("plugin.hello")
+            .config(toml::toml! {
+                [plugin.hello]
+                disabled = true
+            })
+            .path(dir.path())
+            .env("PATH", dir.path().to_string_lossy())
+            .collect();
+
+        assert_eq!(None, actual);
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn plugin_variable_runs_configured_plugins_only() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_plugin(dir.path(), "a", r#"echo '{"segments": [{"text": "<a>"}]}'"#)?;
+        write_plugin(dir.path(), "b", r#"echo '{"segments": [{"text": "<b>"}]}'"#)?;
+        write_plugin(dir.path(), "c", r#"echo '{"segments": [{"text": "<c>"}]}'"#)?;
+
+        let mut context = crate::test::default_context();
+        context.config = crate::config::StarshipConfig {
+            config: Some(toml::toml! {
+                [plugin.a]
+                [plugin.b]
+            }),
+        };
+        context.root_config.format = "${plugin.b}$plugin".to_string();
+        context.current_dir = dir.path().to_path_buf();
+        context
+            .env
+            .insert("PATH", dir.path().to_string_lossy().to_string());
+
+        assert_eq!(crate::print::get_prompt(context), "\n<b><a>");
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn finds_first_plugin_on_path() -> io::Result<()> {
+        let first = tempfile::tempdir()?;
+        let second = tempfile::tempdir()?;
+        write_plugin(first.path(), "a", "true")?;
+        write_plugin(second.path(), "a", "true")?;
+        write_plugin(second.path(), "b", "true")?;
+        fs::write(second.path().join(format!("{PLUGIN_PREFIX}c")), "")?;
+
+        let mut context = crate::test::default_context();
+        let path = env::join_paths([first.path(), second.path()]).unwrap();
+        context
+            .env
+            .insert("PATH", path.to_string_lossy().to_string());
+
+        assert_eq!(
+            find_plugin(&context, "a"),
+            Some(first.path().join(format!("{PLUGIN_PREFIX}a")))
+        );
+        assert_eq!(
+            find_plugin(&context, "b"),
+            Some(second.path().join(format!("{PLUGIN_PREFIX}b")))
+        );
+        assert_eq!(find_plugin(&context, "c"), None);
+        assert_eq!(find_plugin(&context, "../b"), None);
+
+        first.close()?;
+        second.close()
+    }
+}
@@ -5,7 +5,8 @@ use super::{Context, Module, ModuleConfig};
 use crate::configs::python::PythonConfig;
 use crate::formatter::StringFormatter;
 use crate::formatter::VersionFormatter;
-use crate::utils::get_command_string_output;
+use crate::utils::{get_command_string_output, parse_pinned_version, satisfies_pinned_version};
+use once_cell::sync::Lazy;
 
 /// Creates a module with the current Python version and, if active, virtual environment.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -15,8 +16,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_py_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_extensions(&config.detect_extensions)
         .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     let is_venv = context.get_env("VIRTUAL_ENV").is_some();
@@ -31,6 +35,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         ""
     };
 
+    let installed_version = Lazy::new(|| get_python_version(context, &config));
+    let pinned_version = Lazy::new(|| get_pinned_version(context));
+    let pyproject = Lazy::new(|| read_pyproject(context));
+    let manager = Lazy::new(|| get_manager(context, &config, pyproject.as_ref()));
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -38,18 +46,34 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" if config.mismatch_style.is_empty() => Some(Ok(config.style)),
+                "style" => {
+                    let is_mismatch = pinned_version.as_deref().map_or(false, |pinned| {
+                        installed_version.as_deref().map_or(false, |installed| {
+                            !satisfies_pinned_version(installed, pinned)
+                        })
+                    });
+                    Some(Ok(if is_mismatch {
+                        config.mismatch_style
+                    } else {
+                        config.style
+                    }))
+                }
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => {
-                    if config.pyenv_version_name {
-                        return get_pyenv_version(context).map(Ok);
-                    }
-                    let python_version = get_python_version(context, &config)?;
+                "version" if config.pyenv_version_name => get_pyenv_version(context).map(Ok),
+                "version" | "installed" | "pinned" => {
+                    let python_version = match variable {
+                        "version" if config.use_pinned_version => {
+                            pinned_version.as_ref().or(installed_version.as_ref())
+                        }
+                        "pinned" => pinned_version.as_ref(),
+                        _ => installed_version.as_ref(),
+                    }?;
                     VersionFormatter::format_module_version(
                         module.get_name(),
-                        &python_version,
+                        python_version,
                         config.version_format,
                     )
                     .map(Ok)
@@ -59,6 +83,9 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     virtual_env.as_ref().map(|e| Ok(e.trim().to_string()))
                 }
                 "pyenv_prefix" => Some(Ok(pyenv_prefix.to_string())),
+                "manager" => manager.map(|manager| Ok(manager.to_string())),
+                "env_name" => get_manager_env_name(context, (*manager)?).map(Ok),
+                "requires_python" => get_requires_python(pyproject.as_ref()?).map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -102,6 +129,12 @@ fn get_python_version(context: &Context, config: &PythonConfig) -> Option<String
     parse_python_version(&version)
 }
 
+/// Reads the version of `.python-version`, as used by pyenv, without running `python`
+fn get_pinned_version(context: &Context) -> Option<String> {
+    let contents = context.read_file_from_pwd(".python-version")?;
+    parse_pinned_version(&contents).map(str::to_owned)
+}
+
 fn parse_python_version(python_version_string: &str) -> Option<String> {
     let version = python_version_string
         // split into ["Python", "3.8.6", ...]
@@ -112,6 +145,65 @@ fn parse_python_version(python_version_string: &str) -> Option<String> {
     Some(version.to_string())
 }
 
+fn read_pyproject(context: &Context) -> Option<toml::Value> {
+    let contents = context.read_file_from_pwd("pyproject.toml")?;
+    toml::from_str(&contents).ok()
+}
+
+/// Finds the first dependency manager in `managers` that the project is set up for
+fn get_manager<'a>(
+    context: &Context,
+    config: &PythonConfig<'a>,
+    pyproject: Option<&toml::Value>,
+) -> Option<&'a str> {
+    let dir_contents = context.dir_contents().ok();
+    let has_file = |name: &str| dir_contents.map_or(false, |dir| dir.has_file(name));
+    let has_tool = |tool: &str| {
+        pyproject
+            .and_then(|pyproject| pyproject.get("tool")?.get(tool))
+            .is_some()
+    };
+
+    config
+        .managers
+        .iter()
+        .copied()
+        .find(|&manager| match manager {
+            "poetry" => has_tool("poetry") || has_file("poetry.lock"),
+            "uv" => has_tool("uv") || has_file("uv.lock"),
+            "hatch" => has_tool("hatch") || has_file("hatch.toml"),
+            "pipenv" => has_file("Pipfile") || has_file("Pipfile.lock"),
+            "pdm" => has_tool("pdm") || has_file("pdm.lock"),
+            _ => {
+                log::warn!("Unknown Python dependency manager `{}`", manager);
+                false
+            }
+        })
+}
+
+/// Returns the name of the environment `manager` has activated, if any
+fn get_manager_env_name(context: &Context, manager: &str) -> Option<String> {
+    match manager {
+        "hatch" => context.get_env("HATCH_ENV_ACTIVE"),
+        "pipenv" if context.get_env("PIPENV_ACTIVE").is_none() => None,
+        _ => get_python_virtual_env(context),
+    }
+}
+
+fn get_requires_python(pyproject: &toml::Value) -> Option<String> {
+    let requires_python = pyproject
+        .get("project")
+        .and_then(|project| project.get("requires-python"))
+        .or_else(|| {
+            pyproject
+                .get("tool")?
+                .get("poetry")?
+                .get("dependencies")?
+                .get("python")
+        })?;
+    Some(requires_python.as_str()?.trim().to_string())
+}
+
 fn get_python_virtual_env(context: &Context) -> Option<String> {
     context.get_env("VIRTUAL_ENV").and_then(|venv| {
         get_prompt_from_venv(Path::new(&venv)).or_else(|| {
@@ -134,7 +226,7 @@ mod tests {
     use super::*;
     use crate::test::ModuleRenderer;
     use nu_ansi_term::Color;
-    use std::fs::{create_dir_all, File};
+    use std::fs::{self, create_dir_all, File};
     use std::io;
     use std::io::Write;
 
@@ -217,6 +309,23 @@ Python 3.7.9 (7e6e2bb30ac5fbdbd443619cae28c51d5c162a02, Nov 24 2020, 10:03:59)
         dir.close()
     }
 
+    #[test]
+    fn folder_with_pyproject_toml_and_detect_files_not() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("pyproject.toml"))?.sync_all()?;
+        File::create(dir.path().join("package.json"))?.sync_all()?;
+
+        let actual = ModuleRenderer::new("python")
+            .path(dir.path())
+            .config(toml::toml! {
+                [python]
+                detect_files_not = ["package.json"]
+            })
+            .collect();
+        assert_eq!(None, actual);
+        dir.close()
+    }
+
     #[test]
     fn folder_with_pipfile() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -490,4 +599,72 @@ prompt = '(foo)'
         ));
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn folder_with_python_version_and_pinned_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join(".python-version"), "3.11.4\n")?;
+
+        let actual = ModuleRenderer::new("python")
+            .path(dir.path())
+            .config(toml::toml! {
+                [python]
+                format = "$version $pinned $installed"
+                use_pinned_version = true
+            })
+            .collect();
+        assert_eq!(Some("v3.11.4 v3.11.4 v3.8.0".to_string()), actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_poetry_project() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            "[tool.poetry]\nname = \"app\"\n\n[tool.poetry.dependencies]\npython = \"^3.10\"\n",
+        )?;
+
+        let actual = ModuleRenderer::new("python")
+            .path(dir.path())
+            .env("VIRTUAL_ENV", "/foo/bar/app-x1Y2z3-py3.10")
+            .config(toml::toml! {
+                [python]
+                format = "$manager $env_name $requires_python"
+            })
+            .collect();
+        assert_eq!(Some("poetry app-x1Y2z3-py3.10 ^3.10".to_string()), actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_multiple_managers() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"app\"\nrequires-python = \">=3.9\"\n\n[tool.hatch.envs.default]\n",
+        )?;
+        File::create(dir.path().join("uv.lock"))?.sync_all()?;
+
+        let render = |managers: Vec<&str>| {
+            ModuleRenderer::new("python")
+                .path(dir.path())
+                .env("HATCH_ENV_ACTIVE", "default")
+                .config(toml::toml! {
+                    [python]
+                    format = "$manager( $env_name) $requires_python"
+                    managers = managers
+                })
+                .collect()
+        };
+        assert_eq!(
+            Some("uv >=3.9".to_string()),
+            render(vec!["poetry", "uv", "hatch"])
+        );
+        assert_eq!(
+            Some("hatch default >=3.9".to_string()),
+            render(vec!["hatch", "uv"])
+        );
+        dir.close()
+    }
 }
@@ -0,0 +1,210 @@
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::reboot_required::RebootRequiredConfig;
+use crate::formatter::StringFormatter;
+use crate::utils::{cached_output, context_path, parse_refresh_interval, read_file};
+
+/// Creates a module showing whether the system needs to be rebooted to apply updates
+///
+/// Debian and Ubuntu create `/var/run/reboot-required`, dnf based distributions provide
+/// `needs-restarting -r`, and Windows sets the `RebootPending` or `RebootRequired` registry keys.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("reboot_required");
+    let config = RebootRequiredConfig::try_load(module.config);
+
+    // As we default to disabled=true, we have to check here after loading our config module,
+    // before it was only checking against whatever is in the config starship.toml
+    if config.disabled {
+        return None;
+    }
+
+    // The reboot markers are read from this machine
+    if !context.is_local() {
+        return None;
+    }
+
+    let packages = get_reboot_required(context, &config)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "packages" => Some(Ok(packages.join(", "))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `reboot_required`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Returns the packages that require a reboot, which are only known on Debian and Ubuntu, or
+/// `None` when no reboot is required
+fn get_reboot_required(context: &Context, config: &RebootRequiredConfig) -> Option<Vec<String>> {
+    if context_path(context, "/var/run/reboot-required").exists() {
+        let mut packages: Vec<String> =
+            read_file(context_path(context, "/var/run/reboot-required.pkgs"))
+                .unwrap_or_default()
+                .lines()
+                .map(str::trim)
+                .filter(|package| !package.is_empty())
+                .map(str::to_string)
+                .collect();
+        packages.dedup();
+        return Some(packages);
+    }
+
+    if context_path(context, "/usr/bin/needs-restarting").exists() {
+        return needs_restarting_cached(context, config).then(Vec::new);
+    }
+
+    is_windows_reboot_pending().then(Vec::new)
+}
+
+/// Runs `needs-restarting -r`, which is slow, reusing its result for `refresh_interval`
+///
+/// `needs-restarting -r` exits with 1 when a reboot is required, so a failure means a reboot is
+/// needed.
+fn needs_restarting_cached(context: &Context, config: &RebootRequiredConfig) -> bool {
+    let refresh_interval = parse_refresh_interval(config.refresh_interval).unwrap_or_default();
+    let output = cached_output(context, "reboot_required", refresh_interval, || {
+        let is_required = context.exec_cmd("needs-restarting", &["-r"]).is_none();
+        Some(if is_required { "1" } else { "0" }.to_string())
+    });
+    output.map_or(false, |output| output.trim() == "1")
+}
+
+#[cfg(windows)]
+fn is_windows_reboot_pending() -> bool {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use windows::{
+        core::PCWSTR,
+        Win32::{
+            Foundation::ERROR_SUCCESS,
+            System::Registry::{RegCloseKey, RegOpenKeyExW, HKEY, HKEY_LOCAL_MACHINE, KEY_READ},
+        },
+    };
+
+    const PENDING_REBOOT_KEYS: [&str; 2] = [
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\Component Based Servicing\RebootPending",
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\WindowsUpdate\Auto Update\RebootRequired",
+    ];
+
+    PENDING_REBOOT_KEYS.iter().any(|key| {
+        let wkey: Vec<u16> = OsStr::new(key).encode_wide().chain([0]).collect();
+        let mut hkey = HKEY::default();
+        let rc = unsafe {
+            RegOpenKeyExW(
+                HKEY_LOCAL_MACHINE,
+                PCWSTR(wkey.as_ptr()),
+                0,
+                KEY_READ,
+                &mut hkey,
+            )
+        };
+        if rc != ERROR_SUCCESS {
+            return false;
+        }
+        let _ = unsafe { RegCloseKey(hkey) };
+        true
+    })
+}
+
+#[cfg(not(windows))]
+fn is_windows_reboot_pending() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use crate::utils;
+    use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
+
+    #[test]
+    fn disabled_by_default() -> io::Result<()> {
+        let renderer = ModuleRenderer::new("reboot_required");
+        let reboot_required = renderer.root_path().join("var/run/reboot-required");
+        fs::create_dir_all(reboot_required.parent().unwrap())?;
+        utils::write_file(&reboot_required, "*** System restart required ***\n")?;
+
+        assert_eq!(None, renderer.collect());
+        Ok(())
+    }
+
+    #[test]
+    fn no_reboot_required() {
+        let actual = ModuleRenderer::new("reboot_required")
+            .config(toml::toml! {
+                [reboot_required]
+                disabled = false
+            })
+            .collect();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn debian_reboot_required() -> io::Result<()> {
+        let renderer = ModuleRenderer::new("reboot_required").config(toml::toml! {
+            [reboot_required]
+            disabled = false
+            format = "[$symbol \\($packages\\)]($style)"
+        });
+        let run_dir = renderer.root_path().join("var/run");
+        fs::create_dir_all(&run_dir)?;
+        utils::write_file(
+            run_dir.join("reboot-required"),
+            "*** System restart required ***\n",
+        )?;
+        utils::write_file(
+            run_dir.join("reboot-required.pkgs"),
+            "linux-image-6.5.0-14-generic\nlibc6\nlibc6\n",
+        )?;
+
+        let expected = Some(format!(
+            "{}",
+            Color::Yellow
+                .bold()
+                .paint("↻ reboot (linux-image-6.5.0-14-generic, libc6)")
+        ));
+        assert_eq!(expected, renderer.collect());
+        Ok(())
+    }
+
+    #[test]
+    fn needs_restarting() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let renderer = ModuleRenderer::new("reboot_required")
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .cmd("needs-restarting -r", None)
+            .config(toml::toml! {
+                [reboot_required]
+                disabled = false
+            });
+        let needs_restarting = renderer.root_path().join("usr/bin/needs-restarting");
+        fs::create_dir_all(needs_restarting.parent().unwrap())?;
+        utils::write_file(&needs_restarting, "")?;
+
+        let expected = Some(format!("{} ", Color::Yellow.bold().paint("↻ reboot")));
+        assert_eq!(expected, renderer.collect());
+        cache_dir.close()
+    }
+}
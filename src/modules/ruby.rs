@@ -2,6 +2,10 @@ use super::{Context, Module, ModuleConfig};
 
 use crate::configs::ruby::RubyConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils::{parse_pinned_version, satisfies_pinned_version};
+use once_cell::sync::Lazy;
+use std::cmp::Ordering;
+use std::path::Path;
 
 /// Creates a module with the current Ruby version
 ///
@@ -16,8 +20,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_rb_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_extensions(&config.detect_extensions)
         .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     let is_rb_env = &config
@@ -29,22 +36,43 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
+    let ruby_version = Lazy::new(|| {
+        context
+            .exec_cmd("ruby", &["-v"])
+            .map(|output| output.stdout)
+    });
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
                 "symbol" => Some(config.symbol),
+                "bundler" if context.get_env("BUNDLE_GEMFILE").is_some() => {
+                    Some(config.bundler_symbol)
+                }
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" if config.mismatch_style.is_empty() => Some(Ok(config.style)),
+                "style" => {
+                    let is_mismatch = ruby_version
+                        .as_deref()
+                        .and_then(parse_ruby_version)
+                        .zip(get_required_ruby_version(context))
+                        .map_or(false, |(installed, required)| {
+                            !satisfies_ruby_requirement(installed, &required)
+                        });
+                    Some(Ok(if is_mismatch {
+                        config.mismatch_style
+                    } else {
+                        config.style
+                    }))
+                }
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => format_ruby_version(
-                    &context.exec_cmd("ruby", &["-v"])?.stdout,
-                    config.version_format,
-                )
-                .map(Ok),
+                "version" => {
+                    format_ruby_version(ruby_version.as_deref()?, config.version_format).map(Ok)
+                }
+                "gemset" => get_gemset(context).map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -61,8 +89,77 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
-fn format_ruby_version(ruby_version: &str, version_format: &str) -> Option<String> {
-    let version = ruby_version
+/// Returns the current gemset of rvm, e.g. `rails7` of `~/.rvm/gems/ruby-3.2.2@rails7`
+fn get_gemset(context: &Context) -> Option<String> {
+    let gem_home = context.get_env("GEM_HOME")?;
+    let gem_dir = Path::new(&gem_home).file_name()?.to_str()?;
+    let (_ruby, gemset) = gem_dir.split_once('@')?;
+    Some(gemset.to_string())
+}
+
+/// Reads the Ruby version the project requires from `.ruby-version`, or else from the `ruby`
+/// directive of the `Gemfile`
+fn get_required_ruby_version(context: &Context) -> Option<String> {
+    if let Some(contents) = context.read_file_from_pwd(".ruby-version") {
+        let version = contents.trim().trim_start_matches("ruby-");
+        if let Some(version) = parse_pinned_version(version) {
+            return Some(version.to_string());
+        }
+    }
+
+    let gemfile = context.read_file_from_pwd("Gemfile")?;
+    gemfile.lines().find_map(|line| {
+        let directive = line.trim().strip_prefix("ruby")?;
+        if !directive.starts_with([' ', '(']) {
+            return None;
+        }
+        let requirement = directive.split(['"', '\'']).nth(1)?;
+        requirement
+            .contains(|c: char| c.is_ascii_digit())
+            .then(|| requirement.trim().to_string())
+    })
+}
+
+/// Whether the `installed` version satisfies a Gemfile requirement such as `3.2.2`, `~> 3.2` or
+/// `>= 3.1`
+fn satisfies_ruby_requirement(installed: &str, requirement: &str) -> bool {
+    let version_start = requirement
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or_default();
+    let (operator, version) = requirement.split_at(version_start);
+    let ordering = compare_versions(installed, version);
+
+    match operator.trim() {
+        "" | "=" => satisfies_pinned_version(installed, version),
+        "~>" => {
+            let prefix = version.rsplit_once('.').map_or("", |(prefix, _)| prefix);
+            ordering.is_ge() && (prefix.is_empty() || satisfies_pinned_version(installed, prefix))
+        }
+        ">=" => ordering.is_ge(),
+        ">" => ordering.is_gt(),
+        "<=" => ordering.is_le(),
+        "<" => ordering.is_lt(),
+        "!=" => !satisfies_pinned_version(installed, version),
+        _ => true,
+    }
+}
+
+fn compare_versions(left: &str, right: &str) -> Ordering {
+    let parts = |version: &str| -> Vec<u64> {
+        version
+            .split('.')
+            .map(|part| part.parse().unwrap_or_default())
+            .collect()
+    };
+    let (mut left, mut right) = (parts(left), parts(right));
+    let len = left.len().max(right.len());
+    left.resize(len, 0);
+    right.resize(len, 0);
+    left.cmp(&right)
+}
+
+fn parse_ruby_version(ruby_version: &str) -> Option<&str> {
+    ruby_version
         // split into ["ruby", "2.6.0p0", "linux/amd64"]
         .split_whitespace()
         // return "2.6.0p0"
@@ -70,7 +167,11 @@ fn format_ruby_version(ruby_version: &str, version_format: &str) -> Option<Strin
         // split into ["2.6.0", "0"]
         .split('p')
         // return "2.6.0"
-        .next()?;
+        .next()
+}
+
+fn format_ruby_version(ruby_version: &str, version_format: &str) -> Option<String> {
+    let version = parse_ruby_version(ruby_version)?;
 
     match VersionFormatter::format_version(version, version_format) {
         Ok(formatted) => Some(formatted),
@@ -85,8 +186,8 @@ fn format_ruby_version(ruby_version: &str, version_format: &str) -> Option<Strin
 mod tests {
     use super::*;
     use crate::test::ModuleRenderer;
-    use nu_ansi_term::Color;
-    use std::fs::File;
+    use nu_ansi_term::{Color, Style};
+    use std::fs::{self, File};
     use std::io;
 
     #[test]
@@ -188,4 +289,65 @@ mod tests {
             Some("v2.7.0".to_string())
         );
     }
+
+    #[test]
+    fn test_satisfies_ruby_requirement() {
+        assert!(satisfies_ruby_requirement("3.2.2", "3.2.2"));
+        assert!(satisfies_ruby_requirement("3.2.2", "3.2"));
+        assert!(!satisfies_ruby_requirement("3.1.4", "3.2.2"));
+        assert!(satisfies_ruby_requirement("3.3.0", "~> 3.2"));
+        assert!(!satisfies_ruby_requirement("4.0.0", "~> 3.2"));
+        assert!(!satisfies_ruby_requirement("3.3.0", "~> 3.2.1"));
+        assert!(satisfies_ruby_requirement("3.2.0", ">= 3.1"));
+        assert!(!satisfies_ruby_requirement("3.0.6", ">= 3.1"));
+        assert!(satisfies_ruby_requirement("2.7.8", "< 3"));
+    }
+
+    #[test]
+    fn gemset_and_bundler() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("Gemfile"))?.sync_all()?;
+
+        let actual = ModuleRenderer::new("ruby")
+            .path(dir.path())
+            .env("GEM_HOME", "/home/user/.rvm/gems/ruby-2.5.1@rails7")
+            .env(
+                "BUNDLE_GEMFILE",
+                dir.path().join("Gemfile").to_str().unwrap(),
+            )
+            .config(toml::toml! {
+                [ruby]
+                format = "$bundler$version@$gemset"
+                bundler_symbol = "b "
+            })
+            .collect();
+        assert_eq!(Some("b v2.5.1@rails7".to_string()), actual);
+        dir.close()
+    }
+
+    #[test]
+    fn gemfile_requirement_mismatch() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let render = |gemfile: &str| -> io::Result<Option<String>> {
+            fs::write(dir.path().join("Gemfile"), gemfile)?;
+            Ok(ModuleRenderer::new("ruby")
+                .path(dir.path())
+                .config(toml::toml! {
+                    [ruby]
+                    format = "[$version]($style)"
+                    mismatch_style = "yellow"
+                })
+                .collect())
+        };
+
+        let expected = |style: Style| Some(format!("{}", style.paint("v2.5.1")));
+        let gemfile = "source \"https://rubygems.org\"\n\nruby \"~> 3.2\"\n";
+        assert_eq!(expected(Color::Yellow.normal()), render(gemfile)?);
+        let gemfile = "source \"https://rubygems.org\"\n\nruby '~> 2.5'\n";
+        assert_eq!(expected(Color::Red.bold()), render(gemfile)?);
+
+        fs::write(dir.path().join(".ruby-version"), "ruby-2.4.10\n")?;
+        assert_eq!(expected(Color::Yellow.normal()), render(gemfile)?);
+        dir.close()
+    }
 }
@@ -9,7 +9,7 @@ use super::{Context, Module, ModuleConfig};
 
 use crate::configs::rust::RustConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
-use crate::utils::create_command;
+use crate::utils::{create_command, parse_pinned_version, satisfies_pinned_version};
 use home::rustup_home;
 
 use once_cell::sync::OnceCell;
@@ -93,12 +93,35 @@ impl RustToolingEnvironmentInfo {
     fn get_rustup_rustc_version(&self, context: &Context) -> &RustupRunRustcVersionOutcome {
         self.rustup_rustc_output.get_or_init(|| {
             let out = if let Some(toolchain) = self.get_env_toolchain_override(context) {
-                // First try runnig ~/.rustup/toolchains/<toolchain>/bin/rustc --version
+                let toolchain_dir = rustup_home().ok().and_then(|rustup_folder| {
+                    let settings_host_triple =
+                        self.get_rustup_settings(context).default_host_triple();
+                    let host_triple = if settings_host_triple.is_none() {
+                        guess_host_triple()
+                    } else {
+                        settings_host_triple
+                    };
+                    find_toolchain_dir(&rustup_folder.join("toolchains"), toolchain, host_triple)
+                });
+
+                // Installed toolchains record their version in the channel manifest, which
+                // saves spawning rustc (slow on Windows) for every prompt
+                if let Some(version) = toolchain_dir
+                    .as_deref()
+                    .and_then(read_manifest_rustc_version)
+                {
+                    log::trace!(
+                        "Read rustc version from the manifest of {:?}",
+                        toolchain_dir
+                    );
+                    return RustupRunRustcVersionOutcome::RustcVersion(version);
+                }
+
+                // Then try running ~/.rustup/toolchains/<toolchain>/bin/rustc --version
                 rustup_home()
                     .map(|rustup_folder| {
-                        rustup_folder
-                            .join("toolchains")
-                            .join(toolchain)
+                        toolchain_dir
+                            .unwrap_or_else(|| rustup_folder.join("toolchains").join(toolchain))
                             .join("bin")
                             .join("rustc")
                     })
@@ -163,8 +186,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_rs_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_extensions(&config.detect_extensions)
         .set_folders(&config.detect_folders)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     if !is_rs_project {
@@ -177,14 +203,29 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         formatter
             .map_meta(|var, _| match var {
                 "symbol" => Some(config.symbol),
+                "overrides" if has_cargo_overrides(context) => Some(config.overrides_symbol),
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" if config.mismatch_style.is_empty() => Some(Ok(config.style)),
+                "style" => Some(Ok(
+                    if is_pinned_version_mismatch(context, &config, &rust_env_info) {
+                        config.mismatch_style
+                    } else {
+                        config.style
+                    },
+                )),
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => get_module_version(context, &config, &rust_env_info).map(Ok),
+                "version" if config.use_pinned_version => get_pinned_version(context, &config)
+                    .or_else(|| get_module_version(context, &config, &rust_env_info))
+                    .map(Ok),
+                "version" | "installed" => {
+                    get_module_version(context, &config, &rust_env_info).map(Ok)
+                }
+                "pinned" => get_pinned_version(context, &config).map(Ok),
+                "workspace_member" => get_workspace_member(context).map(Ok),
                 "numver" => get_module_numeric_version(context, &config, &rust_env_info).map(Ok),
                 "toolchain" => get_toolchain_version(context, &config, &rust_env_info).map(Ok),
                 _ => None,
@@ -224,6 +265,35 @@ fn get_module_version(
     }
 }
 
+/// Reads the version of the toolchain file, without running `rustup` or `rustc`
+fn get_pinned_version(context: &Context, config: &RustConfig) -> Option<String> {
+    let channel = find_rust_toolchain_file(context)?;
+    VersionFormatter::format_module_version(
+        "rust",
+        parse_pinned_version(&channel)?,
+        config.version_format,
+    )
+}
+
+/// Whether the active `rustc` doesn't satisfy the numeric channel pinned in `rust-toolchain`
+fn is_pinned_version_mismatch(
+    context: &Context,
+    config: &RustConfig,
+    rust_env_info: &RustToolingEnvironmentInfo,
+) -> bool {
+    let channel = match find_rust_toolchain_file(context) {
+        Some(channel) => channel,
+        None => return false,
+    };
+    let pinned = match parse_pinned_version(&channel) {
+        Some(pinned) => pinned,
+        None => return false,
+    };
+    get_module_numeric_version(context, config, rust_env_info).map_or(false, |installed| {
+        !satisfies_pinned_version(&installed, pinned)
+    })
+}
+
 fn get_module_numeric_version(
     context: &Context,
     _config: &RustConfig,
@@ -360,6 +430,80 @@ fn find_rust_toolchain_file(context: &Context) -> Option<String> {
     }
 }
 
+/// Returns the name of the current crate when it's a member of a Cargo workspace
+fn get_workspace_member(context: &Context) -> Option<String> {
+    let mut manifests = context.current_dir.ancestors().filter_map(|dir| {
+        let contents = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+        toml::from_str::<toml::Value>(&contents).ok()
+    });
+
+    let manifest = manifests.next()?;
+    let package = manifest.get("package")?;
+    let name = package.get("name")?.as_str()?;
+    // A crate is part of a workspace if its manifest points at one, declares one itself, or
+    // one of the manifests above it does
+    let is_member = package.get("workspace").is_some()
+        || manifest.get("workspace").is_some()
+        || manifests.any(|manifest| manifest.get("workspace").is_some());
+    is_member.then(|| name.to_owned())
+}
+
+/// Whether compiler flags are overridden, either in the environment or in a `.cargo/config.toml`
+/// above the current directory (the global one in `$CARGO_HOME` is ignored)
+fn has_cargo_overrides(context: &Context) -> bool {
+    const FLAG_VARS: [&str; 3] = [
+        "RUSTFLAGS",
+        "CARGO_ENCODED_RUSTFLAGS",
+        "CARGO_BUILD_RUSTFLAGS",
+    ];
+    if FLAG_VARS
+        .iter()
+        .any(|var| context.get_env(var).map_or(false, |val| !val.is_empty()))
+    {
+        return true;
+    }
+
+    let home = context.get_home();
+    context
+        .current_dir
+        .ancestors()
+        .filter(|dir| Some(*dir) != home.as_deref())
+        .any(|dir| {
+            let cargo_dir = dir.join(".cargo");
+            cargo_dir.join("config.toml").is_file() || cargo_dir.join("config").is_file()
+        })
+}
+
+/// Finds the directory of an installed toolchain, whose name may omit the host triple
+/// (`stable` is installed as `stable-x86_64-unknown-linux-gnu`)
+fn find_toolchain_dir(
+    toolchains_dir: &Path,
+    toolchain: &str,
+    host_triple: Option<&str>,
+) -> Option<PathBuf> {
+    let exact = toolchains_dir.join(toolchain);
+    if exact.is_dir() {
+        return Some(exact);
+    }
+    let with_host = toolchains_dir.join(format!("{}-{}", toolchain, host_triple?));
+    with_host.is_dir().then_some(with_host)
+}
+
+/// Reads the `rustc --version` output of a toolchain from the manifest rustup installed it with
+fn read_manifest_rustc_version(toolchain_dir: &Path) -> Option<String> {
+    let manifest_path = toolchain_dir
+        .join("lib")
+        .join("rustlib")
+        .join("multirust-channel-manifest.toml");
+    let manifest: toml::Value = toml::from_str(&fs::read_to_string(manifest_path).ok()?).ok()?;
+    let version = manifest
+        .get("pkg")?
+        .get("rustc")?
+        .get("version")?
+        .as_str()?;
+    Some(format!("rustc {}\n", version))
+}
+
 fn extract_toolchain_from_rustup_run_rustc_version(output: Output) -> RustupRunRustcVersionOutcome {
     if output.status.success() {
         if let Ok(output) = String::from_utf8(output.stdout) {
@@ -505,6 +649,7 @@ mod tests {
     use std::process::{ExitStatus, Output};
 
     use super::*;
+    use crate::test::ModuleRenderer;
 
     #[test]
     fn test_rustup_settings_from_toml_value() {
@@ -891,6 +1036,78 @@ version = "12"
         dir.close()
     }
 
+    #[test]
+    fn test_read_toolchain_manifest() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let toolchain_dir = dir.path().join("stable-x86_64-unknown-linux-gnu");
+        let rustlib_dir = toolchain_dir.join("lib").join("rustlib");
+        fs::create_dir_all(&rustlib_dir)?;
+        fs::write(
+            rustlib_dir.join("multirust-channel-manifest.toml"),
+            "manifest-version = \"2\"\n\n[pkg.rustc]\nversion = \"1.70.0 (90c541806 2023-05-31)\"\n",
+        )?;
+
+        let found = find_toolchain_dir(dir.path(), "stable", Some("x86_64-unknown-linux-gnu"));
+        assert_eq!(found.as_deref(), Some(toolchain_dir.as_path()));
+        assert_eq!(
+            find_toolchain_dir(dir.path(), "stable-x86_64-unknown-linux-gnu", None).as_deref(),
+            Some(toolchain_dir.as_path())
+        );
+        assert_eq!(find_toolchain_dir(dir.path(), "nightly", None), None);
+
+        let version = read_manifest_rustc_version(&toolchain_dir);
+        assert_eq!(
+            version.as_deref(),
+            Some("rustc 1.70.0 (90c541806 2023-05-31)\n")
+        );
+        assert_eq!(
+            format_rustc_version(&version.unwrap(), "v${raw}"),
+            Some("v1.70.0".to_string())
+        );
+        assert_eq!(read_manifest_rustc_version(dir.path()), None);
+        dir.close()
+    }
+
+    #[test]
+    fn test_workspace_member_and_overrides() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let member_dir = dir.path().join("crates").join("app");
+        fs::create_dir_all(&member_dir)?;
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )?;
+        fs::write(
+            member_dir.join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )?;
+
+        let render = |path: &Path, rustflags: &str| {
+            ModuleRenderer::new("rust")
+                .path(path)
+                .env("RUSTFLAGS", rustflags)
+                .config(toml::toml! {
+                    [rust]
+                    format = "$workspace_member$overrides"
+                    overrides_symbol = "+"
+                })
+                .collect()
+        };
+
+        assert_eq!(render(&member_dir, ""), Some("app".to_string()));
+        assert_eq!(
+            render(&member_dir, "-Ctarget-cpu=native"),
+            Some("app+".to_string())
+        );
+        // The workspace root isn't a member itself
+        assert_eq!(render(dir.path(), ""), None);
+
+        fs::create_dir(dir.path().join(".cargo"))?;
+        fs::write(dir.path().join(".cargo").join("config.toml"), "")?;
+        assert_eq!(render(&member_dir, ""), Some("app+".to_string()));
+        dir.close()
+    }
+
     #[test]
     fn test_format_rustc_version_verbose() {
         macro_rules! test {
@@ -1,8 +1,13 @@
 use super::{Context, Module, ModuleConfig, Shell};
 
 use crate::configs::shell::ShellConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
+/// Creates a module with the currently used shell
+///
+/// The shell version, as well as whether it is a login or a restricted shell, are detected
+/// once by the init script and passed as the `--shell-version`, `--login-shell` and
+/// `--restricted-shell` prompt arguments
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("shell");
     let config: ShellConfig = ShellConfig::try_load(module.config);
@@ -12,6 +17,20 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     }
 
     let shell = &context.shell;
+    let is_login = context.properties.login_shell;
+    let is_restricted = context.properties.restricted_shell;
+    let version = context
+        .properties
+        .shell_version
+        .as_deref()
+        .filter(|version| !version.is_empty())
+        .and_then(|version| {
+            VersionFormatter::format_module_version(
+                module.get_name(),
+                version,
+                config.version_format,
+            )
+        });
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -26,9 +45,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     Shell::Tcsh => Some(config.tcsh_indicator),
                     Shell::Nu => Some(config.nu_indicator),
                     Shell::Xonsh => Some(config.xonsh_indicator),
+                    Shell::Murex => Some(config.murex_indicator),
+                    Shell::Osh => Some(config.osh_indicator),
+                    Shell::Ysh => Some(config.ysh_indicator),
+                    Shell::Rc => Some(config.rc_indicator),
                     Shell::Cmd => Some(config.cmd_indicator),
                     Shell::Unknown => Some(config.unknown_indicator),
                 },
+                "login" => is_login.then_some(config.login_symbol),
+                "restricted" => is_restricted.then_some(config.restricted_symbol),
                 _ => None,
             })
             .map_style(|variable| match variable {
@@ -44,8 +69,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "elvish_indicator" => Some(Ok(config.elvish_indicator)),
                 "tcsh_indicator" => Some(Ok(config.tcsh_indicator)),
                 "xonsh_indicator" => Some(Ok(config.xonsh_indicator)),
+                "murex_indicator" => Some(Ok(config.murex_indicator)),
+                "osh_indicator" => Some(Ok(config.osh_indicator)),
+                "ysh_indicator" => Some(Ok(config.ysh_indicator)),
+                "rc_indicator" => Some(Ok(config.rc_indicator)),
                 "cmd_indicator" => Some(Ok(config.cmd_indicator)),
                 "unknown_indicator" => Some(Ok(config.unknown_indicator)),
+                "version" => version.as_deref().map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -316,6 +346,65 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_murex_default_format() {
+        let expected = Some(format!("{} ", Color::White.bold().paint("murex")));
+        let actual = ModuleRenderer::new("shell")
+            .shell(Shell::Murex)
+            .config(toml::toml! {
+                [shell]
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_murex_custom_format() {
+        let expected = Some(format!("{} ", Color::Cyan.bold().paint("mx")));
+        let actual = ModuleRenderer::new("shell")
+            .shell(Shell::Murex)
+            .config(toml::toml! {
+                [shell]
+                murex_indicator = "[mx](bold cyan)"
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_oils_default_format() {
+        for (shell, indicator) in [(Shell::Osh, "osh"), (Shell::Ysh, "ysh")] {
+            let expected = Some(format!("{} ", Color::White.bold().paint(indicator)));
+            let actual = ModuleRenderer::new("shell")
+                .shell(shell)
+                .config(toml::toml! {
+                    [shell]
+                    disabled = false
+                })
+                .collect();
+
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn test_rc_default_format() {
+        let expected = Some(format!("{} ", Color::White.bold().paint("rc")));
+        let actual = ModuleRenderer::new("shell")
+            .shell(Shell::Rc)
+            .config(toml::toml! {
+                [shell]
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn test_cmd_default_format() {
         let expected = Some(format!("{} ", Color::White.bold().paint("cmd")));
@@ -407,4 +496,68 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_version() {
+        let expected = Some("bsh v5.2.15".to_string());
+        let actual = ModuleRenderer::new("shell")
+            .shell(Shell::Bash)
+            .shell_version("5.2.15")
+            .config(toml::toml! {
+                [shell]
+                format = "$indicator( $version)"
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_version_unknown() {
+        let expected = Some("zsh".to_string());
+        let actual = ModuleRenderer::new("shell")
+            .shell(Shell::Zsh)
+            .config(toml::toml! {
+                [shell]
+                format = "$indicator( $version)"
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_login_and_restricted() {
+        let expected = Some("-bshr".to_string());
+        let actual = ModuleRenderer::new("shell")
+            .shell(Shell::Bash)
+            .login_shell(true)
+            .restricted_shell(true)
+            .config(toml::toml! {
+                [shell]
+                format = "$login$indicator$restricted"
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_not_login() {
+        let expected = Some("fsh".to_string());
+        let actual = ModuleRenderer::new("shell")
+            .shell(Shell::Fish)
+            .login_shell(false)
+            .config(toml::toml! {
+                [shell]
+                format = "$login$indicator$restricted"
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(expected, actual);
+    }
 }
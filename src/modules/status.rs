@@ -59,26 +59,54 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     // Create pipestatus string
     let pipestatus = match pipestatus_status {
-        PipeStatusStatus::Pipe(pipestatus) => pipestatus
-            .iter()
-            .enumerate()
-            .filter_map(|(i, ec)| {
-                format_exit_code(
-                    ec.as_str(),
-                    if i == pipestatus.len() - 1 {
-                        segment_format
-                    } else {
-                        &segment_format_with_separator
-                    },
-                    None,
-                    &config,
-                    context,
-                )
-                .ok()
-                .map(|segments| segments.into_iter().map(|s| s.to_string()))
-            })
-            .flatten()
-            .collect::<String>(),
+        PipeStatusStatus::Pipe(pipestatus) => {
+            let last = pipestatus.len() - 1;
+            let collapse = config.pipestatus_collapse && pipestatus.len() > 2;
+
+            let format_segment = |ec: &str, format: &str| {
+                format_exit_code(ec, format, None, &config, context)
+                    .ok()
+                    .map(|segments| segments.into_iter().map(|s| s.to_string()))
+            };
+
+            if collapse {
+                // Keep the first and last element, and summarize the ones in between,
+                // styled after the first one that failed.
+                let middle = &pipestatus[1..last];
+                let middle_ec = middle.iter().find(|ec| *ec != "0").unwrap_or(&middle[0]);
+                let collapse_format = [
+                    config.pipestatus_collapse_symbol,
+                    config.pipestatus_separator,
+                ]
+                .join("");
+
+                [
+                    format_segment(&pipestatus[0], &segment_format_with_separator),
+                    format_segment(middle_ec, &collapse_format),
+                    format_segment(&pipestatus[last], segment_format),
+                ]
+                .into_iter()
+                .flatten()
+                .flatten()
+                .collect::<String>()
+            } else {
+                pipestatus
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, ec)| {
+                        format_segment(
+                            ec.as_str(),
+                            if i == last {
+                                segment_format
+                            } else {
+                                &segment_format_with_separator
+                            },
+                        )
+                    })
+                    .flatten()
+                    .collect::<String>()
+            }
+        }
         _ => String::new(),
     };
 
@@ -125,6 +153,11 @@ fn format_exit_code<'a>(
     let signal_number = raw_signal_number.map(|sn| sn.to_string());
     let signal_name =
         raw_signal_number.and_then(|sn| status_signal_name(sn).or(signal_number.as_deref()));
+    // The full name of the signal, e.g. `SIGSEGV`
+    let description = match raw_signal_number {
+        Some(sn) => status_signal_name(sn).map(|name| format!("SIG{name}")),
+        None => status_description(exit_code_int).map(ToString::to_string),
+    };
 
     // If not a signal and not a common meaning, it should at least print the raw exit code number
     let maybe_exit_code_number = match common_meaning.is_none() && signal_name.is_none() {
@@ -153,7 +186,10 @@ fn format_exit_code<'a>(
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" => match (exit_code_int, config.success_style) {
+                    (0, Some(success_style)) => Some(Ok(success_style)),
+                    _ => Some(Ok(config.style)),
+                },
                 _ => None,
             })
             .map(|variable| match variable {
@@ -164,6 +200,7 @@ fn format_exit_code<'a>(
                 "common_meaning" => Ok(common_meaning).transpose(),
                 "signal_number" => Ok(signal_number.as_deref()).transpose(),
                 "signal_name" => Ok(signal_name).transpose(),
+                "description" => Ok(description.as_deref()).transpose(),
                 "pipestatus" => {
                     let pipestatus = pipestatus.unwrap_or_else(|| {
                         // We might enter this case if pipestatus hasn't
@@ -227,6 +264,71 @@ fn status_signal_name(signal: SignalNumber) -> Option<&'static str> {
         20 => Some("TSTP"),   // 128 + 20
         21 => Some("TTIN"),   // 128 + 21
         22 => Some("TTOU"),   // 128 + 22
+        23 => Some("URG"),    // 128 + 23
+        24 => Some("XCPU"),   // 128 + 24
+        25 => Some("XFSZ"),   // 128 + 25
+        26 => Some("VTALRM"), // 128 + 26
+        27 => Some("PROF"),   // 128 + 27
+        28 => Some("WINCH"),  // 128 + 28
+        29 => Some("IO"),     // 128 + 29
+        30 => Some("PWR"),    // 128 + 30
+        31 => Some("SYS"),    // 128 + 31
+        _ => None,
+    }
+}
+
+/// Name of a non-signal exit code: the `sysexits.h` constants, and the most common
+/// Windows `NTSTATUS` and `HRESULT` values (which show up as negative 32-bit codes)
+fn status_description(ex: ExitCode) -> Option<&'static str> {
+    // `NTSTATUS` and `HRESULT` values are listed as unsigned hex
+    match ex as u32 {
+        1 => Some("EXIT_FAILURE"),
+        64 => Some("EX_USAGE"),
+        65 => Some("EX_DATAERR"),
+        66 => Some("EX_NOINPUT"),
+        67 => Some("EX_NOUSER"),
+        68 => Some("EX_NOHOST"),
+        69 => Some("EX_UNAVAILABLE"),
+        70 => Some("EX_SOFTWARE"),
+        71 => Some("EX_OSERR"),
+        72 => Some("EX_OSFILE"),
+        73 => Some("EX_CANTCREAT"),
+        74 => Some("EX_IOERR"),
+        75 => Some("EX_TEMPFAIL"),
+        76 => Some("EX_PROTOCOL"),
+        77 => Some("EX_NOPERM"),
+        78 => Some("EX_CONFIG"),
+        0x4000_0015 => Some("STATUS_FATAL_APP_EXIT"),
+        0x8000_0003 => Some("STATUS_BREAKPOINT"),
+        0x8000_4001 => Some("E_NOTIMPL"),
+        0x8000_4002 => Some("E_NOINTERFACE"),
+        0x8000_4003 => Some("E_POINTER"),
+        0x8000_4004 => Some("E_ABORT"),
+        0x8000_4005 => Some("E_FAIL"),
+        0x8000_FFFF => Some("E_UNEXPECTED"),
+        0x8007_0002 => Some("ERROR_FILE_NOT_FOUND"),
+        0x8007_0005 => Some("E_ACCESSDENIED"),
+        0x8007_0006 => Some("E_HANDLE"),
+        0x8007_000E => Some("E_OUTOFMEMORY"),
+        0x8007_0057 => Some("E_INVALIDARG"),
+        0xC000_0005 => Some("STATUS_ACCESS_VIOLATION"),
+        0xC000_0006 => Some("STATUS_IN_PAGE_ERROR"),
+        0xC000_0017 => Some("STATUS_NO_MEMORY"),
+        0xC000_001D => Some("STATUS_ILLEGAL_INSTRUCTION"),
+        0xC000_0022 => Some("STATUS_ACCESS_DENIED"),
+        0xC000_008C => Some("STATUS_ARRAY_BOUNDS_EXCEEDED"),
+        0xC000_008E => Some("STATUS_FLOAT_DIVIDE_BY_ZERO"),
+        0xC000_0094 => Some("STATUS_INTEGER_DIVIDE_BY_ZERO"),
+        0xC000_0095 => Some("STATUS_INTEGER_OVERFLOW"),
+        0xC000_0096 => Some("STATUS_PRIVILEGED_INSTRUCTION"),
+        0xC000_00FD => Some("STATUS_STACK_OVERFLOW"),
+        0xC000_0135 => Some("STATUS_DLL_NOT_FOUND"),
+        0xC000_0139 => Some("STATUS_ENTRYPOINT_NOT_FOUND"),
+        0xC000_013A => Some("STATUS_CONTROL_C_EXIT"),
+        0xC000_0142 => Some("STATUS_DLL_INIT_FAILED"),
+        0xC000_0374 => Some("STATUS_HEAP_CORRUPTION"),
+        0xC000_0409 => Some("STATUS_STACK_BUFFER_OVERRUN"),
+        0xC000_0417 => Some("STATUS_INVALID_CRUNTIME_PARAMETER"),
         _ => None,
     }
 }
@@ -749,4 +851,140 @@ mod tests {
             .collect();
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn pipestatus_success_style() {
+        let pipe_exit_code = &[0, 1];
+        let main_exit_code = 1;
+
+        let expected = Some(format!(
+            "{}{}{}",
+            Color::Green.paint("0"),
+            Color::Green.paint("|"),
+            Color::Red.bold().paint("1"),
+        ));
+        let actual = ModuleRenderer::new("status")
+            .config(toml::toml! {
+                [status]
+                success_style = "green"
+                pipestatus = true
+                pipestatus_separator = "[|]($style)"
+                pipestatus_format = "$pipestatus"
+                pipestatus_segment_format = "[$status]($style)"
+                disabled = false
+            })
+            .status(main_exit_code)
+            .pipestatus(pipe_exit_code)
+            .collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn pipestatus_collapse() {
+        let pipe_exit_code = &[0, 0, 2, 0, 1];
+        let main_exit_code = 1;
+
+        let expected = Some(format!(
+            "{}|{}|{}",
+            Color::Green.paint("0"),
+            Color::Red.bold().paint("…"),
+            Color::Red.bold().paint("1"),
+        ));
+        let actual = ModuleRenderer::new("status")
+            .config(toml::toml! {
+                [status]
+                success_style = "green"
+                pipestatus = true
+                pipestatus_collapse = true
+                pipestatus_collapse_symbol = "[…]($style)"
+                pipestatus_format = "$pipestatus"
+                pipestatus_segment_format = "[$status]($style)"
+                disabled = false
+            })
+            .status(main_exit_code)
+            .pipestatus(pipe_exit_code)
+            .collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn pipestatus_collapse_short_pipeline() {
+        let pipe_exit_code = &[0, 1];
+        let main_exit_code = 1;
+
+        let expected = Some("0|1".to_string());
+        let actual = ModuleRenderer::new("status")
+            .config(toml::toml! {
+                [status]
+                pipestatus = true
+                pipestatus_collapse = true
+                pipestatus_format = "$pipestatus"
+                pipestatus_segment_format = "$status"
+                disabled = false
+            })
+            .status(main_exit_code)
+            .pipestatus(pipe_exit_code)
+            .collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn description() {
+        let exit_values = [
+            (1, "EXIT_FAILURE"),
+            (64, "EX_USAGE"),
+            (78, "EX_CONFIG"),
+            (134, "SIGIOT"),
+            (139, "SIGSEGV"),
+            (159, "SIGSYS"),
+            (-1_073_741_819, "STATUS_ACCESS_VIOLATION"),
+            (-1_073_741_571, "STATUS_STACK_OVERFLOW"),
+            (3_221_225_477, "STATUS_ACCESS_VIOLATION"),
+            (-2_147_467_259, "E_FAIL"),
+        ];
+
+        for (status, description) in exit_values {
+            let expected = Some(description.to_string());
+            let actual = ModuleRenderer::new("status")
+                .config(toml::toml! {
+                    [status]
+                    format = "$description"
+                    disabled = false
+                })
+                .status(status)
+                .collect();
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn description_unknown() {
+        for status in [3, 127, 200] {
+            let expected = Some(status.to_string());
+            let actual = ModuleRenderer::new("status")
+                .config(toml::toml! {
+                    [status]
+                    format = "$status( $description)"
+                    disabled = false
+                })
+                .status(status)
+                .collect();
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn description_without_signal_recognition() {
+        let expected = Some("139".to_string());
+        let actual = ModuleRenderer::new("status")
+            .config(toml::toml! {
+                [status]
+                format = "$status( $description)"
+                recognize_signal_code = false
+                disabled = false
+            })
+            .status(139)
+            .collect();
+        assert_eq!(expected, actual);
+    }
 }
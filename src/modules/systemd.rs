@@ -0,0 +1,200 @@
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::systemd::SystemdConfig;
+use crate::formatter::StringFormatter;
+use crate::utils::{cached_output, parse_refresh_interval};
+use serde_json as json;
+
+/// Creates a module with the number of failed systemd units
+///
+/// The module is only shown when at least one unit has failed.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("systemd");
+    let config = SystemdConfig::try_load(module.config);
+
+    // As we default to disabled=true, we have to check here after loading our config module,
+    // before it was only checking against whatever is in the config starship.toml
+    if config.disabled {
+        return None;
+    }
+
+    let mut failed_units =
+        parse_failed_units(&list_failed_units_cached(context, &config, "system")?);
+    if config.user_units {
+        if let Some(output) = list_failed_units_cached(context, &config, "user") {
+            failed_units.extend(parse_failed_units(&output));
+        }
+    }
+    if failed_units.is_empty() {
+        return None;
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "failed" => Some(Ok(failed_units.len().to_string())),
+                "units" => Some(Ok(failed_units.join(", "))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `systemd`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Runs `systemctl --failed` for the `system` or `user` manager, reusing its output for
+/// `refresh_interval`
+fn list_failed_units_cached(
+    context: &Context,
+    config: &SystemdConfig,
+    manager: &str,
+) -> Option<String> {
+    let refresh_interval = parse_refresh_interval(config.refresh_interval).unwrap_or_default();
+    cached_output(
+        context,
+        &format!("systemd/{manager}"),
+        refresh_interval,
+        || {
+            let manager_flag = format!("--{manager}");
+            let output = context.exec_cmd(
+                "systemctl",
+                &[manager_flag.as_str(), "--failed", "--output=json"],
+            )?;
+            Some(output.stdout)
+        },
+    )
+}
+
+/// Parses the names of the units listed by `systemctl --failed --output=json`, e.g.
+/// `[{"unit":"nginx.service","load":"loaded","active":"failed","sub":"failed",...}]`
+fn parse_failed_units(output: &str) -> Vec<String> {
+    let units: Vec<json::Value> = json::from_str(output).unwrap_or_else(|error| {
+        log::warn!("Unable to parse the output of systemctl: {error}");
+        Vec::new()
+    });
+    units
+        .iter()
+        .filter_map(|unit| Some(unit.get("unit")?.as_str()?.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
+    use nu_ansi_term::Color;
+    use std::io;
+
+    fn systemctl_output(stdout: &str) -> Option<CommandOutput> {
+        Some(CommandOutput {
+            stdout: stdout.to_string(),
+            stderr: String::default(),
+        })
+    }
+
+    const FAILED_UNITS: &str = r#"[{"unit":"nginx.service","load":"loaded","active":"failed","sub":"failed","description":"A high performance web server"},{"unit":"backup.timer","load":"loaded","active":"failed","sub":"failed","description":"Nightly backup"}]"#;
+
+    #[test]
+    fn test_parse_failed_units() {
+        assert_eq!(
+            parse_failed_units(FAILED_UNITS),
+            vec!["nginx.service", "backup.timer"]
+        );
+        assert!(parse_failed_units("[]").is_empty());
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("systemd")
+            .cmd(
+                "systemctl --system --failed --output=json",
+                systemctl_output(FAILED_UNITS),
+            )
+            .collect();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn no_failed_units() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let actual = ModuleRenderer::new("systemd")
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .cmd(
+                "systemctl --system --failed --output=json",
+                systemctl_output("[]"),
+            )
+            .config(toml::toml! {
+                [systemd]
+                disabled = false
+            })
+            .collect();
+        assert_eq!(None, actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    fn failed_system_and_user_units() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let actual = ModuleRenderer::new("systemd")
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .cmd(
+                "systemctl --system --failed --output=json",
+                systemctl_output(FAILED_UNITS),
+            )
+            .cmd(
+                "systemctl --user --failed --output=json",
+                systemctl_output(r#"[{"unit":"syncthing.service"}]"#),
+            )
+            .config(toml::toml! {
+                [systemd]
+                disabled = false
+                user_units = true
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Red.bold().paint("⚠ 3 failed")));
+        assert_eq!(expected, actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    fn reuses_cached_output() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let render = |output| {
+            ModuleRenderer::new("systemd")
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .cmd("systemctl --system --failed --output=json", output)
+                .config(toml::toml! {
+                    [systemd]
+                    disabled = false
+                    format = "$units"
+                })
+                .collect()
+        };
+        assert_eq!(
+            Some("nginx.service, backup.timer".to_string()),
+            render(systemctl_output(FAILED_UNITS))
+        );
+        assert_eq!(
+            Some("nginx.service, backup.timer".to_string()),
+            render(None)
+        );
+        cache_dir.close()
+    }
+}
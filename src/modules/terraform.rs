@@ -5,6 +5,7 @@ use crate::formatter::StringFormatter;
 use crate::utils;
 
 use crate::formatter::VersionFormatter;
+use once_cell::sync::Lazy;
 use std::io;
 use std::path::PathBuf;
 
@@ -16,14 +17,19 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let is_terraform_project = context
         .try_begin_scan()?
         .set_files(&config.detect_files)
+        .set_files_not(&config.detect_files_not)
         .set_folders(&config.detect_folders)
         .set_extensions(&config.detect_extensions)
+        .set_content(&config.detect_content)
+        .set_scan_ancestors(config.scan_ancestors)
         .is_match();
 
     if !is_terraform_project {
         return None;
     }
 
+    let installed_version = Lazy::new(|| get_terraform_version(context));
+    let pinned_version = Lazy::new(|| get_pinned_terraform_version(context));
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
@@ -31,24 +37,39 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
-                _ => None,
-            })
-            .map(|variable| match variable {
-                "version" => {
-                    let terraform_version = parse_terraform_version(
-                        context.exec_cmd("terraform", &["version"])?.stdout.as_str(),
-                    )?;
-                    VersionFormatter::format_module_version(
-                        module.get_name(),
-                        &terraform_version,
-                        config.version_format,
-                    )
+                "style" if config.mismatch_style.is_empty() => Some(Ok(config.style)),
+                "style" => {
+                    let is_mismatch = pinned_version.as_deref().map_or(false, |pinned| {
+                        installed_version.as_deref().map_or(false, |installed| {
+                            !utils::satisfies_pinned_version(installed, pinned)
+                        })
+                    });
+                    Some(Ok(if is_mismatch {
+                        config.mismatch_style
+                    } else {
+                        config.style
+                    }))
                 }
-                .map(Ok),
-                "workspace" => get_terraform_workspace(context).map(Ok),
                 _ => None,
             })
+            .map(|variable| {
+                let version = match variable {
+                    "version" if config.use_pinned_version => {
+                        pinned_version.as_ref().or(installed_version.as_ref())
+                    }
+                    "version" | "installed" => installed_version.as_ref(),
+                    "pinned" => pinned_version.as_ref(),
+                    "workspace" => return get_terraform_workspace(context).map(Ok),
+                    _ => return None,
+                };
+
+                VersionFormatter::format_module_version(
+                    module.get_name(),
+                    version?,
+                    config.version_format,
+                )
+                .map(Ok)
+            })
             .parse(None, Some(context))
     });
 
@@ -63,6 +84,16 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+fn get_terraform_version(context: &Context) -> Option<String> {
+    parse_terraform_version(context.exec_cmd("terraform", &["version"])?.stdout.as_str())
+}
+
+/// Reads the version of `.terraform-version`, as used by tfenv, without running `terraform`
+fn get_pinned_terraform_version(context: &Context) -> Option<String> {
+    let contents = context.read_file_from_pwd(".terraform-version")?;
+    utils::parse_pinned_version(&contents).map(str::to_owned)
+}
+
 // Determines the currently selected workspace (see https://github.com/hashicorp/terraform/blob/master/command/meta.go for the original implementation)
 fn get_terraform_workspace(context: &Context) -> Option<String> {
     // Workspace can be explicitly overwritten by an env var
@@ -289,4 +320,22 @@ is 0.12.14. You can update by downloading from www.terraform.io/downloads.html
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn folder_with_terraform_version_and_pinned_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join(".terraform-version"), "1.5.2\n")?;
+        File::create(dir.path().join("main.tf"))?.sync_all()?;
+
+        let actual = ModuleRenderer::new("terraform")
+            .path(dir.path())
+            .config(toml::toml! {
+                [terraform]
+                format = "$version $pinned $installed"
+                use_pinned_version = true
+            })
+            .collect();
+        assert_eq!(Some("v1.5.2 v1.5.2 v0.12.14".to_string()), actual);
+        dir.close()
+    }
 }
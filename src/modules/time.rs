@@ -3,6 +3,7 @@ use chrono::{DateTime, FixedOffset, Local, NaiveTime, Utc};
 use super::{Context, Module, ModuleConfig};
 use crate::configs::time::TimeConfig;
 use crate::formatter::StringFormatter;
+use crate::segment::Segment;
 
 /// Outputs the current time
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -30,19 +31,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         time_format
     );
 
-    let formatted_time_string = if config.utc_time_offset != "local" {
-        match create_offset_time_string(Utc::now(), config.utc_time_offset, time_format) {
-            Ok(formatted_string) => formatted_string,
-            Err(_) => {
-                log::warn!(
-                    "Invalid utc_time_offset configuration provided! Falling back to \"local\"."
-                );
-                format_time(time_format, Local::now())
-            }
-        }
-    } else {
-        format_time(time_format, Local::now())
-    };
+    let formatted_time_string = time_in_offset(config.utc_time_offset, time_format);
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -54,6 +43,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "time" => Some(Ok(&formatted_time_string)),
                 _ => None,
             })
+            .map_variables_to_segments(|variable| match variable {
+                "timezones" => format_timezones(context, &config, time_format).map(Ok),
+                _ => None,
+            })
             .parse(None, Some(context))
     });
 
@@ -68,6 +61,67 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Formats the current time at the given offset from UTC, or in local time
+fn time_in_offset(utc_time_offset: &str, time_format: &str) -> String {
+    if utc_time_offset == "local" {
+        return format_time(time_format, Local::now());
+    }
+
+    match create_offset_time_string(Utc::now(), utc_time_offset, time_format) {
+        Ok(formatted_string) => formatted_string,
+        Err(_) => {
+            log::warn!(
+                "Invalid utc_time_offset configuration provided! Falling back to \"local\"."
+            );
+            format_time(time_format, Local::now())
+        }
+    }
+}
+
+/// Renders each of the configured `timezones` with `timezone_format`
+fn format_timezones(
+    context: &Context,
+    config: &TimeConfig,
+    time_format: &str,
+) -> Option<Vec<Segment>> {
+    let mut segments = Vec::new();
+
+    for (i, timezone) in config.timezones.iter().enumerate() {
+        let time = time_in_offset(
+            timezone.utc_time_offset,
+            timezone.time_format.unwrap_or(time_format),
+        );
+        let parsed = StringFormatter::new(config.timezone_format).and_then(|formatter| {
+            formatter
+                .map_style(|variable| match variable {
+                    "style" => Some(Ok(timezone.style.unwrap_or(config.style))),
+                    _ => None,
+                })
+                .map(|variable| match variable {
+                    "label" => Some(Ok(timezone.label)),
+                    "time" => Some(Ok(time.as_str())),
+                    _ => None,
+                })
+                .parse(None, Some(context))
+        });
+
+        match parsed {
+            Ok(zone_segments) => {
+                if i > 0 {
+                    segments.extend(Segment::from_text(None, config.timezone_separator));
+                }
+                segments.extend(zone_segments);
+            }
+            Err(error) => {
+                log::warn!("Error in module `time.timezones`:\n{}", error);
+                return None;
+            }
+        }
+    }
+
+    (!segments.is_empty()).then_some(segments)
+}
+
 fn create_offset_time_string(
     utc_time: DateTime<Utc>,
     utc_time_offset_str: &str,
@@ -158,6 +212,7 @@ mod tests {
     use super::*;
     use crate::test::ModuleRenderer;
     use chrono::offset::TimeZone;
+    use nu_ansi_term::Color;
 
     const FMT_12: &str = "%r";
     const FMT_24: &str = "%T";
@@ -508,4 +563,69 @@ mod tests {
         assert!(actual.starts_with(&col_prefix));
         assert!(actual.ends_with(&col_suffix));
     }
+
+    #[test]
+    fn config_timezones() {
+        let actual = ModuleRenderer::new("time")
+            .config(toml::toml! {
+                [time]
+                disabled = false
+                format = "$timezones"
+                timezone_format = "[$label]($style) $time"
+                timezone_separator = " | "
+                [[time.timezones]]
+                label = "UTC"
+                utc_time_offset = "0"
+                time_format = "%:z"
+                [[time.timezones]]
+                label = "BLR"
+                utc_time_offset = "+5.5"
+                time_format = "%:z"
+                style = "green"
+            })
+            .collect();
+
+        let expected = Some(format!(
+            "{} +00:00 | {} +05:30",
+            Color::Yellow.bold().paint("UTC"),
+            Color::Green.paint("BLR"),
+        ));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn config_timezones_with_local_time() {
+        let actual = ModuleRenderer::new("time")
+            .config(toml::toml! {
+                [time]
+                disabled = false
+                time_format = "local"
+                [[time.timezones]]
+                label = "NYC"
+                utc_time_offset = "-5"
+                time_format = "%:z"
+            })
+            .collect();
+
+        let expected = Some(format!(
+            "at {} {} ",
+            Color::Yellow.bold().paint("local"),
+            Color::Yellow.bold().paint("NYC -05:00"),
+        ));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn config_no_timezones() {
+        let actual = ModuleRenderer::new("time")
+            .config(toml::toml! {
+                [time]
+                disabled = false
+                time_format = "now"
+            })
+            .collect();
+
+        let expected = Some(format!("at {} ", Color::Yellow.bold().paint("now")));
+        assert_eq!(expected, actual);
+    }
 }
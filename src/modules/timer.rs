@@ -0,0 +1,184 @@
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::timer::TimerConfig;
+use crate::formatter::StringFormatter;
+use crate::timer::{now_millis, read_state};
+use crate::utils::render_time;
+
+/// Creates a module with the countdown of the focus timer
+///
+/// Will display the timer if it was started with `starship timer start` and
+/// has not been expired for longer than `expired_display_time`
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("timer");
+    let config = TimerConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let state = read_state(context)?;
+    let now = now_millis();
+
+    let (format, style, remaining) = match state.remaining(now) {
+        // Round up, so that a 25m timer starts counting down from 25m
+        Ok(remaining) => (
+            config.format,
+            config.style,
+            render_time(u128::from((remaining + 999) / 1000 * 1000), false),
+        ),
+        Err(expired_for) => {
+            if config.expired_display_time >= 0 && expired_for > config.expired_display_time as u64
+            {
+                return None;
+            }
+            (
+                config.expired_format,
+                config.expired_style,
+                "0s".to_string(),
+            )
+        }
+    };
+    let progress = match state.duration {
+        0 => 100,
+        duration => (now.saturating_sub(state.start) * 100 / duration).min(100),
+    };
+
+    let parsed = StringFormatter::new(format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "remaining" => Some(Ok(remaining.clone())),
+                "progress" => Some(Ok(format!("{progress}%"))),
+                "label" => state.label.clone().map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `timer`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use crate::timer::{now_millis, TimerState};
+    use crate::utils;
+    use nu_ansi_term::Color;
+    use std::io;
+    use std::path::Path;
+
+    fn write_timer(cache_dir: &Path, started_ago: u64, duration: u64, label: Option<&str>) {
+        let state = TimerState {
+            start: now_millis() - started_ago,
+            duration,
+            label: label.map(ToString::to_string),
+        };
+        utils::write_file(
+            cache_dir.join("timer.toml"),
+            toml::to_string(&state).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn no_timer() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let actual = ModuleRenderer::new("timer")
+            .config(toml::toml! {
+                [timer]
+                disabled = false
+            })
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .collect();
+
+        assert_eq!(None, actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    fn running_timer() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        write_timer(cache_dir.path(), 60_000, 25 * 60_000, Some("writing"));
+
+        let actual = ModuleRenderer::new("timer")
+            .config(toml::toml! {
+                [timer]
+                disabled = false
+            })
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .collect();
+        let expected = Some(format!("{} ", Color::Red.bold().paint("🍅 24m writing")));
+
+        assert_eq!(expected, actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    fn running_timer_progress() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        write_timer(cache_dir.path(), 10 * 60_000, 40 * 60_000, None);
+
+        let actual = ModuleRenderer::new("timer")
+            .config(toml::toml! {
+                [timer]
+                disabled = false
+                format = "$progress( $label)"
+            })
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .collect();
+
+        assert_eq!(Some("25%".to_string()), actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    fn expired_timer() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        write_timer(cache_dir.path(), 26 * 60_000, 25 * 60_000, None);
+
+        let actual = ModuleRenderer::new("timer")
+            .config(toml::toml! {
+                [timer]
+                disabled = false
+            })
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .collect();
+        let expected = Some(format!("{} ", Color::Green.bold().paint("🍅 done")));
+
+        assert_eq!(expected, actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    fn expired_timer_hidden() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        write_timer(cache_dir.path(), 31 * 60_000, 25 * 60_000, None);
+
+        let actual = ModuleRenderer::new("timer")
+            .config(toml::toml! {
+                [timer]
+                disabled = false
+            })
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .collect();
+
+        assert_eq!(None, actual);
+        cache_dir.close()
+    }
+}
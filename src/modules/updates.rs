@@ -0,0 +1,286 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::updates::UpdatesConfig;
+use crate::formatter::StringFormatter;
+use crate::utils::{cached_output, context_path, parse_refresh_interval};
+
+/// The package managers whose pending updates can be counted, along with the binary used to
+/// detect them in `auto` mode
+const MANAGERS: [(&str, &str); 4] = [
+    ("apt", "/usr/bin/apt"),
+    ("dnf", "/usr/bin/dnf"),
+    ("pacman", "/usr/bin/checkupdates"),
+    ("brew", "brew"),
+];
+
+/// Creates a module with the number of pending package updates
+///
+/// The count is read from `file` when it is set, so that it can be refreshed out-of-band (e.g.
+/// by a cron job or systemd timer). Otherwise the package manager is queried and its output is
+/// cached for `refresh_interval`, as listing updates is slow.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("updates");
+    let config = UpdatesConfig::try_load(module.config);
+
+    // As we default to disabled=true, we have to check here after loading our config module,
+    // before it was only checking against whatever is in the config starship.toml
+    if config.disabled {
+        return None;
+    }
+
+    let (manager, count) = if config.file.is_empty() {
+        let manager = resolve_manager(context, config.manager)?;
+        let output = list_updates_cached(context, &config, manager)?;
+        (manager, count_updates(manager, &output))
+    } else {
+        // The file is read from the host, which has its own home directory for `--remote`
+        let path = PathBuf::from(config.file);
+        let path = match path.strip_prefix("~") {
+            Ok(rest) => context.get_home()?.join(rest),
+            Err(_) => path,
+        };
+        let contents = context
+            .host
+            .read_file(&path, None)
+            .map_err(|error| log::debug!("Unable to read {path:?}: {error}"))
+            .ok()?;
+        (
+            config.manager,
+            parse_count_file(&String::from_utf8_lossy(&contents))?,
+        )
+    };
+
+    // Use the display entry with the highest threshold the count has reached
+    let display = config
+        .display
+        .iter()
+        .filter(|display| count as i64 >= display.threshold)
+        .max_by_key(|display| display.threshold)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(display.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "count" => Some(Ok(count.to_string())),
+                "manager" => Some(Ok(manager.to_string())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `updates`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn resolve_manager<'a>(context: &Context, manager: &'a str) -> Option<&'a str> {
+    if manager != "auto" {
+        if !MANAGERS.iter().any(|(name, _)| *name == manager) {
+            log::warn!("Unsupported package manager in module `updates`: {manager}");
+            return None;
+        }
+        return Some(manager);
+    }
+
+    // The managers are looked for on this machine, so the one of a remote host isn't known
+    if !context.is_local() {
+        log::debug!("Set `manager` in module `updates` to show updates on a remote host");
+        return None;
+    }
+    if cfg!(target_os = "macos") {
+        return Some("brew");
+    }
+    MANAGERS
+        .iter()
+        .find(|(_, binary)| context_path(context, binary).exists())
+        .map(|(name, _)| *name)
+}
+
+/// Lists the pending updates of `manager`, reusing the output for `refresh_interval`
+///
+/// The package managers only read their local metadata, so the prompt doesn't wait for the
+/// network. They still take longer than `command_timeout` to list the updates, so they get
+/// their own `timeout`. Failed and timed out runs aren't cached, so that they don't show up as
+/// no updates, and the last successful list is used instead.
+fn list_updates_cached(context: &Context, config: &UpdatesConfig, manager: &str) -> Option<String> {
+    let refresh_interval = parse_refresh_interval(config.refresh_interval).unwrap_or_default();
+    let timeout = Duration::from_millis(config.timeout);
+    cached_output(
+        context,
+        &format!("updates/{manager}"),
+        refresh_interval,
+        || {
+            let output = match manager {
+                "apt" => context.exec_cmd_with_timeout("apt", &["list", "--upgradable"], timeout),
+                "dnf" => context.exec_cmd_with_timeout(
+                    "dnf",
+                    &["list", "--upgrades", "--quiet", "--cacheonly"],
+                    timeout,
+                ),
+                // `checkupdates` exits with 2 when there is nothing to update, which isn't an error
+                "pacman" => context.exec_cmd_with_timeout(
+                    "sh",
+                    &["-c", "checkupdates --nosync; test $? -ne 1"],
+                    timeout,
+                ),
+                "brew" => context.exec_cmd_with_timeout("brew", &["outdated", "--quiet"], timeout),
+                _ => None,
+            }?;
+            Some(output.stdout)
+        },
+    )
+}
+
+/// Counts the packages listed by the package manager, skipping headers
+fn count_updates(manager: &str, output: &str) -> usize {
+    let lines = output.lines().filter(|line| !line.trim().is_empty());
+    match manager {
+        // e.g. `curl/jammy-updates 7.81.0-1ubuntu1.15 amd64 [upgradable from: 7.81.0-1ubuntu1.14]`
+        "apt" => lines
+            .filter(|line| line.contains("[upgradable from"))
+            .count(),
+        // e.g. `Available Upgrades` followed by `curl.x86_64    8.2.1-3.fc39    updates`
+        "dnf" => lines
+            .filter(|line| line.split_whitespace().count() == 3)
+            .count(),
+        _ => lines.count(),
+    }
+}
+
+/// Reads the first number of a file written by an external job
+fn parse_count_file(contents: &str) -> Option<usize> {
+    contents
+        .split_whitespace()
+        .find_map(|word| word.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
+    use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
+
+    #[test]
+    fn test_count_updates() {
+        let apt = "Listing... Done
+curl/jammy-updates 7.81.0-1ubuntu1.15 amd64 [upgradable from: 7.81.0-1ubuntu1.14]
+libcurl4/jammy-updates 7.81.0-1ubuntu1.15 amd64 [upgradable from: 7.81.0-1ubuntu1.14]
+";
+        assert_eq!(count_updates("apt", apt), 2);
+
+        let dnf = "Available Upgrades
+curl.x86_64                     8.2.1-3.fc39                    updates
+";
+        assert_eq!(count_updates("dnf", dnf), 1);
+
+        let pacman = "linux 6.6.1.arch1-1 -> 6.6.2.arch1-1\nmesa 1:23.2.1-2 -> 1:23.3.0-1\n";
+        assert_eq!(count_updates("pacman", pacman), 2);
+        assert_eq!(count_updates("pacman", ""), 0);
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("updates").collect();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn brew_outdated() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let render = |output: &str| {
+            ModuleRenderer::new("updates")
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .cmd(
+                    "brew outdated --quiet",
+                    Some(CommandOutput {
+                        stdout: output.to_string(),
+                        stderr: String::default(),
+                    }),
+                )
+                .config(toml::toml! {
+                    [updates]
+                    disabled = false
+                    manager = "brew"
+                    refresh_interval = "0s"
+                })
+                .collect()
+        };
+
+        let expected = Some(format!("{} ", Color::Yellow.bold().paint("⇡ 2")));
+        assert_eq!(expected, render("git\nnode\n"));
+        assert_eq!(None, render(""));
+        cache_dir.close()
+    }
+
+    #[test]
+    fn failure_is_not_cached() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let render = |output: Option<&str>| {
+            ModuleRenderer::new("updates")
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .cmd(
+                    "brew outdated --quiet",
+                    output.map(|output| CommandOutput {
+                        stdout: output.to_string(),
+                        stderr: String::default(),
+                    }),
+                )
+                .config(toml::toml! {
+                    [updates]
+                    disabled = false
+                    manager = "brew"
+                })
+                .collect()
+        };
+
+        assert_eq!(None, render(None));
+        let expected = Some(format!("{} ", Color::Yellow.bold().paint("⇡ 1")));
+        assert_eq!(expected, render(Some("git\n")));
+        // The successful run is reused for `refresh_interval`
+        assert_eq!(expected, render(None));
+        cache_dir.close()
+    }
+
+    #[test]
+    fn count_from_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let count_file = dir.path().join("updates");
+        fs::write(&count_file, "64\n")?;
+
+        let actual = ModuleRenderer::new("updates")
+            .config(
+                toml::from_str(&format!(
+                    "
+                    [updates]
+                        disabled = false
+                        file = '{}'
+                ",
+                    count_file.to_string_lossy()
+                ))
+                .unwrap(),
+            )
+            .collect();
+        let expected = Some(format!("{} ", Color::Red.bold().paint("⇡ 64")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
@@ -15,6 +15,9 @@ const USERNAME_ENV_VAR: &str = "USERNAME";
 ///     - The current user is root (UID = 0) [1]
 ///     - The current user isn't the same as the one that is logged in (`$LOGNAME` != `$USER`) [2]
 ///     - The user is currently connected as an SSH session (`$SSH_CONNECTION`) [3]
+///
+/// When privileges were elevated through `sudo` or `su`, the original user is
+/// available as `$sudo_user`, and can be prepended to `$user` with `show_sudo_user`
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut username = context.get_env(USERNAME_ENV_VAR)?;
 
@@ -34,6 +37,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
+    let sudo_user = original_user(context, &username);
+    let user = match &sudo_user {
+        Some(sudo_user) if config.show_sudo_user => {
+            format!("{}{}{}", sudo_user, config.sudo_user_separator, username)
+        }
+        _ => username,
+    };
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_style(|variable| match variable {
@@ -48,7 +59,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "user" => Some(Ok(&username)),
+                "user" => Some(Ok(&user)),
+                "sudo_user" => sudo_user.as_ref().map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -64,6 +76,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// The user who elevated their privileges to become `username`, if any
+fn original_user(context: &Context, username: &str) -> Option<String> {
+    context
+        .get_env("SUDO_USER")
+        .or_else(|| context.get_env("LOGNAME"))
+        .filter(|user| user != username)
+}
+
 fn is_login_user(context: &Context, username: &str) -> bool {
     context
         .get_env("LOGNAME")
@@ -71,7 +91,7 @@ fn is_login_user(context: &Context, username: &str) -> bool {
 }
 
 #[cfg(all(target_os = "windows", not(test)))]
-fn is_root_user() -> bool {
+pub fn is_root_user() -> bool {
     use deelevate::{PrivilegeLevel, Token};
     let token = match Token::with_current_process() {
         Ok(token) => token,
@@ -93,16 +113,16 @@ fn is_root_user() -> bool {
 }
 
 #[cfg(all(target_os = "windows", test))]
-fn is_root_user() -> bool {
+pub fn is_root_user() -> bool {
     false
 }
 
 #[cfg(not(target_os = "windows"))]
-fn is_root_user() -> bool {
+pub fn is_root_user() -> bool {
     nix::unistd::geteuid() == nix::unistd::ROOT
 }
 
-fn is_ssh_session(context: &Context) -> bool {
+pub fn is_ssh_session(context: &Context) -> bool {
     let ssh_env = ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"];
     ssh_env.iter().any(|env| context.get_env_os(env).is_some())
 }
@@ -241,4 +261,72 @@ mod tests {
 
         assert_eq!(expected, actual.as_deref());
     }
+
+    #[test]
+    fn sudo_user() {
+        let actual = ModuleRenderer::new("username")
+            .env(super::USERNAME_ENV_VAR, "root")
+            .env("SUDO_USER", "astronaut")
+            .config(toml::toml! {
+                [username]
+                format = "$user from $sudo_user"
+            })
+            .collect();
+        let expected = Some("root from astronaut");
+
+        assert_eq!(expected, actual.as_deref());
+    }
+
+    #[test]
+    fn show_sudo_user() {
+        let actual = ModuleRenderer::new("username")
+            .env(super::USERNAME_ENV_VAR, "root")
+            .env("SUDO_USER", "astronaut")
+            .config(toml::toml! {
+                [username]
+                show_sudo_user = true
+                style_root = ""
+                style_user = ""
+            })
+            .collect();
+        let expected = Some("astronaut→root in ");
+
+        assert_eq!(expected, actual.as_deref());
+    }
+
+    #[test]
+    fn show_su_user() {
+        let actual = ModuleRenderer::new("username")
+            .env(super::USERNAME_ENV_VAR, "root")
+            .env("LOGNAME", "astronaut")
+            .config(toml::toml! {
+                [username]
+                show_sudo_user = true
+                sudo_user_separator = " as "
+                style_root = ""
+                style_user = ""
+            })
+            .collect();
+        let expected = Some("astronaut as root in ");
+
+        assert_eq!(expected, actual.as_deref());
+    }
+
+    #[test]
+    fn show_sudo_user_without_elevation() {
+        let actual = ModuleRenderer::new("username")
+            .env(super::USERNAME_ENV_VAR, "astronaut")
+            .env("SUDO_USER", "astronaut")
+            .config(toml::toml! {
+                [username]
+                show_always = true
+                show_sudo_user = true
+                style_root = ""
+                style_user = ""
+            })
+            .collect();
+        let expected = Some("astronaut in ");
+
+        assert_eq!(expected, actual.as_deref());
+    }
 }
@@ -6,6 +6,9 @@ pub mod directory_win;
 #[cfg(not(target_os = "windows"))]
 pub mod directory_nix;
 
+#[cfg(feature = "nvml")]
+pub mod nvml;
+
 pub mod path;
 
 pub mod truncate;
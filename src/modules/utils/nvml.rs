@@ -0,0 +1,239 @@
+//! Reads the status of NVIDIA GPUs through NVML, the library `nvidia-smi` is built on
+//!
+//! NVML comes with the driver, so it is loaded when the module runs instead of being linked.
+//! Systems without an NVIDIA driver then simply don't have any GPU to show.
+
+use std::ffi::{c_char, c_int, c_uint, c_void, CStr};
+
+#[cfg(windows)]
+const LIBRARY: &[u8] = b"nvml.dll\0";
+#[cfg(not(windows))]
+const LIBRARY: &[u8] = b"libnvidia-ml.so.1\0";
+
+const NVML_SUCCESS: c_int = 0;
+/// `NVML_DEVICE_NAME_V2_BUFFER_SIZE`, which fits the name of any device
+const NAME_BUFFER_SIZE: usize = 96;
+
+type Device = *mut c_void;
+
+#[repr(C)]
+#[derive(Default)]
+struct Utilization {
+    gpu: c_uint,
+    memory: c_uint,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct Memory {
+    total: u64,
+    free: u64,
+    used: u64,
+}
+
+/// Lists the GPUs in the format of `nvidia-smi --format=csv,noheader,nounits`, i.e. a line of
+/// `name, utilization, used MiB, total MiB` per GPU
+///
+/// Returns `None` when NVML can't be loaded or initialized.
+pub fn query() -> Option<String> {
+    query_library(LIBRARY)
+}
+
+fn query_library(name: &[u8]) -> Option<String> {
+    let nvml = Nvml::load(&Library::open(name)?)?;
+
+    // SAFETY: NVML takes no arguments to initialize, and is shut down again below
+    if unsafe { (nvml.init)() } != NVML_SUCCESS {
+        log::debug!("Unable to initialize NVML");
+        return None;
+    }
+    let output = list_devices(&nvml);
+    // SAFETY: NVML was initialized above, and none of its handles are used after this
+    unsafe { (nvml.shutdown)() };
+    output
+}
+
+/// Lists the devices of an initialized NVML
+fn list_devices(nvml: &Nvml) -> Option<String> {
+    let mut count = 0;
+    // SAFETY: `count` is a valid pointer to write the number of devices to
+    if unsafe { (nvml.get_count)(&mut count) } != NVML_SUCCESS {
+        return None;
+    }
+
+    let mut output = String::new();
+    for index in 0..count {
+        let mut device = std::ptr::null_mut();
+        // SAFETY: `index` is below the number of devices, and `device` is a valid pointer to
+        // write the handle to
+        if unsafe { (nvml.get_handle)(index, &mut device) } != NVML_SUCCESS {
+            continue;
+        }
+
+        let mut name = [0 as c_char; NAME_BUFFER_SIZE];
+        let mut utilization = Utilization::default();
+        let mut memory = Memory::default();
+        // SAFETY: `device` is a handle returned by NVML, `name` holds `NAME_BUFFER_SIZE` chars
+        // as NVML is told, and the structs match `nvmlUtilization_t` and `nvmlMemory_t`
+        let status = unsafe {
+            [
+                (nvml.get_name)(device, name.as_mut_ptr(), NAME_BUFFER_SIZE as c_uint),
+                (nvml.get_utilization)(device, &mut utilization),
+                (nvml.get_memory)(device, &mut memory),
+            ]
+        };
+        // Like `nvidia-smi`, leave out the GPUs that don't report their status
+        if status.iter().any(|status| *status != NVML_SUCCESS) {
+            continue;
+        }
+
+        // SAFETY: NVML nul-terminates the name within the buffer on success
+        let name = unsafe { CStr::from_ptr(name.as_ptr()) }.to_string_lossy();
+        output.push_str(&format_device(&name, &utilization, &memory));
+    }
+    Some(output)
+}
+
+/// Formats a device like a line of `nvidia-smi`, with the memory in MiB
+fn format_device(name: &str, utilization: &Utilization, memory: &Memory) -> String {
+    format!(
+        "{}, {}, {}, {}\n",
+        name,
+        utilization.gpu,
+        memory.used / 1024 / 1024,
+        memory.total / 1024 / 1024
+    )
+}
+
+/// The functions of NVML that are used, as declared in `nvml.h`
+struct Nvml {
+    init: unsafe extern "C" fn() -> c_int,
+    shutdown: unsafe extern "C" fn() -> c_int,
+    get_count: unsafe extern "C" fn(*mut c_uint) -> c_int,
+    get_handle: unsafe extern "C" fn(c_uint, *mut Device) -> c_int,
+    get_name: unsafe extern "C" fn(Device, *mut c_char, c_uint) -> c_int,
+    get_utilization: unsafe extern "C" fn(Device, *mut Utilization) -> c_int,
+    get_memory: unsafe extern "C" fn(Device, *mut Memory) -> c_int,
+}
+
+impl Nvml {
+    fn load(library: &Library) -> Option<Self> {
+        // SAFETY: Each type is the signature `nvml.h` declares for the function of that name
+        unsafe {
+            Some(Self {
+                init: library.symbol(b"nvmlInit_v2\0")?,
+                shutdown: library.symbol(b"nvmlShutdown\0")?,
+                get_count: library.symbol(b"nvmlDeviceGetCount_v2\0")?,
+                get_handle: library.symbol(b"nvmlDeviceGetHandleByIndex_v2\0")?,
+                get_name: library.symbol(b"nvmlDeviceGetName\0")?,
+                get_utilization: library.symbol(b"nvmlDeviceGetUtilizationRates\0")?,
+                get_memory: library.symbol(b"nvmlDeviceGetMemoryInfo\0")?,
+            })
+        }
+    }
+}
+
+/// A dynamically loaded library, which stays loaded until starship exits
+struct Library(*mut c_void);
+
+#[cfg(not(windows))]
+extern "C" {
+    fn dlopen(filename: *const c_char, flags: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+}
+
+#[cfg(not(windows))]
+const RTLD_NOW: c_int = 2;
+
+#[cfg(windows)]
+#[link(name = "kernel32")]
+extern "system" {
+    fn LoadLibraryA(filename: *const c_char) -> *mut c_void;
+    fn GetProcAddress(module: *mut c_void, name: *const c_char) -> *mut c_void;
+}
+
+impl Library {
+    /// Loads the library named by the nul-terminated `name`
+    fn open(name: &[u8]) -> Option<Self> {
+        assert_eq!(name.last(), Some(&0), "library names are nul-terminated");
+
+        #[cfg(not(windows))]
+        // SAFETY: `name` is nul-terminated, and loading the library runs no code of starship
+        let handle = unsafe { dlopen(name.as_ptr().cast(), RTLD_NOW) };
+        #[cfg(windows)]
+        // SAFETY: `name` is nul-terminated, and loading the library runs no code of starship
+        let handle = unsafe { LoadLibraryA(name.as_ptr().cast()) };
+
+        if handle.is_null() {
+            log::debug!(
+                "Unable to load {}",
+                String::from_utf8_lossy(&name[..name.len() - 1])
+            );
+            return None;
+        }
+        Some(Self(handle))
+    }
+
+    /// Looks up the function named by the nul-terminated `name`
+    ///
+    /// # Safety
+    ///
+    /// `F` must be a function pointer type that matches the signature of the function.
+    unsafe fn symbol<F: Copy>(&self, name: &[u8]) -> Option<F> {
+        assert_eq!(name.last(), Some(&0), "symbol names are nul-terminated");
+        assert_eq!(
+            std::mem::size_of::<F>(),
+            std::mem::size_of::<*mut c_void>(),
+            "symbols are read as function pointers"
+        );
+
+        #[cfg(not(windows))]
+        // SAFETY: `self.0` is a handle of a loaded library, and `name` is nul-terminated
+        let symbol = unsafe { dlsym(self.0, name.as_ptr().cast()) };
+        #[cfg(windows)]
+        // SAFETY: `self.0` is a handle of a loaded library, and `name` is nul-terminated
+        let symbol = unsafe { GetProcAddress(self.0, name.as_ptr().cast()) };
+
+        if symbol.is_null() {
+            return None;
+        }
+        // SAFETY: `F` is a function pointer of the same size as `symbol`, and the caller
+        // guarantees that its signature matches the function
+        Some(unsafe { std::mem::transmute_copy(&symbol) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_like_nvidia_smi() {
+        let utilization = Utilization { gpu: 42, memory: 7 };
+        let memory = Memory {
+            total: 8192 * 1024 * 1024,
+            free: 6144 * 1024 * 1024,
+            used: 2048 * 1024 * 1024 + 1,
+        };
+
+        assert_eq!(
+            format_device("NVIDIA GeForce RTX 3070", &utilization, &memory),
+            "NVIDIA GeForce RTX 3070, 42, 2048, 8192\n"
+        );
+    }
+
+    #[test]
+    fn missing_library() {
+        assert!(Library::open(b"libstarship-missing.so.1\0").is_none());
+        assert_eq!(query_library(b"libstarship-missing.so.1\0"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn missing_symbol() {
+        // Any library that is loaded already, but isn't NVML
+        let library = Library::open(b"libc.so.6\0").unwrap();
+
+        assert!(Nvml::load(&library).is_none());
+    }
+}
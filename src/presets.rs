@@ -0,0 +1,417 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use nu_ansi_term::Color;
+use sha2::{Digest, Sha256};
+use toml_edit::{Document, Item, Table};
+
+use crate::config;
+use crate::configure;
+use crate::shadow;
+use crate::utils;
+
+/// Installs a preset, so that it can be applied by its name later on
+///
+/// If `sha256` is set, the preset is only installed if its checksum matches.
+pub fn install(source: &str, name: Option<&str>, sha256: Option<&str>) {
+    let content = match read_source(source).and_then(|content| {
+        if let Some(expected) = sha256 {
+            verify_checksum(&content, expected)?;
+        }
+        parse(source, &content)?;
+        Ok(content)
+    }) {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("{err}");
+            process::exit(1);
+        }
+    };
+
+    let name = match name
+        .map(ToString::to_string)
+        .or_else(|| source_name(source))
+    {
+        Some(name) => name,
+        None => {
+            eprintln!("Unable to name the preset from '{source}', please set one with --name");
+            process::exit(1);
+        }
+    };
+    if !is_valid_name(&name) {
+        eprintln!("Invalid preset name '{name}', it can't contain path separators or '..'");
+        process::exit(1);
+    }
+
+    let path = match presets_dir() {
+        Some(dir) => dir.join(format!("{name}.toml")),
+        None => {
+            eprintln!("Unable to find the presets directory");
+            process::exit(1);
+        }
+    };
+
+    if let Err(err) =
+        fs::create_dir_all(path.parent().unwrap_or(&path)).and_then(|_| fs::write(&path, content))
+    {
+        eprintln!("Unable to install the preset: {err}");
+        process::exit(1);
+    }
+
+    println!("Installed preset '{name}' to {}", path.display());
+}
+
+/// Returns the names of the installed presets, in alphabetical order
+pub fn installed() -> Vec<String> {
+    let mut names: Vec<String> = presets_dir()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let name = file_name.to_str()?.strip_suffix(".toml")?;
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// Prints an installed preset
+pub fn print_installed(name: &str) {
+    let path = presets_dir().filter(|_| is_valid_name(name));
+    match path.map(|dir| utils::read_file(dir.join(format!("{name}.toml")))) {
+        Some(Ok(content)) => print!("{content}"),
+        _ => {
+            eprintln!("Unable to read the preset '{name}'");
+            process::exit(1);
+        }
+    }
+}
+
+/// Merges a preset into the current configuration
+///
+/// Options set by the preset replace the current ones, while options that the
+/// preset doesn't set are kept as they are.
+pub fn apply(source: &str) {
+    let preset = load_or_exit(source);
+    let mut doc = configure::get_configuration_edit();
+
+    merge_tables(doc.as_table_mut(), preset.as_table());
+    configure::write_configuration(&doc);
+}
+
+/// Prints the changes that applying a preset would make to the current configuration
+pub fn diff(source: &str) {
+    let preset = load_or_exit(source);
+    let current = configure::get_configuration_edit();
+
+    let changes = diff_tables(current.as_table(), preset.as_table());
+    if changes.is_empty() {
+        println!("The preset doesn't change the current configuration");
+        return;
+    }
+
+    for change in changes {
+        if let Some(old) = change.old {
+            println!(
+                "{}",
+                Color::Red.paint(format!("- {} = {}", change.key, old))
+            );
+        }
+        println!(
+            "{}",
+            Color::Green.paint(format!("+ {} = {}", change.key, change.new))
+        );
+    }
+}
+
+/// The directory installed presets are stored in
+fn presets_dir() -> Option<PathBuf> {
+    presets_dir_for(Path::new(&config::get_config_path()?))
+}
+
+/// `starship/presets` in the directory of the config file at `config_path`
+fn presets_dir_for(config_path: &Path) -> Option<PathBuf> {
+    Some(config_path.parent()?.join("starship").join("presets"))
+}
+
+/// Whether `name` can name a file in the presets directory, without leaving it
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\']) && !name.contains("..")
+}
+
+fn load_or_exit(source: &str) -> Document {
+    match load(source) {
+        Ok(preset) => preset,
+        Err(err) => {
+            eprintln!("{err}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Loads a preset from a URL, a local file, or by the name of a built-in or installed preset
+fn load(source: &str) -> Result<Document, String> {
+    parse(source, &read_source(source)?)
+}
+
+fn read_source(source: &str) -> Result<String, String> {
+    if source.starts_with("https://") || source.starts_with("http://") {
+        download(source)
+    } else if Path::new(source).is_file() {
+        utils::read_file(source).map_err(|err| format!("Unable to read '{source}': {err}"))
+    } else if let Some(content) = shadow::get_preset_content(source) {
+        Ok(String::from_utf8_lossy(content).into_owned())
+    } else {
+        presets_dir()
+            .filter(|_| is_valid_name(source))
+            .map(|dir| dir.join(format!("{source}.toml")))
+            .and_then(|path| utils::read_file(path).ok())
+            .ok_or_else(|| {
+                format!("Unknown preset '{source}', expected a preset name, a file or a URL")
+            })
+    }
+}
+
+fn parse(source: &str, content: &str) -> Result<Document, String> {
+    content
+        .parse::<Document>()
+        .map_err(|err| format!("Unable to parse the preset '{source}':\n{err}"))
+}
+
+fn verify_checksum(content: &str, expected: &str) -> Result<(), String> {
+    let actual = utils::encode_to_hex(&Sha256::digest(content.as_bytes()));
+    if actual.eq_ignore_ascii_case(expected.trim()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Checksum mismatch for the preset, expected sha256 {expected} but got {actual}"
+        ))
+    }
+}
+
+#[cfg(feature = "network")]
+fn download(url: &str) -> Result<String, String> {
+    let output = utils::create_command("curl")
+        .and_then(|mut cmd| cmd.args(["-fsSL", url]).output())
+        .map_err(|err| format!("Unable to run curl to download '{url}': {err}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Unable to download '{url}':\n{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|err| format!("Invalid preset at '{url}': {err}"))
+}
+
+#[cfg(not(feature = "network"))]
+fn download(url: &str) -> Result<String, String> {
+    Err(format!(
+        "Unable to download '{url}', starship was built without the `network` feature"
+    ))
+}
+
+/// Names a preset after the file or URL it comes from
+fn source_name(source: &str) -> Option<String> {
+    let file_name = source.trim_end_matches('/').rsplit(['/', '\\']).next()?;
+    let name = file_name.strip_suffix(".toml").unwrap_or(file_name);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+fn merge_tables(target: &mut Table, source: &Table) {
+    for (key, item) in source.iter() {
+        match (target.get_mut(key).and_then(Item::as_table_mut), item) {
+            (Some(target), Item::Table(source)) => merge_tables(target, source),
+            _ => {
+                target.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Change {
+    key: String,
+    old: Option<String>,
+    new: String,
+}
+
+fn diff_tables(current: &Table, preset: &Table) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_tables_at("", current, preset, &mut changes);
+    changes
+}
+
+fn diff_tables_at(prefix: &str, current: &Table, preset: &Table, changes: &mut Vec<Change>) {
+    for (key, item) in preset.iter() {
+        let path = if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        let old = current.get(key);
+
+        if let Item::Table(preset) = item {
+            let empty = Table::new();
+            let current = old.and_then(Item::as_table).unwrap_or(&empty);
+            diff_tables_at(&path, current, preset, changes);
+            continue;
+        }
+
+        let new = item_to_string(item);
+        let old = old.map(item_to_string);
+        if old.as_ref() != Some(&new) {
+            changes.push(Change {
+                key: path,
+                old,
+                new,
+            });
+        }
+    }
+}
+
+fn item_to_string(item: &Item) -> String {
+    match item {
+        Item::Value(value) => value.clone().decorated("", "").to_string(),
+        _ => item.to_string().trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_dir_follows_the_config() {
+        assert_eq!(
+            presets_dir_for(Path::new("/home/astronaut/.config/starship.toml")),
+            Some(PathBuf::from("/home/astronaut/.config/starship/presets"))
+        );
+        assert_eq!(
+            presets_dir_for(Path::new("/dotfiles/starship.toml")),
+            Some(PathBuf::from("/dotfiles/starship/presets"))
+        );
+    }
+
+    #[test]
+    fn preset_names() {
+        assert!(is_valid_name("tokyo-night"));
+        assert!(is_valid_name("v1.2"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("../starship"));
+        assert!(!is_valid_name(".."));
+        assert!(!is_valid_name("themes/tokyo"));
+        assert!(!is_valid_name("themes\\tokyo"));
+    }
+
+    #[test]
+    fn merge_keeps_unrelated_options() {
+        let mut current = r#"
+format = "$all"
+
+[aws]
+symbol = "A "
+disabled = true
+"#
+        .parse::<Document>()
+        .unwrap();
+        let preset = r#"
+format = "$directory$character"
+
+[aws]
+symbol = "☁️ "
+
+[git_branch]
+symbol = "🌱 "
+"#
+        .parse::<Document>()
+        .unwrap();
+
+        merge_tables(current.as_table_mut(), preset.as_table());
+        let merged = current.to_string().parse::<toml::Value>().unwrap();
+
+        assert_eq!(merged["format"].as_str(), Some("$directory$character"));
+        assert_eq!(merged["aws"]["symbol"].as_str(), Some("☁️ "));
+        assert_eq!(merged["aws"]["disabled"].as_bool(), Some(true));
+        assert_eq!(merged["git_branch"]["symbol"].as_str(), Some("🌱 "));
+    }
+
+    #[test]
+    fn diff_lists_changed_options() {
+        let current = r#"
+[aws]
+symbol = "A "
+disabled = true
+"#
+        .parse::<Document>()
+        .unwrap();
+        let preset = r#"
+[aws]
+symbol = "☁️ "
+disabled = true
+
+[git_branch]
+symbol = "🌱 "
+"#
+        .parse::<Document>()
+        .unwrap();
+
+        let changes = diff_tables(current.as_table(), preset.as_table());
+        assert_eq!(
+            changes,
+            vec![
+                Change {
+                    key: "aws.symbol".to_string(),
+                    old: Some(r#""A ""#.to_string()),
+                    new: r#""☁️ ""#.to_string(),
+                },
+                Change {
+                    key: "git_branch.symbol".to_string(),
+                    old: None,
+                    new: r#""🌱 ""#.to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn loads_builtin_and_file_presets() -> std::io::Result<()> {
+        assert!(load("nerd-font-symbols").is_ok());
+        assert!(load("not-a-preset").is_err());
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("mine.toml");
+        fs::write(&path, "[aws]\nsymbol = 'A '\n")?;
+        let preset = load(path.to_str().unwrap()).unwrap();
+        assert!(preset.as_table().contains_key("aws"));
+
+        fs::write(&path, "not toml")?;
+        assert!(load(path.to_str().unwrap()).is_err());
+        dir.close()
+    }
+
+    #[test]
+    fn verifies_checksums() {
+        let sha256 = "b5bb9d8014a0f9b1d61e21e796d78dccdf1352f23cd32812f4850b878ae4944c";
+
+        assert!(verify_checksum("foo\n", sha256).is_ok());
+        assert!(verify_checksum("foo\n", &sha256.to_uppercase()).is_ok());
+        assert!(verify_checksum("bar\n", sha256).is_err());
+    }
+
+    #[test]
+    fn names_presets_after_their_source() {
+        assert_eq!(source_name("mine.toml"), Some("mine".to_string()));
+        assert_eq!(
+            source_name("https://example.com/presets/tokyo.toml"),
+            Some("tokyo".to_string())
+        );
+        assert_eq!(source_name("/tmp/dir/"), Some("dir".to_string()));
+        assert_eq!(source_name(""), None);
+    }
+}
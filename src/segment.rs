@@ -1,5 +1,7 @@
 use crate::print::{Grapheme, UnicodeWidthGraphemes};
-use nu_ansi_term::{AnsiString, Style};
+use nu_ansi_term::{AnsiString, Color, Style};
+use regex::{NoExpand, Regex};
+use std::borrow::Cow;
 use std::fmt;
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -11,18 +13,46 @@ pub struct TextSegment {
 
     /// The string value of the current segment.
     value: String,
+
+    /// The URL the segment links to, if any.
+    link: Option<String>,
 }
 
 impl TextSegment {
     // Returns the AnsiString of the segment value
     fn ansi_string(&self) -> AnsiString {
+        let value = match &self.link {
+            Some(link) => {
+                format!("{}{}{}", hyperlink_start(link), self.value, HYPERLINK_END).into()
+            }
+            None => Cow::from(&self.value),
+        };
         match self.style {
-            Some(style) => style.paint(&self.value),
-            None => AnsiString::from(&self.value),
+            Some(style) => style.paint(value),
+            None => AnsiString::from(value),
         }
     }
 }
 
+/// Ends a hyperlink, see `hyperlink_start`
+pub const HYPERLINK_END: &str = "\u{1b}]8;;\u{7}";
+
+/// Starts an OSC 8 hyperlink to `url`, which lasts until `HYPERLINK_END`
+///
+/// OSC 8 only allows printable ASCII in the URL, so anything else is percent-encoded. This
+/// also keeps control characters, e.g. from a directory name, from ending the sequence early.
+pub fn hyperlink_start(url: &str) -> String {
+    let mut encoded = String::with_capacity(url.len());
+    for byte in url.bytes() {
+        if (0x20..0x7f).contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    format!("\u{1b}]8;;{encoded}\u{7}")
+}
+
 /// Type that holds fill text with an associated style
 #[derive(Clone)]
 pub struct FillSegment {
@@ -31,25 +61,40 @@ pub struct FillSegment {
 
     /// The string value of the current segment.
     value: String,
+
+    /// The relative widths of the fills on a line, by their position. Fills past its end
+    /// have a weight of 1.
+    weights: Vec<usize>,
 }
 
 impl FillSegment {
+    /// The weight of the fill when it is the `index`th one on its line
+    pub fn weight(&self, index: usize) -> usize {
+        self.weights.get(index).copied().unwrap_or(1)
+    }
+
     // Returns the AnsiString of the segment value, not including its prefix and suffix
     pub fn ansi_string(&self, width: Option<usize>) -> AnsiString {
         let s = match width {
-            Some(w) => self
-                .value
-                .graphemes(true)
-                .cycle()
-                .scan(0usize, |len, g| {
-                    *len += Grapheme(g).width();
-                    if *len <= w {
-                        Some(g)
-                    } else {
-                        None
-                    }
-                })
-                .collect::<String>(),
+            Some(w) => {
+                let mut s = self
+                    .value
+                    .graphemes(true)
+                    .filter(|g| Grapheme(g).width() > 0)
+                    .cycle()
+                    .scan(0usize, |len, g| {
+                        *len += Grapheme(g).width();
+                        if *len <= w {
+                            Some(g)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect::<String>();
+                // A wide symbol may not fit in the last columns, which are left blank
+                s.push_str(&" ".repeat(w - s.width_graphemes()));
+                s
+            }
             None => String::from(&self.value),
         };
         match self.style {
@@ -75,12 +120,15 @@ mod fill_seg_tests {
             ("-:-", "-:--:--:--"),
             ("🟦", "🟦🟦🟦🟦🟦"),
             ("🟢🔵🟡", "🟢🔵🟡🟢🔵"),
+            ("🟦.", "🟦.🟦.🟦. "),
+            ("🇯🇵", "🇯🇵🇯🇵🇯🇵🇯🇵🇯🇵"),
         ];
 
         for (text, expected) in &inputs {
             let f = FillSegment {
                 value: String::from(*text),
                 style: Some(style),
+                weights: Vec::new(),
             };
             let actual = f.ansi_string(Some(width));
             assert_eq!(style.paint(*expected), actual);
@@ -94,6 +142,13 @@ pub enum Segment {
     Text(TextSegment),
     Fill(FillSegment),
     LineTerm,
+    /// Marks where a module starts or ends, for powerline separators to be inserted.
+    /// It is removed before the prompt is printed.
+    Separator,
+    /// Marks where the segments of a module start, for structured output
+    ModuleStart(String),
+    /// Marks where the segments of a module end, for structured output
+    ModuleEnd,
 }
 
 impl Segment {
@@ -110,6 +165,7 @@ impl Segment {
             segs.push(Self::Text(TextSegment {
                 value: String::from(s),
                 style,
+                link: None,
             }))
         });
         segs
@@ -117,12 +173,22 @@ impl Segment {
 
     /// Creates a new fill segment
     pub fn fill<T>(style: Option<Style>, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::weighted_fill(style, value, Vec::new())
+    }
+
+    /// Creates a new fill segment, whose width relative to the other fills on its line
+    /// depends on its position
+    pub fn weighted_fill<T>(style: Option<Style>, value: T, weights: Vec<usize>) -> Self
     where
         T: Into<String>,
     {
         Self::Fill(FillSegment {
             style,
             value: value.into(),
+            weights,
         })
     }
 
@@ -130,7 +196,7 @@ impl Segment {
         match self {
             Self::Fill(fs) => fs.style,
             Self::Text(ts) => ts.style,
-            Self::LineTerm => None,
+            Self::LineTerm | Self::Separator | Self::ModuleStart(_) | Self::ModuleEnd => None,
         }
     }
 
@@ -146,7 +212,57 @@ impl Segment {
                     ts.style = style
                 }
             }
-            Self::LineTerm => {}
+            Self::LineTerm | Self::Separator | Self::ModuleStart(_) | Self::ModuleEnd => {}
+        }
+    }
+
+    pub fn link(&self) -> Option<&str> {
+        match self {
+            Self::Text(ts) => ts.link.as_deref(),
+            Self::Fill(_)
+            | Self::LineTerm
+            | Self::Separator
+            | Self::ModuleStart(_)
+            | Self::ModuleEnd => None,
+        }
+    }
+
+    /// Makes the segment a hyperlink to `url`, unless it already links somewhere
+    pub fn set_link_if_empty(&mut self, url: &str) {
+        if let Self::Text(ts) = self {
+            if ts.link.is_none() && !ts.value.is_empty() {
+                ts.link = Some(url.to_string());
+            }
+        }
+    }
+
+    /// Replaces the matches of `pattern` in the text of the segment, and in its link
+    ///
+    /// The `replacement` is used literally, so a `$` in it doesn't refer to a capture group.
+    pub fn redact(&mut self, pattern: &Regex, replacement: &str) {
+        let (value, link) = match self {
+            Self::Fill(fs) => (&mut fs.value, None),
+            Self::Text(ts) => (&mut ts.value, ts.link.as_mut()),
+            Self::LineTerm | Self::Separator | Self::ModuleStart(_) | Self::ModuleEnd => return,
+        };
+        for text in std::iter::once(value).chain(link) {
+            if pattern.is_match(text) {
+                *text = pattern
+                    .replace_all(text, NoExpand(replacement))
+                    .into_owned();
+            }
+        }
+    }
+
+    /// Sets the background color of the segment, unless it already has one
+    pub fn set_background_if_empty(&mut self, background: Color) {
+        let style = match self {
+            Self::Fill(FillSegment { style, .. }) | Self::Text(TextSegment { style, .. }) => style,
+            Self::LineTerm | Self::Separator | Self::ModuleStart(_) | Self::ModuleEnd => return,
+        };
+        let style = style.get_or_insert_with(Style::new);
+        if style.background.is_none() {
+            style.background = Some(background);
         }
     }
 
@@ -155,6 +271,7 @@ impl Segment {
             Self::Fill(fs) => &fs.value,
             Self::Text(ts) => &ts.value,
             Self::LineTerm => LINE_TERMINATOR_STRING,
+            Self::Separator | Self::ModuleStart(_) | Self::ModuleEnd => "",
         }
     }
 
@@ -164,6 +281,7 @@ impl Segment {
             Self::Fill(fs) => fs.ansi_string(None),
             Self::Text(ts) => ts.ansi_string(),
             Self::LineTerm => AnsiString::from(LINE_TERMINATOR_STRING),
+            Self::Separator | Self::ModuleStart(_) | Self::ModuleEnd => AnsiString::from(""),
         }
     }
 
@@ -171,7 +289,7 @@ impl Segment {
         match self {
             Self::Fill(fs) => fs.value.width_graphemes(),
             Self::Text(ts) => ts.value.width_graphemes(),
-            Self::LineTerm => 0,
+            Self::LineTerm | Self::Separator | Self::ModuleStart(_) | Self::ModuleEnd => 0,
         }
     }
 }
@@ -184,3 +302,31 @@ impl fmt::Display for Segment {
         write!(f, "{}", self.ansi_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hyperlink_start_encodes_control_characters() {
+        assert_eq!(
+            hyperlink_start("file:///tmp/a b"),
+            "\u{1b}]8;;file:///tmp/a b\u{7}"
+        );
+        assert_eq!(
+            hyperlink_start("file:///tmp/\u{7}\u{1b}]0;title\u{7}"),
+            "\u{1b}]8;;file:///tmp/%07%1B]0;title%07\u{7}"
+        );
+        assert_eq!(
+            hyperlink_start("file:///tmp/caf\u{e9}"),
+            "\u{1b}]8;;file:///tmp/caf%C3%A9\u{7}"
+        );
+    }
+
+    #[test]
+    fn redact_replaces_literally() {
+        let mut segments = Segment::from_text(None, "token=hunter2");
+        segments[0].redact(&Regex::new("(hunter)2").unwrap(), "$1$$");
+        assert_eq!(segments[0].value(), "token=$1$$");
+    }
+}
@@ -145,7 +145,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
             .filter(|StructInfo { name, .. }| name == &"StarshipRootConfig")
             .and(self.current_key)
             .map_or(false, |key| {
-                ALL_MODULES.contains(&key) || key == "custom" || key == "env_var"
+                ALL_MODULES.contains(&key) || key == "custom" || key == "plugin" || key == "env_var"
             })
         {
             return visitor.visit_none();
@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use crate::configure;
+use crate::context::{Context, Target};
+
+/// Values kept for a shell session with `starship state set`, shown with `$state.<key>`
+pub type SessionState = BTreeMap<String, String>;
+
+/// The key the prompt mode chosen with `starship mode` is kept under
+pub const PROMPT_MODE_KEY: &str = "prompt_mode";
+
+/// The file holding the state of the current shell session, named after its
+/// `STARSHIP_SESSION_KEY`
+pub fn state_path(context: &Context) -> Option<PathBuf> {
+    let session_key = context.get_env("STARSHIP_SESSION_KEY")?;
+    // The key ends up in a path, so only accept the keys the init scripts generate
+    if session_key.is_empty() || !session_key.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    Some(
+        context
+            .get_cache_dir()?
+            .join("state")
+            .join(format!("{session_key}.toml")),
+    )
+}
+
+/// Reads the state of the current shell session, which is empty until a value is set
+pub fn read_state(context: &Context) -> SessionState {
+    let Some(content) = state_path(context).and_then(|path| fs::read_to_string(path).ok()) else {
+        return SessionState::new();
+    };
+    toml::from_str(&content)
+        .map_err(|err| log::warn!("Unable to parse the session state: {}", err))
+        .unwrap_or_default()
+}
+
+pub fn get(key: &str) {
+    let context = Context::new(Default::default(), Target::Main);
+    match read_state(&context).get(key) {
+        Some(value) => println!("{value}"),
+        None => process::exit(1),
+    }
+}
+
+/// Sets `key` to `value` for the current shell session, or removes it without a value
+pub fn set(key: &str, value: Option<String>) {
+    let context = Context::new(Default::default(), Target::Main);
+    if let Err(err) = write_state(&context, key, value) {
+        eprintln!("Unable to set `{key}`: {err}");
+        process::exit(1);
+    }
+}
+
+/// Switches the prompt to the format of a mode in `prompt_modes` for the current shell
+/// session, or back to `format` without a mode or if the mode is already active
+pub fn mode(name: Option<&str>) {
+    let context = Context::new(Default::default(), Target::Main);
+    if let Err(err) = switch_mode(&context, name) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn switch_mode(context: &Context, name: Option<&str>) -> Result<(), String> {
+    let modes = &context.root_config.prompt_modes;
+    if let Some(name) = name.filter(|name| !modes.contains_key(*name)) {
+        let mut known: Vec<_> = modes.keys().map(String::as_str).collect();
+        known.sort_unstable();
+        return Err(format!(
+            "Unknown prompt mode '{name}', the modes in `prompt_modes` are: {}",
+            known.join(", ")
+        ));
+    }
+
+    update_state(context, |state| {
+        match name.filter(|name| state.get(PROMPT_MODE_KEY).map(String::as_str) != Some(*name)) {
+            Some(name) => state.insert(PROMPT_MODE_KEY.to_string(), name.to_string()),
+            None => state.remove(PROMPT_MODE_KEY),
+        };
+    })
+}
+
+/// Hides a module, or the modules of a group in `[groups]`, for the current shell session,
+/// or shows them again if they are all hidden. Modules are toggled in the config file
+/// instead, unless `session` is set.
+pub fn toggle(name: &str, key: &str, session: bool) {
+    let context = Context::new(Default::default(), Target::Main);
+    if !session && !context.root_config.groups.contains_key(name) {
+        return configure::toggle_configuration(name, key);
+    }
+
+    if let Err(err) = toggle_modules(&context, name, key) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+/// Whether `module` is hidden for the current shell session with `starship toggle`
+pub fn is_hidden(state: &SessionState, module: &str) -> bool {
+    state.contains_key(&hidden_key(module))
+}
+
+fn hidden_key(module: &str) -> String {
+    format!("toggle.{module}")
+}
+
+fn toggle_modules(context: &Context, name: &str, key: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Empty module names are not supported".to_owned());
+    }
+    if key != "disabled" {
+        return Err(format!(
+            "Only 'disabled' can be toggled for a session, not '{key}'"
+        ));
+    }
+
+    let modules = match context.root_config.groups.get(name) {
+        Some(group) => group.clone(),
+        None => vec![name.to_string()],
+    };
+    update_state(context, |state| {
+        let hide = !modules.iter().all(|module| is_hidden(state, module));
+        for module in &modules {
+            if hide {
+                state.insert(hidden_key(module), "hidden".to_string());
+            } else {
+                state.remove(&hidden_key(module));
+            }
+        }
+    })
+}
+
+fn write_state(context: &Context, key: &str, value: Option<String>) -> Result<(), String> {
+    update_state(context, |state| {
+        match value {
+            Some(value) => state.insert(key.to_string(), value),
+            None => state.remove(key),
+        };
+    })
+}
+
+fn update_state(context: &Context, update: impl FnOnce(&mut SessionState)) -> Result<(), String> {
+    let path = state_path(context)
+        .ok_or("STARSHIP_SESSION_KEY is not set, is starship initialized in this shell?")?;
+
+    let mut state = read_state(context);
+    update(&mut state);
+    let content = toml::to_string(&state).map_err(|err| err.to_string())?;
+
+    fs::create_dir_all(path.parent().unwrap_or(&path))
+        .and_then(|_| fs::write(&path, content))
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::default_context;
+    use std::io;
+
+    #[test]
+    fn state_of_the_session() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let mut context = default_context();
+        context
+            .env
+            .insert("STARSHIP_CACHE", cache_dir.path().to_string_lossy().into());
+        context.env.insert("STARSHIP_SESSION_KEY", "abc123".into());
+
+        assert_eq!(read_state(&context), SessionState::new());
+
+        write_state(&context, "mode", Some("focus".to_string())).unwrap();
+        write_state(&context, "count", Some("1".to_string())).unwrap();
+        write_state(&context, "count", None).unwrap();
+        let expected = SessionState::from([("mode".to_string(), "focus".to_string())]);
+        assert_eq!(read_state(&context), expected);
+
+        // Other sessions have a state of their own
+        context.env.insert("STARSHIP_SESSION_KEY", "def456".into());
+        assert_eq!(read_state(&context), SessionState::new());
+
+        cache_dir.close()
+    }
+
+    #[test]
+    fn toggle_modules_and_groups() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let mut context = default_context();
+        context
+            .env
+            .insert("STARSHIP_CACHE", cache_dir.path().to_string_lossy().into());
+        context.env.insert("STARSHIP_SESSION_KEY", "abc123".into());
+        context.root_config.groups.insert(
+            "cloud".to_string(),
+            vec!["aws".to_string(), "gcloud".to_string()],
+        );
+        let hidden = |context: &Context| {
+            let state = read_state(context);
+            ["aws", "gcloud", "rust"].map(|module| is_hidden(&state, module))
+        };
+
+        toggle_modules(&context, "gcloud", "disabled").unwrap();
+        assert_eq!(hidden(&context), [false, true, false]);
+
+        // A group is hidden unless all of its modules already are
+        toggle_modules(&context, "cloud", "disabled").unwrap();
+        assert_eq!(hidden(&context), [true, true, false]);
+        toggle_modules(&context, "cloud", "disabled").unwrap();
+        assert_eq!(hidden(&context), [false, false, false]);
+
+        assert!(toggle_modules(&context, "rust", "format").is_err());
+        assert!(toggle_modules(&context, "", "disabled").is_err());
+
+        cache_dir.close()
+    }
+
+    #[test]
+    fn switch_prompt_modes() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let mut context = default_context();
+        context
+            .env
+            .insert("STARSHIP_CACHE", cache_dir.path().to_string_lossy().into());
+        context.env.insert("STARSHIP_SESSION_KEY", "abc123".into());
+        context.root_config.format = "$all".to_string();
+        context
+            .root_config
+            .prompt_modes
+            .insert("minimal".to_string(), "$character".to_string());
+        let mode = |context: &Context| read_state(context).get(PROMPT_MODE_KEY).cloned();
+
+        assert!(switch_mode(&context, Some("verbose")).is_err());
+        assert_eq!(mode(&context), None);
+
+        switch_mode(&context, Some("minimal")).unwrap();
+        assert_eq!(mode(&context), Some("minimal".to_string()));
+
+        // Switching to the active mode goes back to `format`
+        switch_mode(&context, Some("minimal")).unwrap();
+        assert_eq!(mode(&context), None);
+
+        switch_mode(&context, Some("minimal")).unwrap();
+        switch_mode(&context, None).unwrap();
+        assert_eq!(mode(&context), None);
+
+        switch_mode(&context, Some("minimal")).unwrap();
+        assert_eq!(context.prompt_format(), "$character");
+
+        cache_dir.close()
+    }
+
+    #[test]
+    fn no_state_without_a_session() {
+        let mut context = default_context();
+        assert_eq!(state_path(&context), None);
+
+        context.env.insert("STARSHIP_SESSION_KEY", "../etc".into());
+        assert_eq!(state_path(&context), None);
+        assert!(write_state(&context, "mode", Some("focus".to_string())).is_err());
+    }
+}
@@ -1,4 +1,6 @@
 use crate::context::{Context, Shell, Target};
+use crate::executor::CommandExecutor;
+use crate::host::Host;
 use crate::logger::StarshipLogger;
 use crate::{
     config::{ModuleConfig, StarshipConfig},
@@ -7,8 +9,10 @@ use crate::{
 };
 use log::{Level, LevelFilter};
 use once_cell::sync::Lazy;
-use std::io;
+use std::fs;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tempfile::TempDir;
 
 static FIXTURE_DIR: Lazy<PathBuf> =
@@ -48,6 +52,8 @@ pub fn default_context() -> Context<'static> {
 pub struct ModuleRenderer<'a> {
     name: &'a str,
     context: Context<'a>,
+    // Directories created by the fixture builders, removed when the renderer is dropped
+    fixtures: Vec<TempDir>,
 }
 
 impl<'a> ModuleRenderer<'a> {
@@ -58,7 +64,11 @@ impl<'a> ModuleRenderer<'a> {
 
         let context = default_context();
 
-        Self { name, context }
+        Self {
+            name,
+            context,
+            fixtures: Vec::new(),
+        }
     }
 
     pub fn path<T>(mut self, path: T) -> Self
@@ -97,6 +107,72 @@ impl<'a> ModuleRenderer<'a> {
         self
     }
 
+    /// Adds all the variables to the `env_mocks` of the underlying context, e.g. a snapshot of
+    /// the environment a module is expected to run in
+    pub fn with_env<I, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, V)>,
+        V: Into<String>,
+    {
+        for (key, val) in vars {
+            self.context.env.insert(key, val.into());
+        }
+        self
+    }
+
+    /// Writes a file below `root_path`, the root of the file system modules see,
+    /// creating the directories leading up to it
+    pub fn with_file<P, C>(self, path: P, contents: C) -> Self
+    where
+        P: AsRef<Path>,
+        C: AsRef<[u8]>,
+    {
+        write_fixture(&self.root_path().join(path), contents.as_ref());
+        self
+    }
+
+    /// Writes a file into the current directory, which is a new temporary directory
+    /// unless one was already set with `path` or created by a fixture builder
+    pub fn with_project_file<P, C>(mut self, path: P, contents: C) -> Self
+    where
+        P: AsRef<Path>,
+        C: AsRef<[u8]>,
+    {
+        if self.context.current_dir.as_os_str().is_empty() {
+            let dir = tempfile::tempdir().expect("Unable to create the project directory");
+            self = self.path(dir.path());
+            self.fixtures.push(dir);
+        }
+        write_fixture(&self.context.current_dir.join(path), contents.as_ref());
+        self
+    }
+
+    /// Renders the module inside a clone of the git fixture repository, after `setup` was
+    /// run with the path of the repository, e.g. to check out a branch
+    #[cfg(feature = "git")]
+    pub fn with_git_repo<F>(mut self, setup: F) -> Self
+    where
+        F: FnOnce(&Path) -> io::Result<()>,
+    {
+        let repo = fixture_repo(FixtureProvider::Git).expect("Unable to clone the git fixture");
+        setup(repo.path()).expect("Unable to set up the git fixture");
+        self = self.path(repo.path());
+        self.fixtures.push(repo);
+        self
+    }
+
+    /// Replaces how the underlying context runs commands, e.g. to replay a recording
+    pub fn executor(mut self, executor: impl CommandExecutor + 'static) -> Self {
+        self.context.executor = Arc::new(executor);
+        self
+    }
+
+    /// Replaces the host the underlying context reads the current directory from
+    pub fn host(mut self, host: impl Host + 'static) -> Self {
+        self.context.host = Arc::new(host);
+        self
+    }
+
     /// Adds the command to the `command_mocks` of the underlying context
     pub fn cmd(mut self, key: &'a str, val: Option<CommandOutput>) -> Self {
         self.context.cmd.insert(key, val);
@@ -113,11 +189,32 @@ impl<'a> ModuleRenderer<'a> {
         self
     }
 
+    pub fn stopped_jobs(mut self, stopped_jobs: i64) -> Self {
+        self.context.properties.stopped_jobs = stopped_jobs;
+        self
+    }
+
+    pub fn last_job<T>(mut self, last_job: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.context.properties.last_job = Some(last_job.into());
+        self
+    }
+
     pub fn cmd_duration(mut self, duration: u64) -> Self {
         self.context.properties.cmd_duration = Some(duration.to_string());
         self
     }
 
+    pub fn cmd_name<T>(mut self, cmd_name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.context.properties.cmd_name = Some(cmd_name.into());
+        self
+    }
+
     pub fn keymap<T>(mut self, keymap: T) -> Self
     where
         T: Into<String>,
@@ -126,6 +223,24 @@ impl<'a> ModuleRenderer<'a> {
         self
     }
 
+    pub fn shell_version<T>(mut self, version: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.context.properties.shell_version = Some(version.into());
+        self
+    }
+
+    pub fn login_shell(mut self, login_shell: bool) -> Self {
+        self.context.properties.login_shell = login_shell;
+        self
+    }
+
+    pub fn restricted_shell(mut self, restricted_shell: bool) -> Self {
+        self.context.properties.restricted_shell = restricted_shell;
+        self
+    }
+
     pub fn status(mut self, status: i64) -> Self {
         self.context.properties.status_code = Some(status.to_string());
         self
@@ -161,8 +276,21 @@ impl<'a> ModuleRenderer<'a> {
     }
 }
 
+fn write_fixture(path: &Path, contents: &[u8]) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|err| panic!("Unable to create {parent:?}: {err:?}"));
+    }
+    let mut file =
+        fs::File::create(path).unwrap_or_else(|err| panic!("Unable to create {path:?}: {err:?}"));
+    file.write_all(contents)
+        .and_then(|_| file.sync_all())
+        .unwrap_or_else(|err| panic!("Unable to write {path:?}: {err:?}"));
+}
+
 #[derive(Clone, Copy)]
 pub enum FixtureProvider {
+    #[cfg_attr(not(feature = "git"), allow(dead_code))]
     Git,
     Hg,
 }
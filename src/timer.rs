@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::context::{Context, Target};
+use crate::utils::{parse_duration, render_time};
+
+const TIMER_FILE: &str = "timer.toml";
+
+/// A focus timer started with `starship timer start`, as rendered by the `timer` module
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TimerState {
+    /// When the timer was started, in milliseconds since the UNIX epoch
+    pub start: u64,
+    /// How long the timer runs for, in milliseconds
+    pub duration: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl TimerState {
+    /// Returns the milliseconds left at `now`, or how long ago the timer expired as an error
+    pub fn remaining(&self, now: u64) -> Result<u64, u64> {
+        let end = self.start.saturating_add(self.duration);
+        if now < end {
+            Ok(end - now)
+        } else {
+            Err(now - end)
+        }
+    }
+}
+
+pub fn timer_path(context: &Context) -> Option<PathBuf> {
+    Some(context.get_cache_dir()?.join(TIMER_FILE))
+}
+
+/// Reads the state of the running timer, if any
+pub fn read_state(context: &Context) -> Option<TimerState> {
+    let content = fs::read_to_string(timer_path(context)?).ok()?;
+    toml::from_str(&content)
+        .map_err(|err| log::warn!("Unable to parse the timer state: {}", err))
+        .ok()
+}
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis() as u64)
+}
+
+/// Plain numbers are read as minutes, e.g. `starship timer start 25`
+fn parse_timer_duration(duration: &str) -> Option<Duration> {
+    match duration.trim().parse::<u64>() {
+        Ok(minutes) => minutes
+            .checked_mul(60)
+            .filter(|_| minutes > 0)
+            .map(Duration::from_secs),
+        Err(_) => parse_duration(duration),
+    }
+}
+
+pub fn start(duration: &str, label: Option<String>) {
+    let duration = match parse_timer_duration(duration) {
+        Some(duration) => duration,
+        None => {
+            eprintln!("Invalid timer duration '{duration}', expected e.g. '25m' or '1h30m'");
+            process::exit(1);
+        }
+    };
+
+    let state = TimerState {
+        start: now_millis(),
+        duration: duration.as_millis() as u64,
+        label,
+    };
+
+    if let Err(err) = write_state(&state) {
+        eprintln!("Unable to start the timer: {err}");
+        process::exit(1);
+    }
+
+    println!(
+        "Started a {} timer",
+        render_time(duration.as_millis(), false)
+    );
+}
+
+pub fn stop() {
+    let context = Context::new(Default::default(), Target::Main);
+    let path = match timer_path(&context) {
+        Some(path) if path.exists() => path,
+        _ => {
+            eprintln!("No timer is running");
+            process::exit(1);
+        }
+    };
+
+    if let Err(err) = fs::remove_file(path) {
+        eprintln!("Unable to stop the timer: {err}");
+        process::exit(1);
+    }
+}
+
+fn write_state(state: &TimerState) -> Result<(), String> {
+    let context = Context::new(Default::default(), Target::Main);
+    let path = timer_path(&context).ok_or("Unable to find the cache directory")?;
+    let content = toml::to_string(state).map_err(|err| err.to_string())?;
+
+    fs::create_dir_all(path.parent().unwrap_or(&path))
+        .and_then(|_| fs::write(&path, content))
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaining_time() {
+        let state = TimerState {
+            start: 1_000,
+            duration: 5_000,
+            label: None,
+        };
+
+        assert_eq!(state.remaining(1_000), Ok(5_000));
+        assert_eq!(state.remaining(4_500), Ok(1_500));
+        assert_eq!(state.remaining(6_000), Err(0));
+        assert_eq!(state.remaining(9_000), Err(3_000));
+    }
+
+    #[test]
+    fn timer_durations() {
+        assert_eq!(
+            parse_timer_duration("25"),
+            Some(Duration::from_secs(25 * 60))
+        );
+        assert_eq!(
+            parse_timer_duration("1h30m"),
+            Some(Duration::from_secs(90 * 60))
+        );
+        assert_eq!(parse_timer_duration("0"), None);
+        assert_eq!(parse_timer_duration(&u64::MAX.to_string()), None);
+    }
+}
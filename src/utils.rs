@@ -1,14 +1,20 @@
+use once_cell::sync::Lazy;
 use process_control::{ChildExt, Control};
-use std::ffi::OsStr;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::ffi::{OsStr, OsString};
 use std::fmt::Debug;
 use std::fs::read_to_string;
 use std::io::{Error, ErrorKind, Result};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 use crate::context::Context;
 use crate::context::Shell;
+use crate::formatter::string_formatter::shell_prompt_escape;
 
 /// Create a `PathBuf` from an absolute path, where the root directory will be mocked in test
 #[cfg(not(test))]
@@ -48,6 +54,27 @@ pub fn read_file<P: AsRef<Path> + Debug>(file_name: P) -> Result<String> {
     result
 }
 
+/// Replaces the contents of a file, creating its directory if needed
+///
+/// The contents are written to a temporary file next to it first, which is then renamed, so
+/// that other shells reading the file at the same time never see it half written.
+pub fn write_file_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(file_name: P, contents: C) -> Result<()> {
+    let file_name = file_name.as_ref();
+    if let Some(dir) = file_name.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut temp_name = file_name.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_file = file_name.with_file_name(temp_name);
+    std::fs::write(&temp_file, contents)
+        .and_then(|_| std::fs::rename(&temp_file, file_name))
+        .map_err(|error| {
+            let _ = std::fs::remove_file(&temp_file);
+            error
+        })
+}
+
 /// Write a string to a file
 #[cfg(test)]
 pub fn write_file<P: AsRef<Path>, S: AsRef<str>>(file_name: P, text: S) -> Result<()> {
@@ -91,6 +118,135 @@ pub fn get_command_string_output(command: CommandOutput) -> String {
     }
 }
 
+/// The binaries `create_command` could not find in PATH, shown by `starship doctor`
+static MISSING_COMMANDS: Lazy<Mutex<BTreeSet<String>>> = Lazy::new(Default::default);
+
+/// The names of the commands that were run, but could not be found in PATH
+pub fn missing_commands() -> Vec<String> {
+    MISSING_COMMANDS
+        .lock()
+        .map(|missing| missing.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Where the binaries run by `create_command` were found, for the `PATH` they were looked up in
+///
+/// Looking a binary up in PATH probes every directory, and on Windows every extension in
+/// `PATHEXT` too, which is slow enough there to show in the prompt. The prompt runs many
+/// commands, so each name is only looked up once, and on Windows the directories in PATH are
+/// listed once instead of probed for each name.
+#[derive(Default)]
+struct Executables {
+    path: Option<OsString>,
+    found: HashMap<OsString, Option<PathBuf>>,
+    #[cfg(windows)]
+    listing: std::sync::Arc<once_cell::sync::OnceCell<HashMap<String, PathBuf>>>,
+}
+
+static EXECUTABLES: Lazy<Mutex<Executables>> = Lazy::new(Default::default);
+
+/// Finds `binary_name` in PATH, reusing what was found before
+///
+/// `EXECUTABLES` is only locked to read and update what was found, so that modules running in
+/// parallel don't wait for each other's lookups.
+fn find_executable(binary_name: &OsStr) -> Option<PathBuf> {
+    // Paths are looked up as they are, PATH isn't searched for them
+    if Path::new(binary_name).components().count() > 1 {
+        return which::which(binary_name).ok();
+    }
+
+    let path = env::var_os("PATH");
+    #[cfg(windows)]
+    let listing;
+    {
+        let mut executables = EXECUTABLES.lock().ok()?;
+        if executables.path != path {
+            *executables = Executables {
+                path: path.clone(),
+                ..Executables::default()
+            };
+        }
+        if let Some(found) = executables.found.get(binary_name) {
+            return found.clone();
+        }
+        #[cfg(windows)]
+        {
+            listing = std::sync::Arc::clone(&executables.listing);
+        }
+    }
+
+    #[cfg(not(windows))]
+    let found = which::which(binary_name).ok();
+    #[cfg(windows)]
+    let found = {
+        // The listing is built once, and only the lookups that need it wait for it
+        let listing = listing.get_or_init(|| {
+            let extensions = env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into());
+            let dirs: Vec<_> = path.iter().flat_map(env::split_paths).collect();
+            list_executables(&dirs, &extensions)
+        });
+        listing
+            .get(&binary_name.to_string_lossy().to_lowercase())
+            .cloned()
+    };
+
+    if let Ok(mut executables) = EXECUTABLES.lock() {
+        if executables.path == path {
+            executables
+                .found
+                .insert(binary_name.to_owned(), found.clone());
+        }
+    }
+    found
+}
+
+/// The executables in `dirs` by their lowercase name, with and without their extension
+///
+/// Only files with an extension in `extensions` (as in `PATHEXT`) are executables. The
+/// directories that come first in PATH win, and within a directory the extensions that come
+/// first in `extensions` do, e.g. `node.exe` over `node.cmd`.
+#[cfg(any(windows, test))]
+fn list_executables(dirs: &[PathBuf], extensions: &str) -> HashMap<String, PathBuf> {
+    let extensions: Vec<String> = extensions
+        .split(';')
+        .filter(|extension| !extension.is_empty())
+        .map(|extension| extension.trim_start_matches('.').to_lowercase())
+        .collect();
+    let mut listing: HashMap<String, ((usize, usize), PathBuf)> = HashMap::new();
+    for (dir_index, dir) in dirs.iter().enumerate() {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.filter_map(std::result::Result::ok) {
+            let path = entry.path();
+            let extension_index = path.extension().and_then(|extension| {
+                let extension = extension.to_string_lossy().to_lowercase();
+                extensions.iter().position(|known| *known == extension)
+            });
+            let rank = match extension_index {
+                Some(extension_index) if !path.is_dir() => (dir_index, extension_index),
+                _ => continue,
+            };
+
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_lowercase());
+            for name in stem.into_iter().chain(std::iter::once(name)) {
+                let is_better = listing.get(&name).map_or(true, |(best, _)| rank < *best);
+                if is_better {
+                    listing.insert(name, (rank, path.clone()));
+                }
+            }
+        }
+    }
+    listing
+        .into_iter()
+        .map(|(name, (_, path))| (name, path))
+        .collect()
+}
+
 /// Attempt to resolve `binary_name` from and creates a new `Command` pointing at it
 /// This allows executing cmd files on Windows and prevents running executable from cwd on Windows
 /// This function also initializes std{err,out,in} to protect against processes changing the console mode
@@ -98,14 +254,20 @@ pub fn create_command<T: AsRef<OsStr>>(binary_name: T) -> Result<Command> {
     let binary_name = binary_name.as_ref();
     log::trace!("Creating Command for binary {:?}", binary_name);
 
-    let full_path = match which::which(binary_name) {
-        Ok(full_path) => {
+    let full_path = match find_executable(binary_name) {
+        Some(full_path) => {
             log::trace!("Using {:?} as {:?}", full_path, binary_name);
             full_path
         }
-        Err(error) => {
-            log::trace!("Unable to find {:?} in PATH, {:?}", binary_name, error);
-            return Err(Error::new(ErrorKind::NotFound, error));
+        None => {
+            log::trace!("Unable to find {:?} in PATH", binary_name);
+            if let Ok(mut missing) = MISSING_COMMANDS.lock() {
+                missing.insert(binary_name.to_string_lossy().to_string());
+            }
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("{binary_name:?} was not found in PATH"),
+            ));
         }
     };
 
@@ -118,7 +280,7 @@ pub fn create_command<T: AsRef<OsStr>>(binary_name: T) -> Result<Command> {
     Ok(cmd)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandOutput {
     pub stdout: String,
     pub stderr: String,
@@ -130,7 +292,7 @@ impl PartialEq for CommandOutput {
     }
 }
 
-#[cfg(test)]
+/// The command line of a command, as `mock_cmd` and recordings know it
 pub fn display_command<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
     cmd: T,
     args: &[U],
@@ -405,6 +567,10 @@ Target: x86_64-apple-darwin19.4.0\n",
             stdout: String::from("0.6.0\n"),
             stderr: String::default(),
         }),
+        "meson --version" => Some(CommandOutput {
+            stdout: String::from("1.2.3\n"),
+            stderr: String::default(),
+        }),
         "cmake --version" => Some(CommandOutput {
             stdout: String::from(
                 "\
@@ -435,11 +601,41 @@ CMake suite maintained and supported by Kitware (kitware.com/cmake).\n",
     Some(out)
 }
 
-/// Wraps ANSI color escape sequences in the shell-appropriate wrappers.
+/// Wraps ANSI color escape sequences, and OSC 8 hyperlinks, in the shell-appropriate wrappers.
 pub fn wrap_colorseq_for_shell(ansi: String, shell: Shell) -> String {
     const ESCAPE_BEGIN: char = '\u{1b}';
     const ESCAPE_END: char = 'm';
-    wrap_seq_for_shell(ansi, shell, ESCAPE_BEGIN, ESCAPE_END)
+    // Hyperlinks end with BEL rather than `m`, and the URL in between may contain an `m`
+    const OSC_BEGIN: &str = "\u{1b}]";
+    const OSC_END: char = '\u{7}';
+
+    let mut wrapped = String::with_capacity(ansi.len());
+    let mut rest = ansi.as_str();
+    while let Some(start) = rest.find(OSC_BEGIN) {
+        let end = rest[start..]
+            .find(OSC_END)
+            .map_or(rest.len(), |end| start + end + OSC_END.len_utf8());
+        wrapped.push_str(&wrap_seq_for_shell(
+            rest[..start].to_string(),
+            shell,
+            ESCAPE_BEGIN,
+            ESCAPE_END,
+        ));
+        wrapped.push_str(&wrap_seq_for_shell(
+            shell_prompt_escape(&rest[start..end], shell),
+            shell,
+            ESCAPE_BEGIN,
+            OSC_END,
+        ));
+        rest = &rest[end..];
+    }
+    wrapped.push_str(&wrap_seq_for_shell(
+        rest.to_string(),
+        shell,
+        ESCAPE_BEGIN,
+        ESCAPE_END,
+    ));
+    wrapped
 }
 
 /// Many shells cannot deal with raw unprintable characters and miscompute the cursor position,
@@ -457,6 +653,8 @@ pub fn wrap_seq_for_shell(
     const ZSH_END: &str = "\u{25}\u{7d}"; // %}
     const TCSH_BEG: &str = "\u{25}\u{7b}"; // %{
     const TCSH_END: &str = "\u{25}\u{7d}"; // %}
+    const READLINE_BEG: &str = "\u{1}"; // \001
+    const READLINE_END: &str = "\u{2}"; // \002
 
     // ANSI escape codes cannot be nested, so we can keep track of whether we're
     // in an escape or not with a single boolean variable
@@ -467,17 +665,19 @@ pub fn wrap_seq_for_shell(
             if x == escape_begin && !escaped {
                 escaped = true;
                 match shell {
-                    Shell::Bash => format!("{BASH_BEG}{escape_begin}"),
+                    Shell::Bash | Shell::Osh | Shell::Ysh => format!("{BASH_BEG}{escape_begin}"),
                     Shell::Zsh => format!("{ZSH_BEG}{escape_begin}"),
                     Shell::Tcsh => format!("{TCSH_BEG}{escape_begin}"),
+                    Shell::Rc => format!("{READLINE_BEG}{escape_begin}"),
                     _ => x.to_string(),
                 }
             } else if x == escape_end && escaped {
                 escaped = false;
                 match shell {
-                    Shell::Bash => format!("{escape_end}{BASH_END}"),
+                    Shell::Bash | Shell::Osh | Shell::Ysh => format!("{escape_end}{BASH_END}"),
                     Shell::Zsh => format!("{escape_end}{ZSH_END}"),
                     Shell::Tcsh => format!("{escape_end}{TCSH_END}"),
+                    Shell::Rc => format!("{escape_end}{READLINE_END}"),
                     _ => x.to_string(),
                 }
             } else {
@@ -589,6 +789,109 @@ pub fn render_time(raw_millis: u128, show_millis: bool) -> String {
     rendered_components.join("")
 }
 
+/// Parses durations like `30s`, `25m` or `1h30m`, and rejects the ones too long to count in
+/// seconds
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for c in value.trim().chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let seconds = digits.parse::<u64>().ok()?.checked_mul(unit)?;
+        total = total.checked_add(seconds)?;
+        digits.clear();
+    }
+
+    (digits.is_empty() && total > 0).then(|| Duration::from_secs(total))
+}
+
+/// Parses the `refresh_interval` option of a module, warning about invalid ones
+pub fn parse_refresh_interval(refresh_interval: &str) -> Option<Duration> {
+    let interval = parse_duration(refresh_interval);
+    if interval.is_none() {
+        log::warn!(
+            "Invalid refresh_interval {:?}, expected e.g. '30s' or '5m'",
+            refresh_interval
+        );
+    }
+    interval
+}
+
+/// Runs `run`, reusing its output for `interval` by caching it as `key` in the starship cache
+/// directory, e.g. `systemd/user`
+///
+/// Failed runs, for which `run` returns `None`, aren't cached. The last cached output is
+/// returned instead, if there is one.
+pub fn cached_output(
+    context: &Context,
+    key: &str,
+    interval: Duration,
+    run: impl FnOnce() -> Option<String>,
+) -> Option<String> {
+    let path = match context.get_cache_dir() {
+        Some(cache_dir) => cache_dir.join(key),
+        None => return run(),
+    };
+
+    let is_fresh = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .map_or(false, |age| age < interval);
+    if is_fresh {
+        if let Ok(output) = read_to_string(&path) {
+            log::trace!("Using the cached output of {key}");
+            return Some(output);
+        }
+    }
+
+    match run() {
+        Some(output) => {
+            if let Err(error) = write_file_atomic(&path, &output) {
+                log::warn!("Unable to cache the output of {key}: {error}");
+            }
+            Some(output)
+        }
+        None => read_to_string(&path).ok(),
+    }
+}
+
+/// Parses the version pinned in a toolchain file like `.nvmrc`, which is on its first line.
+/// Aliases and names of toolchains, like `lts/*`, `stable` or `system`, aren't versions.
+pub fn parse_pinned_version(contents: &str) -> Option<&str> {
+    let version = contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))?;
+    let version = version.strip_prefix('v').unwrap_or(version);
+
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then_some(version)
+}
+
+/// Whether the `installed` version satisfies a `pinned` one, which can leave out the minor
+/// or patch version: `3.11` is satisfied by `3.11.4`, but not by `3.10.2`
+pub fn satisfies_pinned_version(installed: &str, pinned: &str) -> bool {
+    let installed = installed.trim().trim_start_matches('v');
+    let mut installed_parts = installed.split(['.', '-', '+']);
+
+    pinned
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .all(|part| installed_parts.next() == Some(part))
+}
+
 /// Render a single component of the time string, giving an empty string if component is zero
 fn render_time_component((component, suffix): (&u128, &&str)) -> String {
     match component {
@@ -605,6 +908,34 @@ const HEXTABLE: &[char] = &[
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f',
 ];
 
+/// Returns the `file://` URL of a path on `host`, percent-encoding the characters that
+/// can't be part of a URL. An empty `host` stands for the local machine.
+pub fn file_url(host: &str, path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let path = if cfg!(windows) {
+        path.replace('\\', "/")
+    } else {
+        path.into_owned()
+    };
+
+    let mut url = format!("file://{host}");
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b':' | b'-' | b'_' | b'.' | b'~' => {
+                url.push(char::from(byte));
+            }
+            _ => {
+                url.push('%');
+                url.push_str(&encode_to_hex(&[byte]).to_uppercase());
+            }
+        }
+    }
+    url
+}
+
 /// Encode a u8 slice into a hexadecimal string.
 pub fn encode_to_hex(slice: &[u8]) -> String {
     // let mut j = 0;
@@ -738,6 +1069,37 @@ mod tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn test_hyperlink_wrappers() {
+        let link = "\x1b[31m\x1b]8;;file:///home/m%20n\x07dir\x1b]8;;\x07\x1b[0m";
+
+        assert_eq!(
+            wrap_colorseq_for_shell(link.to_string(), Shell::Zsh),
+            "%{\x1b[31m%}%{\x1b]8;;file:///home/m%%20n\x07%}dir%{\x1b]8;;\x07%}%{\x1b[0m%}"
+        );
+        assert_eq!(
+            wrap_colorseq_for_shell(link.to_string(), Shell::Bash),
+            "\\[\x1b[31m\\]\\[\x1b]8;;file:///home/m%20n\x07\\]dir\\[\x1b]8;;\x07\\]\\[\x1b[0m\\]"
+        );
+        assert_eq!(wrap_colorseq_for_shell(link.to_string(), Shell::Fish), link);
+    }
+
+    #[test]
+    fn test_file_url() {
+        if cfg!(windows) {
+            assert_eq!(
+                file_url("", Path::new(r"C:\Users\a b")),
+                "file:///C:/Users/a%20b"
+            );
+        } else {
+            assert_eq!(
+                file_url("", Path::new("/home/a b/100%")),
+                "file:///home/a%20b/100%25"
+            );
+            assert_eq!(file_url("box", Path::new("/tmp")), "file://box/tmp");
+        }
+    }
+
     #[test]
     fn test_color_sequence_wrappers() {
         let test0 = "\x1b2mhellomynamekeyes\x1b2m"; // BEGIN: \x1b     END: m
@@ -774,6 +1136,19 @@ mod tests {
         assert_eq!(&bresult3, "\\[OH NO\\]");
         assert_eq!(&bresult4, "herpaderp");
         assert_eq!(&bresult5, "");
+
+        for shell in [Shell::Osh, Shell::Ysh] {
+            let result0 = wrap_seq_for_shell(test0.to_string(), shell, '\x1b', 'm');
+            assert_eq!(result0, bresult0);
+        }
+
+        let rresult0 = wrap_seq_for_shell(test0.to_string(), Shell::Rc, '\x1b', 'm');
+        let rresult4 = wrap_seq_for_shell(test4.to_string(), Shell::Rc, '\x1b', 'm');
+        assert_eq!(
+            &rresult0,
+            "\u{1}\x1b2m\u{2}hellomynamekeyes\u{1}\x1b2m\u{2}"
+        );
+        assert_eq!(&rresult4, "herpaderp");
     }
 
     #[test]
@@ -790,6 +1165,81 @@ mod tests {
         assert_eq!(get_command_string_output(case2), "stderr");
     }
 
+    #[test]
+    fn pinned_versions() {
+        assert_eq!(parse_pinned_version("v18.16.0\n"), Some("18.16.0"));
+        assert_eq!(
+            parse_pinned_version("# pinned\n\n3.11\n3.10\n"),
+            Some("3.11")
+        );
+        assert_eq!(parse_pinned_version("lts/*"), None);
+        assert_eq!(parse_pinned_version("stable"), None);
+        assert_eq!(parse_pinned_version(""), None);
+    }
+
+    #[test]
+    fn satisfied_pinned_versions() {
+        assert!(satisfies_pinned_version("3.11.4", "3.11"));
+        assert!(satisfies_pinned_version("v18.16.0\n", "18"));
+        assert!(satisfies_pinned_version("1.72.0-nightly", "1.72"));
+        assert!(!satisfies_pinned_version("3.10.2", "3.11"));
+        assert!(!satisfies_pinned_version("3.11", "3.11.4"));
+        assert!(!satisfies_pinned_version("13.1.0", "1"));
+    }
+
+    #[test]
+    fn parse_durations() {
+        assert_eq!(parse_duration("25m"), Some(Duration::from_secs(25 * 60)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(90 * 60)));
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h5m30s"), Some(Duration::from_secs(3930)));
+    }
+
+    #[test]
+    fn parse_invalid_durations() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("25"), None);
+        assert_eq!(parse_duration("0m"), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("99999999999999999h"), None);
+        assert_eq!(parse_duration("18446744073709551615s1s"), None);
+        assert_eq!(parse_duration("5m3"), None);
+        assert_eq!(parse_duration("5 minutes"), None);
+    }
+
+    #[test]
+    fn cached_output_skips_failures() -> std::io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let mut context = crate::test::default_context();
+        context
+            .env
+            .insert("STARSHIP_CACHE", cache_dir.path().to_string_lossy().into());
+        let interval = Duration::from_secs(60);
+
+        assert_eq!(cached_output(&context, "test/key", interval, || None), None);
+        let output = cached_output(&context, "test/key", interval, || Some("a".into()));
+        assert_eq!(output.as_deref(), Some("a"));
+        let output = cached_output(&context, "test/key", interval, || Some("b".into()));
+        assert_eq!(output.as_deref(), Some("a"));
+        // Once the output is stale, a failed run falls back to it
+        let output = cached_output(&context, "test/key", Duration::ZERO, || None);
+        assert_eq!(output.as_deref(), Some("a"));
+        cache_dir.close()
+    }
+
+    #[test]
+    fn write_file_atomic_replaces_contents() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("cache/durations.toml");
+
+        write_file_atomic(&path, "first")?;
+        write_file_atomic(&path, "second")?;
+        assert_eq!(read_to_string(&path)?, "second");
+        // Only the file itself is left behind, not the temporary one
+        assert_eq!(std::fs::read_dir(path.parent().unwrap())?.count(), 1);
+        dir.close()
+    }
+
     #[test]
     fn sha1_hex() {
         assert_eq!(
@@ -797,4 +1247,34 @@ mod tests {
             "080d09bd815e".to_string()
         );
     }
+
+    #[test]
+    fn executables_are_listed_by_name_and_stem() -> std::io::Result<()> {
+        let first = tempfile::tempdir()?;
+        let second = tempfile::tempdir()?;
+        std::fs::write(first.path().join("Node.EXE"), "")?;
+        std::fs::write(first.path().join("README.txt"), "")?;
+        std::fs::write(second.path().join("node.exe"), "")?;
+        std::fs::write(second.path().join("npm.cmd"), "")?;
+        std::fs::write(second.path().join("npx.cmd"), "")?;
+        std::fs::write(second.path().join("npx.exe"), "")?;
+        let listing = list_executables(
+            &[first.path().to_path_buf(), second.path().to_path_buf()],
+            ".COM;.EXE;.BAT;.CMD",
+        );
+
+        assert_eq!(listing.get("node"), Some(&first.path().join("Node.EXE")));
+        assert_eq!(
+            listing.get("node.exe"),
+            Some(&first.path().join("Node.EXE"))
+        );
+        assert_eq!(listing.get("npm"), Some(&second.path().join("npm.cmd")));
+        // `.exe` comes before `.cmd` in PATHEXT, whichever is listed first
+        assert_eq!(listing.get("npx"), Some(&second.path().join("npx.exe")));
+        assert_eq!(listing.get("npx.cmd"), Some(&second.path().join("npx.cmd")));
+        assert_eq!(listing.get("readme"), None);
+        assert_eq!(listing.get("readme.txt"), None);
+        first.close()?;
+        second.close()
+    }
 }